@@ -173,6 +173,52 @@ fn benchmark_lingua(c: &mut Criterion) {
     group4.finish();
 }
 
+fn benchmark_lingua_with_short_texts(c: &mut Criterion) {
+    let detector = LanguageDetectorBuilder::from_languages(&COMMON_LANGUAGES)
+        .with_preloaded_language_models()
+        .build();
+
+    let words = SENTENCES
+        .iter()
+        .flat_map(|sentence| sentence.split(' '))
+        .collect::<Vec<_>>();
+    let owned_words = words
+        .iter()
+        .map(|word| word.to_string())
+        .collect::<Vec<_>>();
+
+    // The function "owned strings" only calls methods which previous versions of Lingua
+    // provide as well. To compare the current code path with the one of a previous revision,
+    // add this benchmark with just that function to the previous revision and run
+    //
+    //     cargo bench --features benchmark -- --save-baseline previous "single words.*/owned"
+    //
+    // there, followed by the same command with `--baseline previous` in this revision.
+    let mut group = c.benchmark_group("Lingua with single words of common languages");
+    group.bench_function("borrowed strings", |bencher| {
+        bencher.iter(|| {
+            words.iter().for_each(|word| {
+                black_box(detector.detect_language_of_str(word));
+            });
+        });
+    });
+    group.bench_function("owned strings", |bencher| {
+        bencher.iter(|| {
+            owned_words.iter().for_each(|word| {
+                black_box(detector.detect_language_of(word));
+            });
+        });
+    });
+    group.bench_function("confidence values of borrowed strings", |bencher| {
+        bencher.iter(|| {
+            words.iter().for_each(|word| {
+                black_box(detector.compute_language_confidence_values_of_str(word));
+            });
+        });
+    });
+    group.finish();
+}
+
 fn benchmark_whichlang(c: &mut Criterion) {
     let sentences = SENTENCES.repeat(125);
     let mut group = c.benchmark_group("Whichlang");
@@ -273,6 +319,7 @@ criterion_group!(
     benches,
     benchmark_preloading_all_language_models,
     benchmark_lingua,
+    benchmark_lingua_with_short_texts,
     benchmark_whichlang,
     benchmark_whatlang,
     benchmark_cld2
@@ -0,0 +1,243 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    #[cfg(feature = "compiled-models")]
+    compiled_models::generate();
+}
+
+/// Merges the ngram models of all enabled languages into one sorted table per kind of model
+/// and ngram length which is compiled into the binary, so that no JSON needs to be parsed at
+/// runtime. Each ngram maps to the natural logarithms of its relative frequencies in the
+/// languages containing it, identified by their index in the list of enabled languages.
+///
+/// The language model crates publish the directories of their models through their `links`
+/// metadata, so the models are found wherever the crates have been unpacked. A table is
+/// generated for each ngram length and kind of model of which at least one file exists.
+///
+/// Each table is encoded in little-endian byte order as follows:
+///
+/// - the number of ngrams `n` as `u32`
+/// - the number of entries `m` as `u32`
+/// - `n + 1` byte offsets of the ngrams as `u32`
+/// - `n + 1` offsets of the entries of each ngram as `u32`
+/// - `m` log-probabilities as `f64`
+/// - `m` language indices as `u16`
+/// - the UTF-8 bytes of all ngrams in ascending byte order
+#[cfg(feature = "compiled-models")]
+mod compiled_models {
+    use std::cmp::Reverse;
+    use std::collections::{BTreeMap, BinaryHeap};
+    use std::env;
+    use std::fs;
+    use std::io::Read;
+    use std::path::{Path, PathBuf};
+
+    use brotli::Decompressor;
+    use serde_json::Value;
+
+    /// The names of the models of each ngram length, starting with the unigram models.
+    const NGRAM_NAMES: [&str; 8] = [
+        "unigram",
+        "bigram",
+        "trigram",
+        "quadrigram",
+        "fivegram",
+        "sixgram",
+        "sevengram",
+        "eightgram",
+    ];
+
+    /// The kinds of models, identified by whether they contain word boundary markers,
+    /// together with the prefixes of their file names.
+    const MODEL_KINDS: [(bool, &str); 2] = [(false, ""), (true, "word-boundary-")];
+
+    pub(super) fn generate() {
+        let output_directory = PathBuf::from(env::var("OUT_DIR").unwrap());
+        let tables_directory = output_directory.join("compiled-models");
+
+        fs::create_dir_all(&tables_directory).unwrap();
+
+        let languages = enabled_languages();
+        let mut match_arms = String::new();
+        let mut language_names = [String::new(), String::new()];
+
+        for (is_word_boundary_model, prefix) in MODEL_KINDS {
+            let mut compiled_languages = vec![false; languages.len()];
+
+            for (i, ngram_name) in NGRAM_NAMES.iter().enumerate() {
+                let models = languages
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(index, (_, models_directory))| {
+                        let model_file =
+                            models_directory.join(format!("{prefix}{ngram_name}s.json.br"));
+                        model_file
+                            .is_file()
+                            .then(|| (u16::try_from(index).unwrap(), read_model(&model_file)))
+                    })
+                    .collect::<Vec<_>>();
+
+                if models.is_empty() {
+                    continue;
+                }
+                for (index, _) in models.iter() {
+                    compiled_languages[usize::from(*index)] = true;
+                }
+
+                let table_file = tables_directory.join(format!("{prefix}{ngram_name}s.bin"));
+                fs::write(&table_file, encode_table(models)).unwrap();
+                match_arms.push_str(&format!(
+                    "        ({is_word_boundary_model}, {}) => Some(include_bytes!({:?})),\n",
+                    i + 1,
+                    table_file
+                ));
+            }
+
+            language_names[usize::from(is_word_boundary_model)] = languages
+                .iter()
+                .zip(compiled_languages)
+                .filter(|(_, is_compiled)| *is_compiled)
+                .map(|((language, _), _)| format!("    Language::{language},\n"))
+                .collect();
+        }
+
+        let slot_names = languages
+            .iter()
+            .map(|(language, _)| format!("    Language::{language},\n"))
+            .collect::<String>();
+        let [regular_names, word_boundary_names] = language_names;
+
+        let source = format!(
+            "const COMPILED_LANGUAGES: [Language; {}] = [\n{slot_names}];\n\n\
+             const REGULAR_MODEL_LANGUAGES: &[Language] = &[\n{regular_names}];\n\n\
+             const WORD_BOUNDARY_MODEL_LANGUAGES: &[Language] = &[\n{word_boundary_names}];\n\n\
+             fn compiled_table(\n    \
+             is_word_boundary_model: bool,\n    \
+             ngram_length: usize,\n\
+             ) -> Option<&'static [u8]> {{\n    \
+             match (is_word_boundary_model, ngram_length) {{\n{match_arms}        \
+             _ => None,\n    }}\n}}\n",
+            languages.len()
+        );
+
+        fs::write(output_directory.join("compiled_models.rs"), source).unwrap();
+    }
+
+    /// Returns the names of the enabled languages together with the directories
+    /// containing their models, as published by the language model crates in the
+    /// environment variables `DEP_LINGUA_<LANGUAGE>_LANGUAGE_MODEL_MODELS`.
+    fn enabled_languages() -> Vec<(String, PathBuf)> {
+        let mut languages = env::vars()
+            .filter_map(|(variable, value)| {
+                let language = variable
+                    .strip_prefix("DEP_LINGUA_")?
+                    .strip_suffix("_LANGUAGE_MODEL_MODELS")?
+                    .to_lowercase();
+                let models_directory = PathBuf::from(value);
+
+                println!("cargo:rerun-if-changed={}", models_directory.display());
+
+                let mut chars = language.chars();
+                let language = chars.next().unwrap().to_uppercase().chain(chars).collect();
+                Some((language, models_directory))
+            })
+            .collect::<Vec<_>>();
+
+        languages.sort();
+        languages
+    }
+
+    /// Returns the ngrams of a model file in ascending byte order,
+    /// together with the natural logarithms of their relative frequencies.
+    fn read_model(model_file: &Path) -> Vec<(String, f64)> {
+        let compressed_file_content = fs::read(model_file).unwrap();
+        let mut json = String::new();
+        Decompressor::new(compressed_file_content.as_slice(), 4096)
+            .read_to_string(&mut json)
+            .unwrap();
+
+        let model: Value = serde_json::from_str(&json).unwrap();
+        let mut log_probabilities = BTreeMap::new();
+
+        for (fraction, ngrams) in model["ngrams"].as_object().unwrap() {
+            let relative_frequency = match fraction.split_once('/') {
+                Some((numerator, denominator)) => {
+                    numerator.parse::<u64>().unwrap() as f64
+                        / denominator.parse::<u64>().unwrap() as f64
+                }
+                None => fraction.parse::<u64>().unwrap() as f64,
+            };
+            for ngram in ngrams.as_str().unwrap().split(' ') {
+                log_probabilities.insert(ngram.to_string(), relative_frequency.ln());
+            }
+        }
+
+        log_probabilities.into_iter().collect()
+    }
+
+    /// Merges the sorted models of several languages into a single table.
+    fn encode_table(models: Vec<(u16, Vec<(String, f64)>)>) -> Vec<u8> {
+        let mut ngram_offsets = vec![0u32];
+        let mut entry_offsets = vec![0u32];
+        let mut ngrams = vec![];
+        let mut log_probabilities = vec![];
+        let mut language_indices = vec![];
+        let mut entry_count = 0u32;
+
+        let mut heap = models
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, model))| !model.is_empty())
+            .map(|(i, (_, model))| Reverse((model[0].0.as_str(), i, 0)))
+            .collect::<BinaryHeap<_>>();
+
+        while let Some(Reverse((ngram, _, _))) = heap.peek().copied() {
+            while let Some(Reverse((next_ngram, i, position))) = heap.peek().copied() {
+                if next_ngram != ngram {
+                    break;
+                }
+                heap.pop();
+
+                let (language_index, model) = &models[i];
+                log_probabilities.extend_from_slice(&model[position].1.to_le_bytes());
+                language_indices.extend_from_slice(&language_index.to_le_bytes());
+                entry_count += 1;
+
+                if let Some((following_ngram, _)) = model.get(position + 1) {
+                    heap.push(Reverse((following_ngram.as_str(), i, position + 1)));
+                }
+            }
+
+            ngrams.extend_from_slice(ngram.as_bytes());
+            ngram_offsets.push(u32::try_from(ngrams.len()).unwrap());
+            entry_offsets.push(entry_count);
+        }
+
+        let ngram_count = u32::try_from(ngram_offsets.len() - 1).unwrap();
+        let mut table = ngram_count.to_le_bytes().to_vec();
+        table.extend_from_slice(&entry_count.to_le_bytes());
+        for offset in ngram_offsets.into_iter().chain(entry_offsets) {
+            table.extend_from_slice(&offset.to_le_bytes());
+        }
+        table.extend_from_slice(&log_probabilities);
+        table.extend_from_slice(&language_indices);
+        table.extend_from_slice(&ngrams);
+        table
+    }
+}
@@ -16,6 +16,7 @@
 
 use include_dir::{include_dir, Dir};
 
+#[cfg(not(feature = "external-models"))]
 pub const AZERBAIJANI_MODELS_DIRECTORY: Dir = include_dir!("$CARGO_MANIFEST_DIR/models");
 
 pub const AZERBAIJANI_TESTDATA_DIRECTORY: Dir = include_dir!("$CARGO_MANIFEST_DIR/testdata");
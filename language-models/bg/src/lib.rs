@@ -16,6 +16,7 @@
 
 use include_dir::{include_dir, Dir};
 
+#[cfg(not(feature = "external-models"))]
 pub const BULGARIAN_MODELS_DIRECTORY: Dir = include_dir!("$CARGO_MANIFEST_DIR/models");
 
 pub const BULGARIAN_TESTDATA_DIRECTORY: Dir = include_dir!("$CARGO_MANIFEST_DIR/testdata");
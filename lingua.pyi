@@ -15,7 +15,72 @@
 
 from enum import Enum
 from pathlib import Path
-from typing import FrozenSet, Optional, List
+from typing import FrozenSet, Optional, List, Set, Tuple, Union
+
+
+class Alphabet(Enum):
+    """This enum specifies the writing systems recognized by the
+    rule-based engine.
+
+    Some alphabets, such as Kannada or Khmer, are not used by any of the
+    supported languages. They are recognized anyway so that text written
+    in them can be rejected instead of being scored by unrelated language
+    models.
+    """
+
+    ARABIC = 1
+    ARMENIAN = 2
+    BENGALI = 3
+    CYRILLIC = 4
+    DEVANAGARI = 5
+    ETHIOPIC = 6
+    GEORGIAN = 7
+    GREEK = 8
+    GUJARATI = 9
+    GURMUKHI = 10
+    HAN = 11
+    HANGUL = 12
+    HEBREW = 13
+    HIRAGANA = 14
+    KANNADA = 15
+    KATAKANA = 16
+    KHMER = 17
+    LAO = 18
+    LATIN = 19
+    MALAYALAM = 20
+    MYANMAR = 21
+    SINHALA = 22
+    TAMIL = 23
+    TELUGU = 24
+    THAI = 25
+    TIBETAN = 26
+
+    @property
+    def name(self) -> str:
+        """Return the name of this alphabet."""
+
+    def is_supported(self) -> bool:
+        """Return `True` if this alphabet is used by at least one of the
+        languages that have been enabled at compile time.
+        """
+
+
+class ChineseScript(Enum):
+    """This enum specifies the scripts in which Chinese text can be written."""
+
+    SIMPLIFIED = 1
+    TRADITIONAL = 2
+    MIXED = 3
+
+    @property
+    def name(self) -> str:
+        """Return the name of this script."""
+
+    def bcp47_tag(self) -> str:
+        """Return the BCP 47 tag of Chinese written in this script, which is
+        zh-Hans or zh-Hant. Text in mixed scripts is tagged as zh without
+        a script subtag.
+        """
 
 
 class ConfidenceValue:
@@ -33,6 +98,28 @@ class ConfidenceValue:
         """Return the language's confidence value which lies between 0.0 and 1.0."""
 
 
+class DetectionOutcome:
+    """This class describes the outcome of detecting the language of a text."""
+
+    @property
+    def language(self) -> Optional[Language]:
+        """Return the detected language or None if no language has been
+        detected.
+        """
+
+    @property
+    def unsupported_alphabet(self) -> Optional["Alphabet"]:
+        """Return the alphabet of the text if none of the languages of the
+        detector uses it, otherwise None.
+        """
+
+    @property
+    def is_undecided(self) -> bool:
+        """Return whether the language of the text cannot be reliably detected
+        although it is written in an alphabet of the languages of the detector.
+        """
+
+
 class DetectionResult:
     """This class describes a contiguous single-language
     text section within a possibly mixed-language text.
@@ -70,6 +157,9 @@ class DetectionResult:
 class Language(Enum):
     """This enum specifies the so far 75 supported languages which can be
     detected by *Lingua*.
+
+    Further languages can be registered at runtime with
+    `Language.register_custom`.
     """
 
     AFRIKAANS = 1
@@ -198,6 +288,36 @@ class Language(Enum):
             ValueError: if there is no language for the given ISO code
         """
 
+    @classmethod
+    def register_custom(
+        cls,
+        name: str,
+        iso_code_639_1: str,
+        iso_code_639_3: str,
+        alphabets: List["Alphabet"],
+        models_directory: Path,
+    ) -> "Language":
+        """Register a language which is not built into *Lingua* and return it.
+
+        The language models are loaded lazily from the given directory which
+        must contain the files written by `LanguageModelFilesWriter` for this
+        language. Afterwards, the language and its ISO codes are available as
+        attributes of the classes `Language`, `IsoCode639_1` and `IsoCode639_3`.
+        Registering exactly the same language again returns the existing one.
+
+        Args:
+            name: The name of the language consisting of ASCII letters only.
+            iso_code_639_1: The two-letter ISO 639-1 code of the language.
+            iso_code_639_3: The three-letter ISO 639-3 code of the language.
+            alphabets: The alphabets the language is written in.
+            models_directory: The directory containing the language models.
+
+        Raises:
+            ValueError: if the name or the ISO codes are malformed, if no
+                alphabet is given or if the name or one of the ISO codes is
+                already used by another language
+        """
+
 
 class IsoCode639_1(Enum):
     """This enum specifies the ISO 639-1 code representations for the
@@ -376,6 +496,50 @@ class LanguageDetector:
         This helps to free allocated memory previously consumed by the models.
         """
 
+    def write_snapshot(self, file_path: Path):
+        """Write this detector to a snapshot file from which it can be restored
+        with from_snapshot without decompressing and parsing the language
+        models again.
+
+        The snapshot contains the settings of this detector and all models
+        it uses. Models which have not been loaded yet are loaded before.
+
+        Args:
+            file_path: The path of the snapshot file to write.
+
+        Raises:
+            OSError: if the file cannot be written
+        """
+
+    @classmethod
+    def from_snapshot(cls, file_path: Path) -> "LanguageDetector":
+        """Restore a detector from a snapshot file written by write_snapshot.
+
+        Args:
+            file_path: The path of the snapshot file to read.
+
+        Raises:
+            ValueError: if the file is not a snapshot, if it has been written
+                by another version of this library, with other language models or
+                other built-in languages enabled, or if one of its custom languages
+                has not been registered with the same ISO codes
+            OSError: if the file cannot be read
+        """
+
+    def preloading_progress(self) -> Optional["PreloadingProgress"]:
+        """Return how far the language models have been preloaded if this
+        detector has been built with preloaded language models, or `None`
+        otherwise.
+        """
+
+    def model_status(self) -> "ModelStatus":
+        """Return which language models of this detector have been loaded
+        and which have failed to load.
+
+        Models are loaded on demand, so a model which has neither been
+        loaded nor failed to load is not needed yet.
+        """
+
     def detect_language_of(self, text: str) -> Optional[Language]:
         """Detect the language of text.
 
@@ -393,6 +557,75 @@ class LanguageDetector:
             reliably detected, `None` is returned.
         """
 
+    def detect_unsupported_alphabet_of(self, text: str) -> Optional[Alphabet]:
+        """Detect the alphabet of text if none of the languages of this
+        LanguageDetector instance is written in it.
+
+        Text written in an alphabet that is not used by any of the configured
+        languages cannot be classified. This method allows to tell such text
+        apart from text whose language is merely ambiguous.
+
+        Args:
+            text (str): The text whose alphabet should be identified.
+
+        Returns:
+            The unsupported alphabet of the text. If the predominant alphabet
+            of the text is used by at least one of the configured languages,
+            or if no alphabet can be determined, `None` is returned.
+        """
+
+    def detect_language_outcome_of(self, text: str) -> DetectionOutcome:
+        """Detect the language of given input text and tell apart why no
+        language has been detected, if so.
+
+        detect_language_of returns None both for text whose language cannot be
+        reliably detected and for text written in an alphabet which none of
+        the languages of this detector uses. The returned outcome tells both
+        cases apart.
+
+        Args:
+            text (str): The text whose language should be identified.
+
+        Returns:
+            The outcome holding either the detected language or the
+            unsupported alphabet of the text, or neither if the language
+            cannot be reliably detected.
+        """
+
+    def detect_chinese_script_of(self, text: str) -> Optional[ChineseScript]:
+        """Detect whether the given input text is written in Simplified or
+        Traditional Chinese.
+
+        The language is detected first as by `detect_language_of`. If it is
+        Chinese, the characters which only occur in one of both scripts are
+        counted. ChineseScript.MIXED is returned if both scripts make up a
+        substantial share of them.
+
+        Args:
+            text (str): The text whose script should be identified.
+
+        Returns:
+            The script of the text. If the language is not Chinese or if the
+            text consists of characters shared by both scripts only, `None`
+            is returned.
+        """
+
+    def detect_collapsed_language_of(
+        self,
+        text: str
+    ) -> Optional[Union[Language, "MacroLanguage"]]:
+        """Detect the language of the given input text, collapsing the languages
+        of the macrolanguages configured with
+        LanguageDetectorBuilder.with_macro_languages into their macrolanguage.
+
+        Args:
+            text (str): The text whose language should be identified.
+
+        Returns:
+            The identified language or macrolanguage. If it cannot be reliably
+            detected, `None` is returned.
+        """
+
     def detect_languages_in_parallel_of(self, texts: List[str]) -> List[Optional[Language]]:
         """Detect the languages of all given input texts.
 
@@ -581,6 +814,63 @@ class LanguageDetector:
             A list of float values between 0.0 and 1.0.
         """
 
+    def detect_language_variant_of(self, text: str) -> Optional[str]:
+        """Detect the regional variant of the language of given input text
+        and return its BCP 47 tag, such as pt-BR or pt-PT.
+
+        The language is detected first as by `detect_language_of`. If the
+        language cannot be reliably detected, if no variants of it have been
+        loaded or if its most likely variants are equally likely, None is
+        returned.
+
+        Args:
+            text (str): The text whose language variant should be identified.
+
+        Returns:
+            The BCP 47 tag of the identified variant, if any.
+        """
+
+    def compute_language_variant_confidence_values(
+        self,
+        text: str
+    ) -> List[Tuple[str, float]]:
+        """Compute confidence values for the regional variants of the language
+        of the given text.
+
+        A list of tuples is returned, each consisting of the BCP 47 tag of a
+        variant and its confidence value. The entries are sorted by their
+        confidence value in descending order and sum to 1.0. If the language
+        cannot be reliably detected or if no variants of it have been loaded,
+        the list is empty.
+
+        Args:
+            text (str): The text for which to compute the confidence values.
+
+        Returns:
+            A list of tuples of BCP 47 tags and float values between 0.0 and 1.0.
+        """
+
+    def compute_collapsed_language_confidence_values(
+        self,
+        text: str
+    ) -> List[Tuple[Union[Language, "MacroLanguage"], float]]:
+        """Compute confidence values for each language supported by this
+        detector for the given input text, collapsing the languages of the
+        macrolanguages configured with LanguageDetectorBuilder.with_macro_languages
+        into their macrolanguage.
+
+        The confidence value of a macrolanguage is the sum of the values of
+        its individual languages. The entries are sorted by their confidence
+        value in descending order.
+
+        Args:
+            text (str): The text for which to compute the confidence values.
+
+        Returns:
+            A list of tuples of languages or macrolanguages and float values
+            between 0.0 and 1.0.
+        """
+
 
 class LanguageDetectorBuilder:
     """This class configures and creates an instance of LanguageDetector."""
@@ -689,6 +979,18 @@ class LanguageDetectorBuilder:
         method allows to switch between these two loading modes.
         """
 
+    def with_preloaded_language_models_in_background(
+        self
+    ) -> "LanguageDetectorBuilder":
+        """Preload all language models on a background thread after creating
+        the LanguageDetector instance.
+
+        Unlike with_preloaded_language_models, this does not block build
+        until all models have been loaded. Until the preloading is complete,
+        the detector loads the models it needs on demand. The progress can
+        be polled with LanguageDetector.preloading_progress.
+        """
+
     def with_low_accuracy_mode(self) -> "LanguageDetectorBuilder":
         """Disable the high accuracy mode in order to save memory
         and increase performance.
@@ -705,13 +1007,385 @@ class LanguageDetectorBuilder:
         will remain mostly unaffected.
         """
 
+    def with_accent_insensitive_mode(self) -> "LanguageDetectorBuilder":
+        """Enable the accent-insensitive mode in order to classify text
+        whose diacritics have been stripped.
+
+        Text from sources such as search queries, SMS or old ASCII-only
+        systems often lacks the diacritics of the language it is written in.
+        As the language models are built from correctly accented text,
+        such input is frequently misclassified.
+
+        This method removes diacritics from both the input text and the
+        language models of all languages written in the Latin or Greek
+        script before comparing them. Rules based on characters unique
+        to a language still see the original input text.
+        """
+
+    def with_derived_character_rules(self) -> "LanguageDetectorBuilder":
+        """Derive the character rules of the rule-based engine from the
+        language models instead of using the hand-maintained tables.
+
+        Before the language models are consulted, Lingua identifies or
+        filters languages by characters which occur in only one or a few
+        languages. By default, these characters are taken from hand-maintained
+        tables. This method derives them from the unigram models of the
+        languages of the detector instead, including custom languages,
+        so the rules are always consistent with the models. A character
+        is unique to a language if it occurs in none of the other languages
+        of the detector.
+        """
+
+    def with_word_boundary_ngrams(self) -> "LanguageDetectorBuilder":
+        """Use language models whose ngrams include word boundary markers.
+
+        By default, ngrams are extracted from inside of words only, so ngrams
+        at the start or the end of a word cannot be distinguished from the same
+        ngrams in the middle of a word. The word boundary models are not bundled
+        with the built-in languages, so this option is restricted to custom
+        languages. Building the detector raises a ValueError if the models
+        are missing for any of its languages.
+        """
+
+    def with_ngram_lengths(
+        self,
+        short_text_ngram_lengths: Tuple[int, int],
+        long_text_ngram_lengths: Tuple[int, int],
+    ) -> "LanguageDetectorBuilder":
+        """Use the given ngram lengths for the classification of short and long text.
+
+        By default, unigrams up to fivegrams are used for text consisting of
+        less than 120 characters, whereas only trigrams are used for longer
+        text. Higher-order models, such as sixgrams or sevengrams, can improve
+        the accuracy for long text. Ngram lengths for which no model is
+        available for a language are skipped.
+
+        Args:
+            short_text_ngram_lengths: The smallest and the largest ngram length
+                used for short text.
+            long_text_ngram_lengths: The smallest and the largest ngram length
+                used for long text and in low accuracy mode.
+
+        Raises:
+            ValueError: if one of the ranges is empty or does not lie in
+                between 1 and 8
+        """
+
+    def with_add_k_smoothing(self, k: float) -> "LanguageDetectorBuilder":
+        """Estimate the ngram probabilities with add-k smoothing.
+
+        The constant k is added to the frequency of each ngram, including
+        ngrams which do not occur in the training data. Smoothing requires the
+        ngram count files of all languages of the detector. They are not bundled
+        with the built-in languages, so smoothing is restricted to custom
+        languages. Building the detector raises a ValueError if they are
+        missing for any of its languages.
+
+        Args:
+            k: The constant added to the frequency of each ngram.
+
+        Raises:
+            ValueError: if k is not greater than 0.0
+        """
+
+    def with_witten_bell_smoothing(self) -> "LanguageDetectorBuilder":
+        """Estimate the ngram probabilities with Witten-Bell smoothing.
+
+        Smoothing requires the ngram count files of all languages of the
+        detector. They are not bundled with the built-in languages, so smoothing
+        is restricted to custom languages. Building the detector raises a
+        ValueError if they are missing for any of its languages.
+        """
+
+    def with_kneser_ney_smoothing(self, discount: float = 0.75) -> "LanguageDetectorBuilder":
+        """Estimate the ngram probabilities with interpolated Kneser-Ney smoothing.
+
+        Smoothing requires the ngram count files of all languages of the
+        detector. They are not bundled with the built-in languages, so smoothing
+        is restricted to custom languages. Building the detector raises a
+        ValueError if they are missing for any of its languages.
+
+        Args:
+            discount: The absolute discount subtracted from the frequency
+                of each ngram.
+
+        Raises:
+            ValueError: if discount does not lie in between 0.0 and 1.0
+        """
+
+    def with_word_model(self, weight: float = 0.5) -> "LanguageDetectorBuilder":
+        """Combine the ngram models with the relative frequencies of whole words
+        for text consisting of one or two words.
+
+        The summed log-probabilities of the words are interpolated with those
+        of the ngrams, using the given weight for the words. The word models are
+        not bundled with the built-in languages, so this option is restricted to
+        custom languages. Building the detector raises a ValueError if they are
+        missing for any of its languages.
+
+        Args:
+            weight: The weight of the word models in between 0.0 and 1.0.
+
+        Raises:
+            ValueError: if weight is not greater than 0.0 or greater than 1.0
+        """
+
+    def with_language_variants(self, models_directory: Path) -> "LanguageDetectorBuilder":
+        """Load the models of regional language variants, such as pt-BR and pt-PT,
+        from the subdirectories of the given directory.
+
+        The subdirectories are named after the BCP 47 tags of the variants and
+        are created by LanguageModelFilesWriter.write_language_variant_model_files.
+        Variants of languages which this detector has not been built from are
+        ignored.
+
+        Args:
+            models_directory: The directory containing the variant models.
+
+        Raises:
+            ValueError: if models_directory is not an existing directory
+        """
+
+    def with_pair_discriminators(self, models_directory: Path) -> "LanguageDetectorBuilder":
+        """Load discriminators for pairs of closely related languages, such as
+        Bokmal and Nynorsk or Malay and Indonesian, from the given directory.
+
+        The discriminators are created by
+        LanguageModelFilesWriter.write_pair_discriminator_file. No
+        discriminators are bundled with this library, so they have to be
+        trained from your own text of both languages of each pair before
+        this stage has any effect.
+        Only the discriminators whose languages are both languages of this
+        detector are loaded. If the two most likely languages of a text form
+        such a pair, their discriminator decides how their combined confidence
+        value is split between them, which may change their ranking.
+
+        Args:
+            models_directory: The directory containing the discriminators.
+
+        Raises:
+            ValueError: if models_directory is not an existing directory
+        """
+
+    def with_macro_languages(
+        self,
+        *macro_languages: "MacroLanguage"
+    ) -> "LanguageDetectorBuilder":
+        """Collapse the languages of the given macrolanguages into their
+        macrolanguage, such as Bokmal and Nynorsk into Norwegian.
+
+        The macrolanguages are reported by the methods
+        LanguageDetector.detect_collapsed_language_of and
+        LanguageDetector.compute_collapsed_language_confidence_values,
+        which sum up the confidence values of their individual languages.
+        The fine-grained distribution remains available through the other
+        methods of the detector.
+
+        Raises:
+            ValueError: if no macrolanguage is passed
+        """
+
+    def with_memory_budget(self, bytes: int) -> "LanguageDetectorBuilder":
+        """Keep the memory occupied by the loaded language models below the
+        given number of bytes.
+
+        By default, lazily loaded language models stay in memory until
+        LanguageDetector.unload_language_models is called. With a memory
+        budget, the sizes of the loaded models are estimated and the least
+        recently used models of a language and ngram length are evicted once
+        the budget is exceeded. Evicted models are loaded again when they are
+        needed.
+
+        The budget applies to each detector separately and covers only the
+        models it has used. As all detectors share the same language models,
+        a model evicted by one detector is loaded again by any other detector
+        which still needs it, even if that detector has preloaded its models.
+
+        Args:
+            bytes: The maximum number of bytes of the loaded language models.
+
+        Raises:
+            ValueError: if bytes is 0
+        """
+
+    def with_normalizers(self, normalizers: List["Normalizer"]) -> "LanguageDetectorBuilder":
+        """Apply the given normalizers to the input text before it is split
+        into words.
+
+        By default, the input text is only trimmed and lowercased. Text from
+        social media or user input often contains invisible control characters,
+        fullwidth letters or elongated words such as "sooooo" which do not occur
+        in the training data. The normalizers are applied in the given order.
+        """
+
     def build(self) -> LanguageDetector:
-        """Create and return the configured LanguageDetector instance."""
+        """Create and return the configured LanguageDetector instance.
+
+        Raises:
+            ValueError: if the models required by one of the configured
+                modes are missing for any of the languages
+        """
+
+
+class MacroLanguage(Enum):
+    """This enum specifies the macrolanguages which comprise several
+    closely related languages.
+    """
+
+    MALAY = 1
+    NORWEGIAN = 2
+    SERBO_CROATIAN = 3
+
+    @property
+    def name(self) -> str:
+        """Return the name of this macrolanguage."""
+
+    def iso_code_639_3(self) -> str:
+        """Return the ISO 639-3 code of this macrolanguage, such as nor for
+        Norwegian.
+        """
+
+    def languages(self) -> Set[Language]:
+        """Return the individual languages which this macrolanguage comprises."""
+
+
+class Normalizer(Enum):
+    """This enum specifies the text normalization steps which can be
+    applied to input text before it is split into words.
+    """
+
+    NFC = 1
+    NFKC = 2
+    REMOVE_INVISIBLE_CHARACTERS = 3
+    FOLD_FULLWIDTH_CHARACTERS = 4
+    SQUASH_ELONGATION = 5
+    STRIP_NUMERALS = 6
+    CLEAN_MARKUP = 7
+
+    @property
+    def name(self) -> str:
+        """Return the name of this normalizer."""
+
+
+class PreloadingProgress:
+    """This class describes how far the language models of a detector
+    have been preloaded.
+    """
+
+    @property
+    def loaded_language_count(self) -> int:
+        """Return the number of languages whose models have been preloaded."""
+
+    @property
+    def total_language_count(self) -> int:
+        """Return the number of languages whose models are preloaded in total."""
+
+    def is_complete(self) -> bool:
+        """Return True if the models of all languages have been preloaded."""
+
+
+class ModelKind(Enum):
+    """This enum specifies the kinds of models which a language detector loads."""
+
+    NGRAMS = 1
+    NGRAM_COUNTS = 2
+    WORDS = 3
+
+    @property
+    def name(self) -> str:
+        """Return the name of this kind of model."""
+
+
+class ModelLoadFailure:
+    """This class describes a language model of a single ngram length
+    which could not be loaded.
+    """
+
+    @property
+    def language(self) -> Language:
+        """Return the language of the model."""
+
+    @property
+    def kind(self) -> ModelKind:
+        """Return the kind of the model."""
+
+    @property
+    def ngram_length(self) -> int:
+        """Return the ngram length of the model, which is 0 for word models."""
+
+    @property
+    def error(self) -> str:
+        """Return the reason why the model could not be loaded."""
+
+
+class ModelStatus:
+    """This class reports which ngram models of the languages of a detector
+    have been loaded and which models of any kind have failed to load.
+    """
+
+    @property
+    def loaded_models(self) -> List[Tuple[Language, int]]:
+        """Return the languages and ngram lengths of the models which have
+        been loaded, sorted by language and ngram length.
+        """
+
+    @property
+    def failures(self) -> List[ModelLoadFailure]:
+        """Return the models which have failed to load,
+        sorted by language, kind of model and ngram length.
+        """
+
+    def is_healthy(self) -> bool:
+        """Return True if no model has failed to load."""
 
 
 class LanguageModelFilesWriter:
     """This class creates language model files and writes them to a directory."""
 
+    def __init__(self) -> None:
+        """Create a new writer which builds the language models from the
+        unmodified input text.
+        """
+
+    def with_normalizers(
+        self, normalizers: List[Normalizer]
+    ) -> "LanguageModelFilesWriter":
+        """Apply the given normalizers to each line of the input text before
+        the language models are built from it.
+
+        The normalizers should be the same as the ones passed to
+        LanguageDetectorBuilder.with_normalizers so that the models match
+        the text they are compared with.
+        """
+
+    def with_word_boundary_ngrams(self) -> "LanguageModelFilesWriter":
+        """Surround each word with boundary markers, so that ngrams at the start
+        or the end of a word are distinguished from the same ngrams inside of
+        it. The language model files are named word-boundary-unigrams.json.br
+        and so on.
+        """
+
+    def with_ngram_counts(self) -> "LanguageModelFilesWriter":
+        """Additionally write the absolute frequencies of all ngrams to files
+        named unigram-counts.json.br and so on, which smoothing is computed from.
+        """
+
+    def with_word_model(self) -> "LanguageModelFilesWriter":
+        """Additionally write the relative frequencies of whole words
+        to a file named words.json.br.
+        """
+
+    def with_maximum_ngram_length(
+        self, maximum_ngram_length: int
+    ) -> "LanguageModelFilesWriter":
+        """Create language models for all ngram lengths from 1 up to and
+        including the given maximum ngram length. By default, unigram up to
+        fivegram models are created.
+
+        Raises:
+            ValueError: if the maximum ngram length does not lie in between 1 and 8
+        """
+
     @classmethod
     def create_and_write_language_model_files(
         cls,
@@ -720,7 +1394,8 @@ class LanguageModelFilesWriter:
         language: Language,
         char_class: str,
     ):
-        """Create language model files and write them to a directory.
+        """Create language model files with the default configuration and write
+        them to a directory.
 
         Args:
             input_file_path: The path to a txt file used for language
@@ -739,6 +1414,94 @@ class LanguageModelFilesWriter:
                 be compiled to a valid regular expression
         """
 
+    def write_language_model_files(
+        self,
+        input_file_path: Path,
+        output_directory_path: Path,
+        language: Language,
+        char_class: str,
+    ):
+        """Create language model files with the configuration of this writer
+        and write them to a directory.
+
+        Args:
+            input_file_path: The path to a txt file used for language
+                model creation. The assumed encoding of the txt file is UTF-8.
+            output_directory_path: The path to an existing directory where the
+                language model files are to be written.
+            language: The language for which to create language models.
+            char_class: A regex character class such as \\p{L} to restrict the
+                set of characters that the language models are built from.
+
+        Raises:
+            Exception: under the same conditions as
+                create_and_write_language_model_files
+        """
+
+    def write_language_variant_model_files(
+        self,
+        input_file_path: Path,
+        output_directory_path: Path,
+        variant: str,
+        char_class: str,
+    ):
+        """Create the language model files of a regional language variant with
+        the configuration of this writer and write them to a subdirectory of
+        the given output directory which is named after the variant's
+        BCP 47 tag.
+
+        A word model is always written in addition to the ngram models.
+
+        Args:
+            input_file_path: The path to a txt file used for language
+                model creation. The assumed encoding of the txt file is UTF-8.
+            output_directory_path: The path to an existing directory in which
+                the subdirectory of the variant is created.
+            variant: The BCP 47 tag of the variant, such as pt-BR.
+            char_class: A regex character class such as \\p{L} to restrict the
+                set of characters that the language models are built from.
+
+        Raises:
+            ValueError: if the variant is not a valid BCP 47 tag consisting of
+                a supported ISO 639-1 code and a region subtag
+            Exception: under the same conditions as
+                create_and_write_language_model_files
+        """
+
+    def write_pair_discriminator_file(
+        self,
+        first_input_file_path: Path,
+        second_input_file_path: Path,
+        output_directory_path: Path,
+        first_language: Language,
+        second_language: Language,
+        char_class: str,
+    ):
+        """Train a discriminator for a pair of closely related languages with
+        the configuration of this writer and write it to a file named after
+        the ISO 639-1 codes of both languages, such as nb-nn.json.br.
+
+        The normalizers of this writer are applied to each line of the input
+        texts before the discriminator is trained on them.
+
+        Args:
+            first_input_file_path: The path to a txt file containing text
+                written in the first language, encoded in UTF-8.
+            second_input_file_path: The path to a txt file containing text
+                written in the second language, encoded in UTF-8.
+            output_directory_path: The path to an existing directory where
+                the discriminator file is to be written.
+            first_language: The language of the first input file.
+            second_language: The language of the second input file.
+            char_class: A regex character class such as \\p{L} to restrict the
+                set of characters that the discriminator is built from.
+
+        Raises:
+            Exception: if both languages are the same; under the same
+                conditions as create_and_write_language_model_files for
+                any of the input files and the output directory
+        """
+
 
 class TestDataFilesWriter:
     """This class creates test data files for accuracy report generation
@@ -15,6 +15,7 @@
  */
 
 use std::collections::HashMap;
+use std::fmt::{Display, Formatter, Result};
 
 use ahash::AHashSet;
 use once_cell::sync::Lazy;
@@ -23,13 +24,23 @@ use strum_macros::EnumIter;
 
 use crate::language::Language;
 
-#[derive(EnumIter, Eq, PartialEq, Hash)]
-pub(crate) enum Alphabet {
+/// This enum specifies the writing systems recognized by the rule-based engine.
+///
+/// Some alphabets, such as [`Alphabet::Kannada`] or [`Alphabet::Khmer`], are not used by
+/// any of the supported languages. They are recognized anyway so that text written in them
+/// can be rejected instead of being scored by unrelated language models.
+#[derive(Clone, Copy, Debug, EnumIter, Eq, PartialEq, Hash, Ord, PartialOrd)]
+#[cfg_attr(
+    feature = "python",
+    pyo3::prelude::pyclass(eq, eq_int, frozen, hash, ord, rename_all = "UPPERCASE")
+)]
+pub enum Alphabet {
     Arabic,
     Armenian,
     Bengali,
     Cyrillic,
     Devanagari,
+    Ethiopic,
     Georgian,
     Greek,
     Gujarati,
@@ -38,23 +49,69 @@ pub(crate) enum Alphabet {
     Hangul,
     Hebrew,
     Hiragana,
+    Kannada,
     Katakana,
+    Khmer,
+    Lao,
     Latin,
+    Malayalam,
+    Myanmar,
+    Sinhala,
     Tamil,
     Telugu,
     Thai,
+    Tibetan,
+}
+
+impl Display for Alphabet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.write_str(match self {
+            Alphabet::Arabic => "Arabic",
+            Alphabet::Armenian => "Armenian",
+            Alphabet::Bengali => "Bengali",
+            Alphabet::Cyrillic => "Cyrillic",
+            Alphabet::Devanagari => "Devanagari",
+            Alphabet::Ethiopic => "Ethiopic",
+            Alphabet::Georgian => "Georgian",
+            Alphabet::Greek => "Greek",
+            Alphabet::Gujarati => "Gujarati",
+            Alphabet::Gurmukhi => "Gurmukhi",
+            Alphabet::Han => "Han",
+            Alphabet::Hangul => "Hangul",
+            Alphabet::Hebrew => "Hebrew",
+            Alphabet::Hiragana => "Hiragana",
+            Alphabet::Kannada => "Kannada",
+            Alphabet::Katakana => "Katakana",
+            Alphabet::Khmer => "Khmer",
+            Alphabet::Lao => "Lao",
+            Alphabet::Latin => "Latin",
+            Alphabet::Malayalam => "Malayalam",
+            Alphabet::Myanmar => "Myanmar",
+            Alphabet::Sinhala => "Sinhala",
+            Alphabet::Tamil => "Tamil",
+            Alphabet::Telugu => "Telugu",
+            Alphabet::Thai => "Thai",
+            Alphabet::Tibetan => "Tibetan",
+        })
+    }
 }
 
 impl Alphabet {
-    pub fn matches(&self, text: &str) -> bool {
+    /// Returns `true` if this alphabet is used by at least one of the languages
+    /// that have been enabled by cargo features.
+    pub fn is_supported(&self) -> bool {
+        !self.supported_languages().is_empty()
+    }
+
+    pub(crate) fn matches(&self, text: &str) -> bool {
         self.char_set().is_match(text)
     }
 
-    pub fn matches_char(&self, ch: char) -> bool {
+    pub(crate) fn matches_char(&self, ch: char) -> bool {
         self.char_set().is_char_match(ch)
     }
 
-    pub fn all_supporting_single_language() -> HashMap<Alphabet, Language> {
+    pub(crate) fn all_supporting_single_language() -> HashMap<Alphabet, Language> {
         let mut alphabets = HashMap::new();
         for alphabet in Alphabet::iter() {
             let supported_languages = alphabet.supported_languages();
@@ -82,6 +139,7 @@ impl Alphabet {
             Alphabet::Bengali => &BENGALI,
             Alphabet::Cyrillic => &CYRILLIC,
             Alphabet::Devanagari => &DEVANAGARI,
+            Alphabet::Ethiopic => &ETHIOPIC,
             Alphabet::Georgian => &GEORGIAN,
             Alphabet::Greek => &GREEK,
             Alphabet::Gujarati => &GUJARATI,
@@ -90,11 +148,18 @@ impl Alphabet {
             Alphabet::Hangul => &HANGUL,
             Alphabet::Hebrew => &HEBREW,
             Alphabet::Hiragana => &HIRAGANA,
+            Alphabet::Kannada => &KANNADA,
             Alphabet::Katakana => &KATAKANA,
+            Alphabet::Khmer => &KHMER,
+            Alphabet::Lao => &LAO,
             Alphabet::Latin => &LATIN,
+            Alphabet::Malayalam => &MALAYALAM,
+            Alphabet::Myanmar => &MYANMAR,
+            Alphabet::Sinhala => &SINHALA,
             Alphabet::Tamil => &TAMIL,
             Alphabet::Telugu => &TELUGU,
             Alphabet::Thai => &THAI,
+            Alphabet::Tibetan => &TIBETAN,
         }
     }
 }
@@ -142,6 +207,7 @@ static ARMENIAN: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Armenian
 static BENGALI: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Bengali"));
 static CYRILLIC: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Cyrillic"));
 static DEVANAGARI: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Devanagari"));
+static ETHIOPIC: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Ethiopic"));
 static GEORGIAN: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Georgian"));
 static GREEK: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Greek"));
 static GUJARATI: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Gujarati"));
@@ -150,8 +216,15 @@ static HAN: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Han"));
 static HANGUL: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Hangul"));
 static HEBREW: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Hebrew"));
 static HIRAGANA: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Hiragana"));
+static KANNADA: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Kannada"));
 static KATAKANA: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Katakana"));
+static KHMER: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Khmer"));
+static LAO: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Lao"));
 static LATIN: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Latin"));
+static MALAYALAM: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Malayalam"));
+static MYANMAR: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Myanmar"));
+static SINHALA: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Sinhala"));
 static TAMIL: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Tamil"));
 static TELUGU: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Telugu"));
 static THAI: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Thai"));
+static TIBETAN: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Tibetan"));
@@ -15,10 +15,25 @@
  */
 
 use std::collections::HashSet;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
-use crate::detector::LanguageDetector;
+use itertools::Itertools;
+
+use crate::detector::{DetectorSettings, LanguageDetector};
 use crate::isocode::{IsoCode639_1, IsoCode639_3};
+use crate::json::{has_ngram_counts, has_word_boundary_models, has_word_model};
+#[cfg(feature = "external-models")]
+use crate::json::{
+    missing_model_files, models_directory, set_models_directory, MISSING_MODELS_DIRECTORY_MESSAGE,
+};
 use crate::language::Language;
+use crate::macrolanguage::MacroLanguage;
+use crate::ngram::MAXIMUM_NGRAM_LENGTH;
+use crate::normalizer::Normalizer;
+use crate::smoothing::Smoothing;
+use crate::tokenizer::{DefaultTokenizer, Tokenizer};
 
 pub(crate) const MISSING_LANGUAGE_MESSAGE: &str =
     "LanguageDetector needs at least 2 languages to choose from";
@@ -26,6 +41,44 @@ pub(crate) const MISSING_LANGUAGE_MESSAGE: &str =
 pub(crate) const MINIMUM_RELATIVE_DISTANCE_MESSAGE: &str =
     "Minimum relative distance must lie in between 0.0 and 0.99";
 
+pub(crate) const NGRAM_LENGTHS_MESSAGE: &str =
+    "Ngram lengths must be non-empty ranges lying in between 1 and 8";
+
+pub(crate) const ADD_K_SMOOTHING_MESSAGE: &str =
+    "The constant k of add-k smoothing must be greater than 0.0";
+
+pub(crate) const KNESER_NEY_SMOOTHING_MESSAGE: &str =
+    "The discount of Kneser-Ney smoothing must lie in between 0.0 and 1.0";
+
+pub(crate) const WORD_MODEL_WEIGHT_MESSAGE: &str =
+    "Word model weight must be greater than 0.0 and at most 1.0";
+
+pub(crate) const LANGUAGE_VARIANTS_DIRECTORY_MESSAGE: &str =
+    "Language variant models directory must be an existing directory";
+
+pub(crate) const PAIR_DISCRIMINATORS_DIRECTORY_MESSAGE: &str =
+    "Pair discriminator models directory must be an existing directory";
+
+pub(crate) const MACRO_LANGUAGES_MESSAGE: &str = "At least one macro language must be specified";
+
+pub(crate) const MEMORY_BUDGET_MESSAGE: &str = "Memory budget must be greater than 0 bytes";
+
+pub(crate) const MISSING_WORD_BOUNDARY_MODELS_MESSAGE: &str =
+    "Word boundary ngrams cannot be used as the word boundary models are missing for";
+
+pub(crate) const UNBUNDLED_MODELS_MESSAGE: &str =
+    "These models are not bundled with the built-in languages, so they can only be used for custom languages and with the models directory of the crate feature `external-models`";
+
+pub(crate) const MISSING_NGRAM_COUNTS_MESSAGE: &str =
+    "Smoothing cannot be applied as the ngram counts are missing for";
+
+pub(crate) const MISSING_WORD_MODELS_MESSAGE: &str =
+    "Word models cannot be used as they are missing for";
+
+#[cfg(feature = "external-models")]
+pub(crate) const MODELS_DIRECTORY_MESSAGE: &str =
+    "Language models directory must be an existing directory";
+
 /// This struct configures and creates an instance of [LanguageDetector].
 #[derive(Clone)]
 #[cfg_attr(feature = "python", pyo3::prelude::pyclass)]
@@ -33,7 +86,23 @@ pub struct LanguageDetectorBuilder {
     languages: HashSet<Language>,
     minimum_relative_distance: f64,
     is_every_language_model_preloaded: bool,
+    is_every_language_model_preloaded_in_background: bool,
     is_low_accuracy_mode_enabled: bool,
+    is_accent_insensitive_mode_enabled: bool,
+    is_derived_character_rules_enabled: bool,
+    is_word_boundary_mode_enabled: bool,
+    short_text_ngram_lengths: RangeInclusive<usize>,
+    long_text_ngram_lengths: RangeInclusive<usize>,
+    smoothing: Smoothing,
+    word_model_weight: f64,
+    normalizers: Vec<Normalizer>,
+    tokenizer: Arc<dyn Tokenizer>,
+    language_variants_directory: Option<PathBuf>,
+    pair_discriminators_directory: Option<PathBuf>,
+    macro_languages: HashSet<MacroLanguage>,
+    memory_budget: Option<usize>,
+    #[cfg(feature = "external-models")]
+    models_directory: Option<PathBuf>,
 }
 
 impl LanguageDetectorBuilder {
@@ -165,6 +234,24 @@ impl LanguageDetectorBuilder {
     /// service response. This method allows to switch between these two loading modes.
     pub fn with_preloaded_language_models(&mut self) -> &mut Self {
         self.is_every_language_model_preloaded = true;
+        self.is_every_language_model_preloaded_in_background = false;
+        self
+    }
+
+    /// Configures `LanguageDetectorBuilder` to preload all language models on a background
+    /// thread after creating the instance of [LanguageDetector].
+    ///
+    /// Unlike [`with_preloaded_language_models`](#method.with_preloaded_language_models),
+    /// this does not block [`build`](#method.build) until all models have been loaded.
+    /// Until the preloading is complete, the detector loads the models it needs on demand
+    /// as in the lazy-loading mode. The progress can be polled with
+    /// [`LanguageDetector::preloading_progress`](crate::LanguageDetector::preloading_progress).
+    ///
+    /// On WebAssembly, where no threads are available, the models are preloaded
+    /// while building the detector instead.
+    pub fn with_preloaded_language_models_in_background(&mut self) -> &mut Self {
+        self.is_every_language_model_preloaded = false;
+        self.is_every_language_model_preloaded_in_background = true;
         self
     }
 
@@ -184,28 +271,491 @@ impl LanguageDetectorBuilder {
         self
     }
 
+    /// Enables the accent-insensitive mode in order to classify text whose diacritics
+    /// have been stripped.
+    ///
+    /// Text from sources such as search queries, SMS or old ASCII-only systems often
+    /// lacks the diacritics of the language it is written in, so *Portuguese* text
+    /// contains `nao` instead of `não`. As the language models are built from correctly
+    /// accented text, such input is frequently misclassified.
+    ///
+    /// This method removes diacritics from both the input text and the language models
+    /// of all languages written in the Latin or Greek script before comparing them.
+    /// The accent-insensitive models are derived from the regular models when they are
+    /// loaded, so they do not require any additional files. Rules based on characters
+    /// unique to a language still see the original input text, which means that text
+    /// containing diacritics is classified as reliably as before.
+    pub fn with_accent_insensitive_mode(&mut self) -> &mut Self {
+        self.is_accent_insensitive_mode_enabled = true;
+        self
+    }
+
+    /// Configures `LanguageDetectorBuilder` to derive the character rules of the rule-based
+    /// engine from the language models instead of using the hand-maintained tables.
+    ///
+    /// Before the language models are consulted, *Lingua* identifies or filters languages
+    /// by characters which occur in only one or a few languages, such as `ß` for *German*.
+    /// By default, these characters are taken from hand-maintained tables. This method
+    /// derives them from the unigram models of the languages of the detector instead,
+    /// including custom languages and models loaded from a models directory, so the rules
+    /// are always consistent with the models. A character is unique to a language if it
+    /// occurs in none of the other languages of the detector. Deriving the rules requires
+    /// loading the unigram models of these languages when creating the instance of
+    /// [LanguageDetector]. The tables derived from all built-in languages can be written
+    /// as source code with [CharacterRulesWriter](crate::CharacterRulesWriter).
+    pub fn with_derived_character_rules(&mut self) -> &mut Self {
+        self.is_derived_character_rules_enabled = true;
+        self
+    }
+
+    /// Configures `LanguageDetectorBuilder` to use language models whose ngrams include
+    /// word boundary markers.
+    ///
+    /// By default, ngrams are extracted from inside of words only, so ngrams at the start or
+    /// the end of a word, such as the English suffix `ing`, cannot be distinguished from the
+    /// same ngrams in the middle of a word. The word boundary models mark the start and the
+    /// end of each word, which makes these strong signals available to the detection.
+    ///
+    /// The word boundary models are created by
+    /// [LanguageModelFilesWriter::with_word_boundary_ngrams](crate::LanguageModelFilesWriter::with_word_boundary_ngrams).
+    /// They are not bundled with the built-in languages, so this option is restricted to
+    /// custom languages and to models loaded from the directory of the crate feature
+    /// `external-models`. For the same reason, the accuracy reports do not cover it.
+    ///
+    /// ⚠ Building the detector panics if the word boundary models are missing for any of
+    /// its languages, as ngrams with and without boundary markers cannot be compared.
+    pub fn with_word_boundary_ngrams(&mut self) -> &mut Self {
+        self.is_word_boundary_mode_enabled = true;
+        self
+    }
+
+    /// Configures `LanguageDetectorBuilder` to use the given ngram lengths for the
+    /// classification of short and long text.
+    ///
+    /// By default, unigrams up to fivegrams are used for text consisting of less than
+    /// 120 characters, whereas only trigrams are used for longer text. Higher-order models,
+    /// such as sixgrams or sevengrams, can improve the accuracy for long text. They can be
+    /// created with
+    /// [LanguageModelFilesWriter::with_maximum_ngram_length](crate::LanguageModelFilesWriter::with_maximum_ngram_length).
+    /// Ngram lengths for which no model is available for a language are skipped.
+    /// If the low accuracy mode is enabled, the ngram lengths for long text are used for
+    /// all input.
+    ///
+    /// ⚠ Panics if one of the ranges is empty or does not lie in between 1 and 8.
+    pub fn with_ngram_lengths(
+        &mut self,
+        short_text_ngram_lengths: RangeInclusive<usize>,
+        long_text_ngram_lengths: RangeInclusive<usize>,
+    ) -> &mut Self {
+        if !is_valid_ngram_length_range(&short_text_ngram_lengths)
+            || !is_valid_ngram_length_range(&long_text_ngram_lengths)
+        {
+            panic!("{}", NGRAM_LENGTHS_MESSAGE);
+        }
+        self.short_text_ngram_lengths = short_text_ngram_lengths;
+        self.long_text_ngram_lengths = long_text_ngram_lengths;
+        self
+    }
+
+    /// Configures `LanguageDetectorBuilder` to estimate the ngram probabilities with the
+    /// given smoothing scheme.
+    ///
+    /// By default, the relative frequency of the longest ngram found in the language model
+    /// is used without any discount, and ngrams which are not found at all are ignored.
+    /// This lets rare ngrams dominate the classification of short text. The smoothing
+    /// schemes assign a proper probability to every ngram instead. They are computed from
+    /// the ngram count files created by
+    /// [LanguageModelFilesWriter::with_ngram_counts](crate::LanguageModelFilesWriter::with_ngram_counts).
+    /// They are not bundled with the built-in languages, so smoothing is restricted to custom
+    /// languages and to models loaded from the directory of the crate feature `external-models`.
+    /// If the ngram counts of a language fail to load, the failure is reported by
+    /// [LanguageDetector::model_status](crate::LanguageDetector::model_status).
+    ///
+    /// ⚠ Panics if the constant of [Smoothing::AddK] is not greater than 0.0 or if the
+    /// discount of [Smoothing::KneserNey] does not lie in between 0.0 and 1.0. Building the
+    /// detector panics if the ngram counts are missing for any of its languages, as smoothed
+    /// and unsmoothed probabilities cannot be compared.
+    pub fn with_smoothing(&mut self, smoothing: Smoothing) -> &mut Self {
+        match smoothing {
+            Smoothing::AddK(k) if !is_valid_add_k_constant(k) => {
+                panic!("{}", ADD_K_SMOOTHING_MESSAGE)
+            }
+            Smoothing::KneserNey(discount) if !is_valid_kneser_ney_discount(discount) => {
+                panic!("{}", KNESER_NEY_SMOOTHING_MESSAGE)
+            }
+            _ => {}
+        }
+        self.smoothing = smoothing;
+        self
+    }
+
+    /// Configures `LanguageDetectorBuilder` to combine the ngram models with the relative
+    /// frequencies of whole words for text consisting of one or two words.
+    ///
+    /// Single words and word pairs are the hardest texts to classify, as they contain only
+    /// few ngrams. Whole-word frequencies provide the missing evidence in these cases. The
+    /// summed log-probabilities of the words are interpolated with those of the ngrams,
+    /// using the given weight for the words. Languages for which the ngram models provide
+    /// no evidence at all are not scored by their word models either. The word models are
+    /// created by
+    /// [LanguageModelFilesWriter::with_word_model](crate::LanguageModelFilesWriter::with_word_model).
+    /// They are not bundled with the built-in languages, so this option is restricted to custom
+    /// languages and to models loaded from the directory of the crate feature `external-models`.
+    /// If the word model of a language fails to load, the failure is reported by
+    /// [LanguageDetector::model_status](crate::LanguageDetector::model_status).
+    ///
+    /// ⚠ Panics if `weight` is not greater than 0.0 or greater than 1.0. Building the detector
+    /// panics if the word models are missing for any of its languages.
+    pub fn with_word_model(&mut self, weight: f64) -> &mut Self {
+        if !is_valid_word_model_weight(weight) {
+            panic!("{}", WORD_MODEL_WEIGHT_MESSAGE);
+        }
+        self.word_model_weight = weight;
+        self
+    }
+
+    /// Configures `LanguageDetectorBuilder` to load the models of regional language variants,
+    /// such as `pt-BR` and `pt-PT`, from the given directory.
+    ///
+    /// The directory contains a subdirectory for each variant which is named after its BCP 47
+    /// tag and holds the files written by
+    /// [`LanguageModelFilesWriter::write_language_variant_model_files`](crate::LanguageModelFilesWriter::write_language_variant_model_files).
+    /// Only the variants of the languages of the detector are loaded. They are used by
+    /// [`LanguageDetector::detect_language_variant_of`](crate::LanguageDetector::detect_language_variant_of)
+    /// once the language of a text has been detected.
+    ///
+    /// ⚠ Panics if `models_directory` does not point to an existing directory.
+    pub fn with_language_variants(&mut self, models_directory: impl AsRef<Path>) -> &mut Self {
+        let models_directory = models_directory.as_ref();
+        if !is_valid_models_directory(models_directory) {
+            panic!("{}", LANGUAGE_VARIANTS_DIRECTORY_MESSAGE);
+        }
+        self.language_variants_directory = Some(models_directory.to_path_buf());
+        self
+    }
+
+    /// Configures `LanguageDetectorBuilder` to load discriminators for pairs of closely related
+    /// languages, such as Bokmal and Nynorsk or Malay and Indonesian, from the given directory.
+    ///
+    /// The directory holds the files written by
+    /// [`LanguageModelFilesWriter::write_pair_discriminator_file`](crate::LanguageModelFilesWriter::write_pair_discriminator_file).
+    /// No discriminators are bundled with this library, so they have to be trained from your own
+    /// text of both languages of each pair before this stage has any effect.
+    /// Only the discriminators whose languages are both languages of the detector are loaded.
+    /// If the two most likely languages of a text form such a pair, their discriminator decides
+    /// how their combined confidence value is split between them, which may change their ranking.
+    ///
+    /// ⚠ Panics if `models_directory` does not point to an existing directory.
+    pub fn with_pair_discriminators(&mut self, models_directory: impl AsRef<Path>) -> &mut Self {
+        let models_directory = models_directory.as_ref();
+        if !is_valid_models_directory(models_directory) {
+            panic!("{}", PAIR_DISCRIMINATORS_DIRECTORY_MESSAGE);
+        }
+        self.pair_discriminators_directory = Some(models_directory.to_path_buf());
+        self
+    }
+
+    /// Configures `LanguageDetectorBuilder` to collapse the languages of the given
+    /// macrolanguages, such as *Bokmal* and *Nynorsk* into *Norwegian*.
+    ///
+    /// The macrolanguages are reported by
+    /// [`LanguageDetector::detect_collapsed_language_of`](crate::LanguageDetector::detect_collapsed_language_of)
+    /// and
+    /// [`LanguageDetector::compute_collapsed_language_confidence_values`](crate::LanguageDetector::compute_collapsed_language_confidence_values),
+    /// which sum up the confidence values of their individual languages. The fine-grained
+    /// distribution remains available through the other methods of the detector.
+    ///
+    /// ⚠ Panics if `macro_languages` is empty.
+    pub fn with_macro_languages(&mut self, macro_languages: &[MacroLanguage]) -> &mut Self {
+        if macro_languages.is_empty() {
+            panic!("{}", MACRO_LANGUAGES_MESSAGE);
+        }
+        self.macro_languages = macro_languages.iter().copied().collect();
+        self
+    }
+
+    /// Configures `LanguageDetectorBuilder` to keep the memory occupied by the loaded
+    /// language models below the given number of bytes.
+    ///
+    /// By default, lazily loaded language models stay in memory until
+    /// [`LanguageDetector::unload_language_models`](crate::LanguageDetector::unload_language_models)
+    /// is called, so that a detector for all languages eventually holds every model touched
+    /// by its input. With a memory budget, the sizes of the loaded models are estimated and
+    /// the least recently used models of a language and ngram length are evicted once the
+    /// budget is exceeded. Evicted models are loaded again when they are needed.
+    ///
+    /// The budget applies to each detector separately and covers only the models it has used.
+    /// As all detectors share the same language models, a model evicted by one detector is
+    /// gone for the others as well and loaded again by any other detector which still needs
+    /// it, even if that detector has preloaded its models.
+    ///
+    /// The models required for the current text are never evicted before it is classified,
+    /// so the budget may be exceeded temporarily if it is too small for them. The budget
+    /// applies to the ngram models only, not to the word models or the ngram counts of the
+    /// smoothing schemes.
+    ///
+    /// ⚠ Panics if `bytes` is 0.
+    pub fn with_memory_budget(&mut self, bytes: usize) -> &mut Self {
+        if bytes == 0 {
+            panic!("{}", MEMORY_BUDGET_MESSAGE);
+        }
+        self.memory_budget = Some(bytes);
+        self
+    }
+
+    /// Configures `LanguageDetectorBuilder` to load the models of the built-in languages
+    /// from the given directory instead of the environment variable `LINGUA_MODELS_DIRECTORY`.
+    /// This method is only available with the crate feature `external-models`.
+    ///
+    /// The directory contains a subdirectory for each language which is named after its
+    /// ISO 639-1 code, such as `en` for English, and holds the files of the `models`
+    /// directory of the respective language model crate. As all detectors share the loaded
+    /// language models, the directory applies to all of them.
+    ///
+    /// ⚠ Panics if `models_directory` does not point to an existing directory.
+    #[cfg(feature = "external-models")]
+    pub fn with_models_directory(&mut self, models_directory: impl AsRef<Path>) -> &mut Self {
+        let models_directory = models_directory.as_ref();
+        if !is_valid_models_directory(models_directory) {
+            panic!("{}", MODELS_DIRECTORY_MESSAGE);
+        }
+        self.models_directory = Some(models_directory.to_path_buf());
+        self
+    }
+
+    /// Configures `LanguageDetectorBuilder` to apply the given normalizers to the input text
+    /// before it is split into words.
+    ///
+    /// By default, the input text is only trimmed and lowercased. Text from social media
+    /// or user input often contains invisible control characters, fullwidth letters or
+    /// elongated words such as `sooooo` which do not occur in the training data. The
+    /// normalizers are applied in the given order. Indices returned by
+    /// [LanguageDetector::detect_multiple_languages_of] still refer to the original text.
+    /// For best results, the language models should be created with the same normalizers,
+    /// see [LanguageModelFilesWriter::with_normalizers](crate::LanguageModelFilesWriter::with_normalizers).
+    pub fn with_normalizers(&mut self, normalizers: &[Normalizer]) -> &mut Self {
+        self.normalizers = normalizers.to_vec();
+        self
+    }
+
+    /// Configures `LanguageDetectorBuilder` to split text into words with the given tokenizer
+    /// instead of the [DefaultTokenizer].
+    ///
+    /// The default tokenizer treats every Chinese, Japanese and Korean character as a
+    /// separate word and splits any other text at each character which is not a letter.
+    /// A custom tokenizer, such as a dictionary-based segmenter, can produce more meaningful
+    /// words for some languages or domains. The rest of the detection pipeline stays the same.
+    /// For best results, the language models should be created with the same tokenizer,
+    /// see [LanguageModelFilesWriter::with_tokenizer](crate::LanguageModelFilesWriter::with_tokenizer).
+    pub fn with_tokenizer(&mut self, tokenizer: Arc<dyn Tokenizer>) -> &mut Self {
+        self.tokenizer = tokenizer;
+        self
+    }
+
     /// Creates and returns the configured instance of [LanguageDetector].
+    ///
+    /// ⚠ Panics if the models required by one of the configured modes are missing for any of
+    /// the languages. With the crate feature `external-models`, also panics if no models
+    /// directory has been specified or if it lacks any ngram model file of the languages.
     pub fn build(&mut self) -> LanguageDetector {
+        #[cfg(feature = "external-models")]
+        self.check_models_directory();
+
+        if let Some(message) = self.missing_models_message() {
+            panic!("{}", message);
+        }
+
         LanguageDetector::from(
             self.languages.clone(),
-            self.minimum_relative_distance,
-            self.is_every_language_model_preloaded,
-            self.is_low_accuracy_mode_enabled,
+            DetectorSettings {
+                minimum_relative_distance: self.minimum_relative_distance,
+                is_every_language_model_preloaded: self.is_every_language_model_preloaded,
+                is_every_language_model_preloaded_in_background: self
+                    .is_every_language_model_preloaded_in_background,
+                is_low_accuracy_mode_enabled: self.is_low_accuracy_mode_enabled,
+                is_accent_insensitive_mode_enabled: self.is_accent_insensitive_mode_enabled,
+                is_derived_character_rules_enabled: self.is_derived_character_rules_enabled,
+                is_word_boundary_mode_enabled: self.is_word_boundary_mode_enabled,
+                short_text_ngram_lengths: self.short_text_ngram_lengths.clone(),
+                long_text_ngram_lengths: self.long_text_ngram_lengths.clone(),
+                smoothing: self.smoothing,
+                word_model_weight: self.word_model_weight,
+                normalizers: self.normalizers.clone(),
+                tokenizer: self.tokenizer.clone(),
+                language_variants_directory: self.language_variants_directory.clone(),
+                pair_discriminators_directory: self.pair_discriminators_directory.clone(),
+                macro_languages: self.macro_languages.clone(),
+                memory_budget: self.memory_budget,
+            },
         )
     }
 
+    /// Returns the reason why the models required by the configured modes cannot be used,
+    /// naming the languages for which they are missing.
+    pub(crate) fn missing_models_message(&self) -> Option<String> {
+        let maximum_ngram_length = self.maximum_ngram_length();
+
+        if self.is_word_boundary_mode_enabled {
+            let languages = self.languages_without(|language| {
+                has_word_boundary_models(language, maximum_ngram_length)
+            });
+            if !languages.is_empty() {
+                return Some(unbundled_models_message(
+                    MISSING_WORD_BOUNDARY_MODELS_MESSAGE,
+                    &languages,
+                ));
+            }
+        }
+
+        if self.smoothing != Smoothing::None {
+            let languages = self.languages_without(|language| {
+                has_ngram_counts(
+                    language,
+                    maximum_ngram_length,
+                    self.is_word_boundary_mode_enabled,
+                )
+            });
+            if !languages.is_empty() {
+                return Some(unbundled_models_message(
+                    MISSING_NGRAM_COUNTS_MESSAGE,
+                    &languages,
+                ));
+            }
+        }
+
+        if self.word_model_weight > 0.0 {
+            let languages = self.languages_without(has_word_model);
+            if !languages.is_empty() {
+                return Some(unbundled_models_message(
+                    MISSING_WORD_MODELS_MESSAGE,
+                    &languages,
+                ));
+            }
+        }
+
+        None
+    }
+
+    /// Returns the languages which lack the models checked by the given function.
+    fn languages_without(&self, has_models: impl Fn(Language) -> bool) -> Vec<Language> {
+        self.languages
+            .iter()
+            .filter(|language| !has_models(**language))
+            .copied()
+            .collect()
+    }
+
+    fn maximum_ngram_length(&self) -> usize {
+        *self
+            .short_text_ngram_lengths
+            .end()
+            .max(self.long_text_ngram_lengths.end())
+    }
+
+    /// Makes the models directory of this builder the one of all detectors
+    /// and lists every ngram model file which is missing in it at once.
+    #[cfg(feature = "external-models")]
+    fn check_models_directory(&self) {
+        if let Some(models_directory) = &self.models_directory {
+            set_models_directory(models_directory);
+        }
+
+        let Some(models_directory) = models_directory() else {
+            panic!("{}", MISSING_MODELS_DIRECTORY_MESSAGE);
+        };
+
+        let missing_files = missing_model_files(
+            &models_directory,
+            &self.languages,
+            self.maximum_ngram_length(),
+        );
+
+        if !missing_files.is_empty() {
+            panic!(
+                "The following language model files are missing in '{}':\n{}",
+                models_directory.display(),
+                missing_files
+                    .iter()
+                    .map(|file| format!("- {}", file.display()))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            );
+        }
+    }
+
     fn from(languages: HashSet<Language>) -> Self {
         Self {
             languages,
             minimum_relative_distance: 0.0,
             is_every_language_model_preloaded: false,
+            is_every_language_model_preloaded_in_background: false,
             is_low_accuracy_mode_enabled: false,
+            is_accent_insensitive_mode_enabled: false,
+            is_derived_character_rules_enabled: false,
+            is_word_boundary_mode_enabled: false,
+            short_text_ngram_lengths: 1..=5,
+            long_text_ngram_lengths: 3..=3,
+            smoothing: Smoothing::None,
+            word_model_weight: 0.0,
+            normalizers: vec![],
+            tokenizer: Arc::new(DefaultTokenizer),
+            language_variants_directory: None,
+            pair_discriminators_directory: None,
+            macro_languages: hashset!(),
+            memory_budget: None,
+            #[cfg(feature = "external-models")]
+            models_directory: None,
         }
     }
 }
 
+pub(crate) fn is_valid_add_k_constant(k: f64) -> bool {
+    k > 0.0 && k.is_finite()
+}
+
+pub(crate) fn is_valid_kneser_ney_discount(discount: f64) -> bool {
+    discount > 0.0 && discount < 1.0
+}
+
+pub(crate) fn is_valid_word_model_weight(weight: f64) -> bool {
+    weight > 0.0 && weight <= 1.0
+}
+
+pub(crate) fn is_valid_models_directory(models_directory: &Path) -> bool {
+    models_directory.is_dir()
+}
+
+pub(crate) fn is_valid_ngram_length_range(ngram_lengths: &RangeInclusive<usize>) -> bool {
+    !ngram_lengths.is_empty()
+        && *ngram_lengths.start() >= 1
+        && *ngram_lengths.end() <= MAXIMUM_NGRAM_LENGTH
+}
+
+/// Returns the given message about models which are missing for the given languages.
+/// If built-in languages are among them, it is explained that these models are not
+/// bundled with the language model crates.
+fn unbundled_models_message(message: &str, languages: &[Language]) -> String {
+    let language_names = languages
+        .iter()
+        .map(|language| language.to_string())
+        .sorted()
+        .join(", ");
+    if languages
+        .iter()
+        .all(|language| matches!(language, Language::Custom(_)))
+    {
+        format!("{message} {language_names}")
+    } else {
+        format!("{message} {language_names}. {UNBUNDLED_MODELS_MESSAGE}.")
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use tempfile::tempdir;
+
+    use crate::alphabet::Alphabet;
+
     use super::*;
 
     #[test]
@@ -218,6 +768,274 @@ mod tests {
         assert_eq!(builder.minimum_relative_distance, 0.2);
     }
 
+    #[test]
+    fn assert_detector_can_be_built_in_accent_insensitive_mode() {
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+        assert!(!builder.is_accent_insensitive_mode_enabled);
+
+        builder.with_accent_insensitive_mode();
+        assert!(builder.is_accent_insensitive_mode_enabled);
+    }
+
+    #[test]
+    fn assert_detector_can_be_built_with_derived_character_rules() {
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+        assert!(!builder.is_derived_character_rules_enabled);
+
+        builder.with_derived_character_rules();
+        assert!(builder.is_derived_character_rules_enabled);
+    }
+
+    #[test]
+    fn assert_detector_can_be_built_with_word_boundary_ngrams() {
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+        assert!(!builder.is_word_boundary_mode_enabled);
+
+        builder.with_word_boundary_ngrams();
+        assert!(builder.is_word_boundary_mode_enabled);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Word boundary ngrams cannot be used as the word boundary models are missing for English, German"
+    )]
+    fn assert_detector_cannot_be_built_without_word_boundary_models() {
+        LanguageDetectorBuilder::from_languages(&[Language::German, Language::English])
+            .with_word_boundary_ngrams()
+            .build();
+    }
+
+    #[test]
+    fn assert_missing_models_message_explains_that_models_are_not_bundled() {
+        let models_directory = tempdir().unwrap();
+        let adunaic = Language::register_custom(
+            "Adunaic",
+            "ad",
+            "adu",
+            &[Alphabet::Latin],
+            models_directory.path(),
+        );
+
+        assert_eq!(
+            unbundled_models_message("Models are missing for", &[adunaic]),
+            "Models are missing for Adunaic"
+        );
+        assert_eq!(
+            unbundled_models_message(
+                "Models are missing for",
+                &[Language::German, adunaic, Language::English]
+            ),
+            format!("Models are missing for Adunaic, English, German. {UNBUNDLED_MODELS_MESSAGE}.")
+        );
+    }
+
+    #[test]
+    fn assert_detector_can_be_built_with_ngram_lengths() {
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+        assert_eq!(builder.short_text_ngram_lengths, 1..=5);
+        assert_eq!(builder.long_text_ngram_lengths, 3..=3);
+
+        builder.with_ngram_lengths(1..=6, 3..=7);
+        assert_eq!(builder.short_text_ngram_lengths, 1..=6);
+        assert_eq!(builder.long_text_ngram_lengths, 3..=7);
+    }
+
+    #[test]
+    #[should_panic(expected = "Ngram lengths must be non-empty ranges lying in between 1 and 8")]
+    fn assert_detector_cannot_be_built_with_ngram_lengths_above_maximum() {
+        LanguageDetectorBuilder::from_all_languages().with_ngram_lengths(1..=9, 3..=3);
+    }
+
+    #[test]
+    #[should_panic(expected = "Ngram lengths must be non-empty ranges lying in between 1 and 8")]
+    fn assert_detector_cannot_be_built_with_empty_ngram_lengths() {
+        #[allow(clippy::reversed_empty_ranges)]
+        LanguageDetectorBuilder::from_all_languages().with_ngram_lengths(1..=5, 4..=3);
+    }
+
+    #[test]
+    fn assert_detector_can_be_built_with_smoothing() {
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+        assert_eq!(builder.smoothing, Smoothing::None);
+
+        builder.with_smoothing(Smoothing::KneserNey(0.75));
+        assert_eq!(builder.smoothing, Smoothing::KneserNey(0.75));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Smoothing cannot be applied as the ngram counts are missing for English, German"
+    )]
+    fn assert_detector_cannot_be_built_without_ngram_counts() {
+        LanguageDetectorBuilder::from_languages(&[Language::German, Language::English])
+            .with_smoothing(Smoothing::KneserNey(0.75))
+            .build();
+    }
+
+    #[test]
+    #[should_panic(expected = "The constant k of add-k smoothing must be greater than 0.0")]
+    fn assert_detector_cannot_be_built_with_zero_add_k_constant() {
+        LanguageDetectorBuilder::from_all_languages().with_smoothing(Smoothing::AddK(0.0));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "The discount of Kneser-Ney smoothing must lie in between 0.0 and 1.0"
+    )]
+    fn assert_detector_cannot_be_built_with_too_large_kneser_ney_discount() {
+        LanguageDetectorBuilder::from_all_languages().with_smoothing(Smoothing::KneserNey(1.5));
+    }
+
+    #[test]
+    fn assert_detector_can_be_built_with_word_model() {
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+        assert_eq!(builder.word_model_weight, 0.0);
+
+        builder.with_word_model(0.4);
+        assert_eq!(builder.word_model_weight, 0.4);
+    }
+
+    #[test]
+    #[should_panic(expected = "Word models cannot be used as they are missing for English, German")]
+    fn assert_detector_cannot_be_built_without_word_models() {
+        LanguageDetectorBuilder::from_languages(&[Language::German, Language::English])
+            .with_word_model(0.4)
+            .build();
+    }
+
+    #[test]
+    #[should_panic(expected = "Word model weight must be greater than 0.0 and at most 1.0")]
+    fn assert_detector_cannot_be_built_with_zero_word_model_weight() {
+        LanguageDetectorBuilder::from_all_languages().with_word_model(0.0);
+    }
+
+    #[test]
+    fn assert_detector_can_be_built_with_language_variants() {
+        let models_directory = std::env::temp_dir();
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+        assert_eq!(builder.language_variants_directory, None);
+
+        builder.with_language_variants(&models_directory);
+        assert_eq!(builder.language_variants_directory, Some(models_directory));
+    }
+
+    #[test]
+    #[should_panic(expected = "Language variant models directory must be an existing directory")]
+    fn assert_detector_cannot_be_built_with_missing_language_variants_directory() {
+        LanguageDetectorBuilder::from_all_languages()
+            .with_language_variants("/this/directory/does/not/exist");
+    }
+
+    #[test]
+    #[cfg(feature = "external-models")]
+    #[should_panic(expected = "Language models directory must be an existing directory")]
+    fn assert_detector_cannot_be_built_with_missing_models_directory() {
+        LanguageDetectorBuilder::from_all_languages()
+            .with_models_directory("/this/directory/does/not/exist");
+    }
+
+    #[test]
+    fn assert_detector_can_be_built_with_pair_discriminators() {
+        let models_directory = std::env::temp_dir();
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+        assert_eq!(builder.pair_discriminators_directory, None);
+
+        builder.with_pair_discriminators(&models_directory);
+        assert_eq!(
+            builder.pair_discriminators_directory,
+            Some(models_directory)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Pair discriminator models directory must be an existing directory")]
+    fn assert_detector_cannot_be_built_with_missing_pair_discriminators_directory() {
+        LanguageDetectorBuilder::from_all_languages()
+            .with_pair_discriminators("/this/directory/does/not/exist");
+    }
+
+    #[test]
+    fn assert_detector_can_be_built_with_macro_languages() {
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+        assert!(builder.macro_languages.is_empty());
+
+        builder.with_macro_languages(&[MacroLanguage::Norwegian, MacroLanguage::Malay]);
+        assert_eq!(
+            builder.macro_languages,
+            hashset!(MacroLanguage::Malay, MacroLanguage::Norwegian)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "At least one macro language must be specified")]
+    fn assert_detector_cannot_be_built_without_macro_languages() {
+        LanguageDetectorBuilder::from_all_languages().with_macro_languages(&[]);
+    }
+
+    #[test]
+    fn assert_detector_can_be_built_with_background_preloading() {
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+        builder.with_preloaded_language_models();
+        builder.with_preloaded_language_models_in_background();
+        assert!(!builder.is_every_language_model_preloaded);
+        assert!(builder.is_every_language_model_preloaded_in_background);
+
+        builder.with_preloaded_language_models();
+        assert!(builder.is_every_language_model_preloaded);
+        assert!(!builder.is_every_language_model_preloaded_in_background);
+    }
+
+    #[test]
+    fn assert_detector_can_be_built_with_memory_budget() {
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+        assert_eq!(builder.memory_budget, None);
+
+        builder.with_memory_budget(50_000_000);
+        assert_eq!(builder.memory_budget, Some(50_000_000));
+    }
+
+    #[test]
+    #[should_panic(expected = "Memory budget must be greater than 0 bytes")]
+    fn assert_detector_cannot_be_built_with_zero_memory_budget() {
+        LanguageDetectorBuilder::from_all_languages().with_memory_budget(0);
+    }
+
+    #[test]
+    fn assert_detector_can_be_built_with_normalizers() {
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+        assert!(builder.normalizers.is_empty());
+
+        builder.with_normalizers(&[Normalizer::Nfkc, Normalizer::SquashElongation]);
+        assert_eq!(
+            builder.normalizers,
+            vec![Normalizer::Nfkc, Normalizer::SquashElongation]
+        );
+    }
+
+    #[test]
+    fn assert_detector_can_be_built_with_tokenizer() {
+        struct WhitespaceTokenizer;
+
+        impl Tokenizer for WhitespaceTokenizer {
+            fn words<'a>(&self, text: &'a str) -> Vec<&'a str> {
+                text.split_whitespace().collect()
+            }
+
+            fn token_ranges(&self, _text: &str) -> Vec<std::ops::Range<usize>> {
+                vec![]
+            }
+        }
+
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+        assert_eq!(
+            builder.tokenizer.words("don't stop"),
+            vec!["don", "t", "stop"]
+        );
+
+        builder.with_tokenizer(Arc::new(WhitespaceTokenizer));
+        assert_eq!(builder.tokenizer.words("don't stop"), vec!["don't", "stop"]);
+    }
+
     #[test]
     fn assert_detector_can_be_built_from_spoken_languages() {
         let mut builder = LanguageDetectorBuilder::from_all_spoken_languages();
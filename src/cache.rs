@@ -0,0 +1,155 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::HashMap;
+
+use crate::language::Language;
+
+/// Keeps track of the estimated sizes of the language models used by a detector and of
+/// the order in which they have been used, so that the least recently used models can be
+/// evicted once the memory budget of the detector is exceeded.
+#[derive(Default)]
+pub(crate) struct ModelCache {
+    entries: HashMap<(Language, usize), ModelCacheEntry>,
+    used_memory: usize,
+    clock: u64,
+}
+
+struct ModelCacheEntry {
+    size: usize,
+    last_used: u64,
+}
+
+impl ModelCache {
+    /// Marks the model of the given language and ngram length as most recently used.
+    /// Its size is only estimated when the model is not tracked yet.
+    pub(crate) fn touch(
+        &mut self,
+        language: Language,
+        ngram_length: usize,
+        estimate_size: impl FnOnce() -> usize,
+    ) {
+        self.clock += 1;
+
+        match self.entries.get_mut(&(language, ngram_length)) {
+            Some(entry) => entry.last_used = self.clock,
+            None => {
+                let size = estimate_size();
+                self.used_memory += size;
+                self.entries.insert(
+                    (language, ngram_length),
+                    ModelCacheEntry {
+                        size,
+                        last_used: self.clock,
+                    },
+                );
+            }
+        }
+    }
+
+    /// Stops tracking the model of the given language and ngram length.
+    pub(crate) fn remove(&mut self, language: Language, ngram_length: usize) {
+        if let Some(entry) = self.entries.remove(&(language, ngram_length)) {
+            self.used_memory -= entry.size;
+        }
+    }
+
+    /// Stops tracking the models for which the given predicate returns `false`, such as
+    /// models which have been evicted or unloaded by another detector in the meantime.
+    pub(crate) fn retain(&mut self, mut predicate: impl FnMut(Language, usize) -> bool) {
+        let mut used_memory = self.used_memory;
+        self.entries.retain(|(language, ngram_length), entry| {
+            let is_retained = predicate(*language, *ngram_length);
+            if !is_retained {
+                used_memory -= entry.size;
+            }
+            is_retained
+        });
+        self.used_memory = used_memory;
+    }
+
+    /// Stops tracking the least recently used models until the remaining ones fit
+    /// into the given memory budget and returns the models to evict.
+    pub(crate) fn evict(&mut self, memory_budget: usize) -> Vec<(Language, usize)> {
+        let mut evicted_models = vec![];
+
+        while self.used_memory > memory_budget {
+            let Some(key) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| *key)
+            else {
+                break;
+            };
+            self.remove(key.0, key.1);
+            evicted_models.push(key);
+        }
+
+        evicted_models
+    }
+
+    #[cfg(test)]
+    pub(crate) fn used_memory(&self) -> usize {
+        self.used_memory
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::Language::{English, French, German};
+
+    #[test]
+    fn assert_least_recently_used_models_are_evicted() {
+        let mut cache = ModelCache::default();
+        cache.touch(English, 1, || 100);
+        cache.touch(German, 1, || 100);
+        cache.touch(French, 1, || 100);
+        cache.touch(English, 1, || unreachable!());
+
+        assert_eq!(cache.used_memory(), 300);
+        assert_eq!(cache.evict(300), vec![]);
+        assert_eq!(cache.evict(150), vec![(German, 1), (French, 1)]);
+        assert_eq!(cache.used_memory(), 100);
+        assert_eq!(cache.evict(0), vec![(English, 1)]);
+        assert_eq!(cache.used_memory(), 0);
+    }
+
+    #[test]
+    fn assert_removed_models_are_not_tracked_anymore() {
+        let mut cache = ModelCache::default();
+        cache.touch(English, 1, || 100);
+        cache.touch(English, 2, || 200);
+        cache.remove(English, 2);
+        cache.remove(German, 2);
+
+        assert_eq!(cache.used_memory(), 100);
+        assert_eq!(cache.evict(0), vec![(English, 1)]);
+    }
+
+    #[test]
+    fn assert_models_not_retained_are_not_tracked_anymore() {
+        let mut cache = ModelCache::default();
+        cache.touch(English, 1, || 100);
+        cache.touch(German, 1, || 200);
+        cache.touch(French, 2, || 300);
+        cache.retain(|language, _| language != German);
+
+        assert_eq!(cache.used_memory(), 400);
+        assert_eq!(cache.evict(0), vec![(English, 1), (French, 2)]);
+    }
+}
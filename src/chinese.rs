@@ -0,0 +1,213 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter, Result};
+
+use once_cell::sync::Lazy;
+use strum_macros::EnumIter;
+
+/// Characters which only occur in Simplified Chinese. Each of them has a distinct
+/// traditional counterpart which is listed at the same position in
+/// [`TRADITIONAL_ONLY_CHARACTERS`].
+const SIMPLIFIED_ONLY_CHARACTERS: &str = concat!(
+    "个们来时国说这对会学过还发经动长见间现样开关机问门东车马鸟鱼",
+    "龙书买卖电话语读写认识让请谁谢该论议记设计训试诗词调谈课师员",
+    "题页顶项顺须领头颜风飞饭饮馆钱银铁钟错闻阳阴队际陆险难双鸡变",
+    "实宝审宁尔尽层属岁岛带帮广庆应废张弹归当录彻径忆态总恋恶惊惯",
+    "战执扩扫扬护报担择挂挡挥换携摄敌数断无旧显晓暂术杀杂权条杨极",
+    "构枪标树桥检楼欢气汉汤沟浅测济浓湾满灯灵灾点炼热爱牵状独狮猎",
+    "环产画疗盖盘矿码础确礼祸离种积称穷竞笔简类粮紧红约级纪线组细",
+    "织终结给绝统继续维综绿网罗职联听肃脑脸节艺药苏获营萨蓝虑虽虾",
+    "蚁补装观规视览觉订讨许证评译诚询误谋谓谱贝负贡财责败货质贩购",
+    "贯费贴贵贸资赛赞转轮软轻较辆辑输边达迁运进远违连迟选递遗邮邻",
+    "郑酱释针钓铅链销锁锅键镜闭闹阅阶隐雾韩顾预频额饿驾验骑鲜鸭麦",
+    "齐齿龟亚仅从价众优伤伦侠侣侧侨债储儿兰兴养兽军农冯冻净减凤击",
+    "则刚创删剧劝办务劳势区医华协单卫厂厅压厌县参叙吗启呜响哑唤团",
+    "园围图圆圣场坏块坚坛垒备夺奋奖妇妈娱婴孙宽宾寻导将尘尝岭峡币",
+    "帅帐库庄庙弯忧怀怜恳悬惩愤戏扰抚抢拥拟拣挤挣损捡摆撑敛斋旷昼",
+    "晕枣栏梦欧毕汇沪泪泽洁浊浏浑涛润涨渐渔湿溃滚滩潜灭灿炉烂烦烧",
+    "爷牺犹狱献玛琐畅疯疮皱盐监盗睁矫砖碍祷禅秃稳窃竖笼粪纠纤纯纲",
+    "纳纵纷纸纹练绍绘络绕绩绪绳编缓缘缩罚罢习翘耸聋肠肤肿胀胁腾舰",
+    "舱艰茧荣莲萝蒋蚕蛮袭觅触誉贞贤账贷贼赋赌赏赔赖赠赵趋跃践踪轨",
+    "轰辈辉辞辩迈逊遥邓酿鉴钢钥钻铃铜铭锐锦镇闪闯阀阁阐阵陈陕隶鸣",
+    "鸿鹅鹤龄",
+);
+
+/// Characters which only occur in Traditional Chinese. Characters whose simplified form
+/// occurs in Traditional Chinese as well, such as 後 whose simplified form 后 means queen,
+/// are appended at the end.
+const TRADITIONAL_ONLY_CHARACTERS: &str = concat!(
+    "個們來時國說這對會學過還發經動長見間現樣開關機問門東車馬鳥魚",
+    "龍書買賣電話語讀寫認識讓請誰謝該論議記設計訓試詩詞調談課師員",
+    "題頁頂項順須領頭顏風飛飯飲館錢銀鐵鐘錯聞陽陰隊際陸險難雙雞變",
+    "實寶審寧爾盡層屬歲島帶幫廣慶應廢張彈歸當錄徹徑憶態總戀惡驚慣",
+    "戰執擴掃揚護報擔擇掛擋揮換攜攝敵數斷無舊顯曉暫術殺雜權條楊極",
+    "構槍標樹橋檢樓歡氣漢湯溝淺測濟濃灣滿燈靈災點煉熱愛牽狀獨獅獵",
+    "環產畫療蓋盤礦碼礎確禮禍離種積稱窮競筆簡類糧緊紅約級紀線組細",
+    "織終結給絕統繼續維綜綠網羅職聯聽肅腦臉節藝藥蘇獲營薩藍慮雖蝦",
+    "蟻補裝觀規視覽覺訂討許證評譯誠詢誤謀謂譜貝負貢財責敗貨質販購",
+    "貫費貼貴貿資賽贊轉輪軟輕較輛輯輸邊達遷運進遠違連遲選遞遺郵鄰",
+    "鄭醬釋針釣鉛鏈銷鎖鍋鍵鏡閉鬧閱階隱霧韓顧預頻額餓駕驗騎鮮鴨麥",
+    "齊齒龜亞僅從價眾優傷倫俠侶側僑債儲兒蘭興養獸軍農馮凍淨減鳳擊",
+    "則剛創刪劇勸辦務勞勢區醫華協單衛廠廳壓厭縣參敘嗎啟嗚響啞喚團",
+    "園圍圖圓聖場壞塊堅壇壘備奪奮獎婦媽娛嬰孫寬賓尋導將塵嘗嶺峽幣",
+    "帥帳庫莊廟彎憂懷憐懇懸懲憤戲擾撫搶擁擬揀擠掙損撿擺撐斂齋曠晝",
+    "暈棗欄夢歐畢匯滬淚澤潔濁瀏渾濤潤漲漸漁濕潰滾灘潛滅燦爐爛煩燒",
+    "爺犧猶獄獻瑪瑣暢瘋瘡皺鹽監盜睜矯磚礙禱禪禿穩竊豎籠糞糾纖純綱",
+    "納縱紛紙紋練紹繪絡繞績緒繩編緩緣縮罰罷習翹聳聾腸膚腫脹脅騰艦",
+    "艙艱繭榮蓮蘿蔣蠶蠻襲覓觸譽貞賢賬貸賊賦賭賞賠賴贈趙趨躍踐蹤軌",
+    "轟輩輝辭辯邁遜遙鄧釀鑒鋼鑰鑽鈴銅銘銳錦鎮閃闖閥閣闡陣陳陝隸鳴",
+    "鴻鵝鶴齡後裡麼髮麵臺雲幾隻係鬆穀製曆醜衝鬥範復複準願據勝號葉",
+    "體黨劃歷櫃築簽臟跡適蟲傑煙趕異幹",
+);
+
+/// The minimum share of characters of the less frequent script for text
+/// to be classified as [`ChineseScript::Mixed`].
+const MIXED_SCRIPT_MINIMUM_SHARE: f64 = 0.2;
+
+static SIMPLIFIED_CHARACTER_SET: Lazy<HashSet<char>> =
+    Lazy::new(|| SIMPLIFIED_ONLY_CHARACTERS.chars().collect());
+static TRADITIONAL_CHARACTER_SET: Lazy<HashSet<char>> =
+    Lazy::new(|| TRADITIONAL_ONLY_CHARACTERS.chars().collect());
+
+/// This enum specifies the scripts in which Chinese text can be written.
+#[derive(Clone, Copy, Debug, EnumIter, Eq, PartialEq, Hash, Ord, PartialOrd)]
+#[cfg_attr(
+    feature = "python",
+    pyo3::prelude::pyclass(eq, eq_int, frozen, hash, ord, rename_all = "UPPERCASE")
+)]
+pub enum ChineseScript {
+    /// Simplified Chinese characters as used in Mainland China and Singapore.
+    Simplified,
+
+    /// Traditional Chinese characters as used in Taiwan, Hong Kong and Macau.
+    Traditional,
+
+    /// Text which contains a substantial share of characters of both scripts.
+    Mixed,
+}
+
+impl ChineseScript {
+    /// Returns the BCP 47 tag of Chinese written in this script, which is `zh-Hans`
+    /// or `zh-Hant`. Text in mixed scripts is tagged as `zh` without a script subtag.
+    pub fn bcp47_tag(&self) -> &'static str {
+        match self {
+            ChineseScript::Simplified => "zh-Hans",
+            ChineseScript::Traditional => "zh-Hant",
+            ChineseScript::Mixed => "zh",
+        }
+    }
+
+    /// Classifies the given text by counting the characters which only occur in
+    /// one of both scripts. Characters shared by both scripts are ignored, so
+    /// [`None`] is returned if the text does not contain any distinctive character.
+    pub(crate) fn classify(text: &str) -> Option<Self> {
+        let mut simplified_count = 0;
+        let mut traditional_count = 0;
+
+        for ch in text.chars() {
+            if SIMPLIFIED_CHARACTER_SET.contains(&ch) {
+                simplified_count += 1;
+            } else if TRADITIONAL_CHARACTER_SET.contains(&ch) {
+                traditional_count += 1;
+            }
+        }
+
+        let total_count = simplified_count + traditional_count;
+
+        if total_count == 0 {
+            return None;
+        }
+
+        let minority_share = simplified_count.min(traditional_count) as f64 / total_count as f64;
+
+        if minority_share >= MIXED_SCRIPT_MINIMUM_SHARE {
+            Some(ChineseScript::Mixed)
+        } else if simplified_count > traditional_count {
+            Some(ChineseScript::Simplified)
+        } else {
+            Some(ChineseScript::Traditional)
+        }
+    }
+}
+
+impl Display for ChineseScript {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let debug_repr = format!("{self:?}");
+        write!(f, "{}", debug_repr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn assert_character_tables_are_disjoint() {
+        assert!(SIMPLIFIED_CHARACTER_SET.is_disjoint(&TRADITIONAL_CHARACTER_SET));
+        assert_eq!(
+            SIMPLIFIED_CHARACTER_SET.len(),
+            SIMPLIFIED_ONLY_CHARACTERS.chars().count()
+        );
+        assert_eq!(
+            TRADITIONAL_CHARACTER_SET.len(),
+            TRADITIONAL_ONLY_CHARACTERS.chars().count()
+        );
+    }
+
+    #[test]
+    fn assert_chinese_script_is_classified_correctly() {
+        assert_eq!(
+            ChineseScript::classify("我们学习中文已经三年了"),
+            Some(ChineseScript::Simplified)
+        );
+        assert_eq!(
+            ChineseScript::classify("我們學習中文已經三年了"),
+            Some(ChineseScript::Traditional)
+        );
+        assert_eq!(
+            ChineseScript::classify("这个問題很難"),
+            Some(ChineseScript::Mixed)
+        );
+        assert_eq!(
+            ChineseScript::classify("中文大学"),
+            Some(ChineseScript::Simplified)
+        );
+        assert_eq!(
+            ChineseScript::classify("中文大學"),
+            Some(ChineseScript::Traditional)
+        );
+        assert_eq!(ChineseScript::classify("中文"), None);
+    }
+
+    #[test]
+    fn assert_minor_share_of_other_script_is_tolerated() {
+        assert_eq!(
+            ChineseScript::classify(
+                "我们在这个城市里学习了很多东西，后来还认识了很多朋友和老师，張先生"
+            ),
+            Some(ChineseScript::Simplified)
+        );
+    }
+
+    #[test]
+    fn assert_bcp47_tags_are_correct() {
+        assert_eq!(ChineseScript::Simplified.bcp47_tag(), "zh-Hans");
+        assert_eq!(ChineseScript::Traditional.bcp47_tag(), "zh-Hant");
+        assert_eq!(ChineseScript::Mixed.bcp47_tag(), "zh");
+    }
+}
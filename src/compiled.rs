@@ -0,0 +1,198 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::cmp::Ordering;
+
+use crate::language::Language;
+
+include!(concat!(env!("OUT_DIR"), "/compiled_models.rs"));
+
+/// The ngram models of all enabled languages for a single kind of model and ngram length,
+/// merged into a sorted table by the build script and compiled into the binary. Looking up an ngram
+/// performs a binary search without any allocation.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct CompiledTable {
+    table: &'static [u8],
+    ngram_count: usize,
+    entry_count: usize,
+}
+
+impl CompiledTable {
+    /// Returns the compiled table of the given ngram length, either of the models with or
+    /// without word boundary markers, or [`None`] if no such table has been compiled into
+    /// the binary.
+    pub(crate) fn of(is_word_boundary_model: bool, ngram_length: usize) -> Option<Self> {
+        let table = compiled_table(is_word_boundary_model, ngram_length)?;
+        let ngram_count = read_u32(table, 0) as usize;
+        let entry_count = read_u32(table, 4) as usize;
+        Some(Self {
+            table,
+            ngram_count,
+            entry_count,
+        })
+    }
+
+    /// Returns `true` if the models of the given language, either with or without word
+    /// boundary markers, have been compiled into the binary.
+    pub(crate) fn contains(is_word_boundary_model: bool, language: &Language) -> bool {
+        if is_word_boundary_model {
+            WORD_BOUNDARY_MODEL_LANGUAGES.contains(language)
+        } else {
+            REGULAR_MODEL_LANGUAGES.contains(language)
+        }
+    }
+
+    pub(crate) fn slot_count() -> usize {
+        COMPILED_LANGUAGES.len()
+    }
+
+    pub(crate) fn language_at(slot: usize) -> Language {
+        COMPILED_LANGUAGES[slot]
+    }
+
+    /// Calls the given closure with the slot and log-probability of each language
+    /// containing the given ngram.
+    pub(crate) fn for_each_entry(&self, ngram: &str, mut callback: impl FnMut(usize, f64)) {
+        let Some(index) = self.find(ngram.as_bytes()) else {
+            return;
+        };
+        let start = self.entry_offset(index);
+        let end = self.entry_offset(index + 1);
+
+        for entry in start..end {
+            callback(self.slot_at(entry), self.log_probability_at(entry));
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn len(&self) -> usize {
+        self.ngram_count
+    }
+
+    fn find(&self, ngram: &[u8]) -> Option<usize> {
+        let (mut low, mut high) = (0, self.ngram_count);
+
+        while low < high {
+            let middle = low + (high - low) / 2;
+            match self.ngram_at(middle).cmp(ngram) {
+                Ordering::Less => low = middle + 1,
+                Ordering::Greater => high = middle,
+                Ordering::Equal => return Some(middle),
+            }
+        }
+
+        None
+    }
+
+    fn ngram_at(&self, index: usize) -> &'static [u8] {
+        let start = read_u32(self.table, 8 + index * 4) as usize;
+        let end = read_u32(self.table, 12 + index * 4) as usize;
+        &self.table[self.ngrams_offset() + start..self.ngrams_offset() + end]
+    }
+
+    fn entry_offset(&self, index: usize) -> usize {
+        read_u32(self.table, self.entry_offsets_offset() + index * 4) as usize
+    }
+
+    fn log_probability_at(&self, entry: usize) -> f64 {
+        let offset = self.log_probabilities_offset() + entry * 8;
+        f64::from_le_bytes(self.table[offset..offset + 8].try_into().unwrap())
+    }
+
+    fn slot_at(&self, entry: usize) -> usize {
+        let offset = self.slots_offset() + entry * 2;
+        u16::from_le_bytes(self.table[offset..offset + 2].try_into().unwrap()) as usize
+    }
+
+    fn entry_offsets_offset(&self) -> usize {
+        8 + (self.ngram_count + 1) * 4
+    }
+
+    fn log_probabilities_offset(&self) -> usize {
+        self.entry_offsets_offset() + (self.ngram_count + 1) * 4
+    }
+
+    fn slots_offset(&self) -> usize {
+        self.log_probabilities_offset() + self.entry_count * 8
+    }
+
+    fn ngrams_offset(&self) -> usize {
+        self.slots_offset() + self.entry_count * 2
+    }
+}
+
+fn read_u32(table: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(table[offset..offset + 4].try_into().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json::load_json;
+    use crate::language::Language::{English, German};
+    use crate::model::TrainingDataLanguageModel;
+
+    fn log_probability_of(table: &CompiledTable, language: Language, ngram: &str) -> Option<f64> {
+        let mut log_probability = None;
+        table.for_each_entry(ngram, |slot, value| {
+            if CompiledTable::language_at(slot) == language {
+                log_probability = Some(value);
+            }
+        });
+        log_probability
+    }
+
+    #[test]
+    fn assert_compiled_table_equals_loaded_model() {
+        for ngram_length in 1..=5 {
+            let compiled_table = CompiledTable::of(false, ngram_length).unwrap();
+            let loaded_model =
+                TrainingDataLanguageModel::from_json(&load_json(English, ngram_length).unwrap())
+                    .unwrap();
+
+            assert!(compiled_table.len() >= loaded_model.len());
+            for (ngram, relative_frequency) in loaded_model.iter() {
+                assert_eq!(
+                    log_probability_of(&compiled_table, English, ngram),
+                    Some(relative_frequency.ln())
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn assert_ngram_is_shared_by_languages() {
+        let compiled_table = CompiledTable::of(false, 1).unwrap();
+        assert!(log_probability_of(&compiled_table, English, "e").is_some());
+        assert!(log_probability_of(&compiled_table, German, "e").is_some());
+        assert!(CompiledTable::contains(false, &German));
+    }
+
+    #[test]
+    fn assert_tables_are_only_compiled_for_existing_models() {
+        assert!(CompiledTable::of(false, 5).is_some());
+        assert!(CompiledTable::of(false, 6).is_none());
+        assert!(CompiledTable::of(true, 1).is_none());
+        assert!(!CompiledTable::contains(true, &English));
+    }
+
+    #[test]
+    fn assert_unknown_ngram_is_not_found() {
+        let compiled_table = CompiledTable::of(false, 3).unwrap();
+        assert_eq!(log_probability_of(&compiled_table, English, "äöü"), None);
+        assert_eq!(log_probability_of(&compiled_table, English, ""), None);
+    }
+}
@@ -23,6 +23,9 @@ use regex::Regex;
 use crate::alphabet::CharSet;
 use crate::language::Language;
 
+pub(crate) const WORD_START_MARKER: char = '<';
+pub(crate) const WORD_END_MARKER: char = '>';
+
 pub(crate) static JAPANESE_CHARACTER_SET: Lazy<CharSet> =
     Lazy::new(|| CharSet::from_char_classes(&["Hiragana", "Katakana", "Han"]));
 pub(crate) static MULTIPLE_WHITESPACE: Lazy<Regex> = Lazy::new(|| Regex::new("\\s+").unwrap());
@@ -36,8 +39,24 @@ pub(crate) static TOKENS_WITH_OPTIONAL_WHITESPACE: Lazy<Regex> = Lazy::new(|| {
     )
     .unwrap()
 });
-pub(crate) static TOKENS_WITHOUT_WHITESPACE: Lazy<Regex> =
-    Lazy::new(|| Regex::new("\\p{Han}|\\p{Hangul}|\\p{Hiragana}|\\p{Katakana}|\\p{L}+").unwrap());
+pub(crate) static MARKUP: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?xs)
+        ```.*?```
+        | `[^`\n]+`
+        | <(?:script|style)\b.*?</(?:script|style)>
+        | <[!/]?[a-zA-Z][^<>]*>
+        | &(?:[a-zA-Z]+|\#[0-9]+|\#x[0-9a-fA-F]+);
+        | (?:https?|ftp)://\S+
+        | www\.\S+
+        | [\w.+-]+@[\w-]+(?:\.[\w-]+)+
+        | \B@\w+
+        | \B\#(?P<hashtag>\w+)
+        | [\p{Extended_Pictographic}\p{Emoji_Modifier}\x{FE0F}\x{200D}\x{1F1E6}-\x{1F1FF}]+
+        ",
+    )
+    .unwrap()
+});
 
 pub(crate) static CHARS_TO_LANGUAGES_MAPPING: Lazy<HashMap<&'static str, HashSet<Language>>> =
     Lazy::new(|| {
@@ -0,0 +1,437 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::HashSet;
+use std::fmt::{Debug, Formatter, Result};
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::alphabet::Alphabet;
+
+pub(crate) const CUSTOM_LANGUAGE_NAME_MESSAGE: &str =
+    "The name of a custom language must consist of ASCII letters only";
+pub(crate) const CUSTOM_ISO_CODE_639_1_MESSAGE: &str =
+    "The ISO 639-1 code of a custom language must consist of two ASCII letters";
+pub(crate) const CUSTOM_ISO_CODE_639_3_MESSAGE: &str =
+    "The ISO 639-3 code of a custom language must consist of three ASCII letters";
+pub(crate) const CUSTOM_ALPHABETS_MESSAGE: &str =
+    "A custom language must be written in at least one alphabet";
+pub(crate) const CUSTOM_LANGUAGE_CONFLICT_MESSAGE: &str =
+    "The name and ISO codes of a custom language must not be used by another language";
+
+/// The names and ISO 639-1 and 639-3 codes of all built-in languages, including those
+/// whose crate features are disabled, so that custom languages never collide with them.
+const BUILT_IN_LANGUAGES: [(&str, &str, &str); 75] = [
+    ("Afrikaans", "af", "afr"),
+    ("Albanian", "sq", "sqi"),
+    ("Arabic", "ar", "ara"),
+    ("Armenian", "hy", "hye"),
+    ("Azerbaijani", "az", "aze"),
+    ("Basque", "eu", "eus"),
+    ("Belarusian", "be", "bel"),
+    ("Bengali", "bn", "ben"),
+    ("Bokmal", "nb", "nob"),
+    ("Bosnian", "bs", "bos"),
+    ("Bulgarian", "bg", "bul"),
+    ("Catalan", "ca", "cat"),
+    ("Chinese", "zh", "zho"),
+    ("Croatian", "hr", "hrv"),
+    ("Czech", "cs", "ces"),
+    ("Danish", "da", "dan"),
+    ("Dutch", "nl", "nld"),
+    ("English", "en", "eng"),
+    ("Esperanto", "eo", "epo"),
+    ("Estonian", "et", "est"),
+    ("Finnish", "fi", "fin"),
+    ("French", "fr", "fra"),
+    ("Ganda", "lg", "lug"),
+    ("Georgian", "ka", "kat"),
+    ("German", "de", "deu"),
+    ("Greek", "el", "ell"),
+    ("Gujarati", "gu", "guj"),
+    ("Hebrew", "he", "heb"),
+    ("Hindi", "hi", "hin"),
+    ("Hungarian", "hu", "hun"),
+    ("Icelandic", "is", "isl"),
+    ("Indonesian", "id", "ind"),
+    ("Irish", "ga", "gle"),
+    ("Italian", "it", "ita"),
+    ("Japanese", "ja", "jpn"),
+    ("Kazakh", "kk", "kaz"),
+    ("Korean", "ko", "kor"),
+    ("Latin", "la", "lat"),
+    ("Latvian", "lv", "lav"),
+    ("Lithuanian", "lt", "lit"),
+    ("Macedonian", "mk", "mkd"),
+    ("Malay", "ms", "msa"),
+    ("Maori", "mi", "mri"),
+    ("Marathi", "mr", "mar"),
+    ("Mongolian", "mn", "mon"),
+    ("Nynorsk", "nn", "nno"),
+    ("Persian", "fa", "fas"),
+    ("Polish", "pl", "pol"),
+    ("Portuguese", "pt", "por"),
+    ("Punjabi", "pa", "pan"),
+    ("Romanian", "ro", "ron"),
+    ("Russian", "ru", "rus"),
+    ("Serbian", "sr", "srp"),
+    ("Shona", "sn", "sna"),
+    ("Slovak", "sk", "slk"),
+    ("Slovene", "sl", "slv"),
+    ("Somali", "so", "som"),
+    ("Sotho", "st", "sot"),
+    ("Spanish", "es", "spa"),
+    ("Swahili", "sw", "swa"),
+    ("Swedish", "sv", "swe"),
+    ("Tagalog", "tl", "tgl"),
+    ("Tamil", "ta", "tam"),
+    ("Telugu", "te", "tel"),
+    ("Thai", "th", "tha"),
+    ("Tsonga", "ts", "tso"),
+    ("Tswana", "tn", "tsn"),
+    ("Turkish", "tr", "tur"),
+    ("Ukrainian", "uk", "ukr"),
+    ("Urdu", "ur", "urd"),
+    ("Vietnamese", "vi", "vie"),
+    ("Welsh", "cy", "cym"),
+    ("Xhosa", "xh", "xho"),
+    ("Yoruba", "yo", "yor"),
+    ("Zulu", "zu", "zul"),
+];
+
+/// The definitions of all custom languages registered so far, indexed by
+/// their [`CustomLanguage`] handles. Definitions are never removed, so they
+/// can be handed out with a static lifetime.
+static CUSTOM_LANGUAGES: RwLock<Vec<&'static CustomLanguageDefinition>> = RwLock::new(Vec::new());
+
+/// A handle to a language which is not built into *Lingua* but has been registered
+/// at runtime with [`Language::register_custom`].
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct CustomLanguage(u32);
+
+struct CustomLanguageDefinition {
+    name: &'static str,
+    iso_code_639_1: &'static str,
+    iso_code_639_3: &'static str,
+    alphabets: HashSet<Alphabet>,
+    models_directory: PathBuf,
+}
+
+impl CustomLanguage {
+    /// Returns the name of this language as given at registration.
+    pub fn name(&self) -> &'static str {
+        self.definition().name
+    }
+
+    /// Returns the lowercase ISO 639-1 code of this language.
+    pub fn iso_code_639_1(&self) -> &'static str {
+        self.definition().iso_code_639_1
+    }
+
+    /// Returns the lowercase ISO 639-3 code of this language.
+    pub fn iso_code_639_3(&self) -> &'static str {
+        self.definition().iso_code_639_3
+    }
+
+    pub(crate) fn alphabets(&self) -> HashSet<Alphabet> {
+        self.definition().alphabets.clone()
+    }
+
+    pub(crate) fn models_directory(&self) -> &'static Path {
+        &self.definition().models_directory
+    }
+
+    /// Registers a new custom language or returns the existing one
+    /// if exactly the same definition has been registered before.
+    pub(crate) fn register(
+        name: &str,
+        iso_code_639_1: &str,
+        iso_code_639_3: &str,
+        alphabets: &[Alphabet],
+        models_directory: &Path,
+    ) -> std::result::Result<Self, &'static str> {
+        if name.is_empty() || !name.chars().all(|ch| ch.is_ascii_alphabetic()) {
+            return Err(CUSTOM_LANGUAGE_NAME_MESSAGE);
+        }
+        if !is_valid_iso_code(iso_code_639_1, 2) {
+            return Err(CUSTOM_ISO_CODE_639_1_MESSAGE);
+        }
+        if !is_valid_iso_code(iso_code_639_3, 3) {
+            return Err(CUSTOM_ISO_CODE_639_3_MESSAGE);
+        }
+        if alphabets.is_empty() {
+            return Err(CUSTOM_ALPHABETS_MESSAGE);
+        }
+
+        let iso_code_639_1 = iso_code_639_1.to_ascii_lowercase();
+        let iso_code_639_3 = iso_code_639_3.to_ascii_lowercase();
+
+        let is_built_in = BUILT_IN_LANGUAGES
+            .iter()
+            .any(|(built_in_name, code_1, code_3)| {
+                built_in_name.eq_ignore_ascii_case(name)
+                    || *code_1 == iso_code_639_1
+                    || *code_3 == iso_code_639_3
+            });
+
+        if is_built_in {
+            return Err(CUSTOM_LANGUAGE_CONFLICT_MESSAGE);
+        }
+
+        let alphabets = alphabets.iter().copied().collect::<HashSet<_>>();
+        let mut custom_languages = CUSTOM_LANGUAGES.write().unwrap();
+
+        for (index, registered) in custom_languages.iter().enumerate() {
+            let is_same_name = registered.name.eq_ignore_ascii_case(name);
+            let is_same_iso_code_639_1 = registered.iso_code_639_1 == iso_code_639_1;
+            let is_same_iso_code_639_3 = registered.iso_code_639_3 == iso_code_639_3;
+
+            if is_same_name
+                && is_same_iso_code_639_1
+                && is_same_iso_code_639_3
+                && registered.alphabets == alphabets
+                && registered.models_directory == models_directory
+            {
+                return Ok(Self(index as u32));
+            }
+            if is_same_name || is_same_iso_code_639_1 || is_same_iso_code_639_3 {
+                return Err(CUSTOM_LANGUAGE_CONFLICT_MESSAGE);
+            }
+        }
+
+        custom_languages.push(Box::leak(Box::new(CustomLanguageDefinition {
+            name: Box::leak(name.into()),
+            iso_code_639_1: Box::leak(iso_code_639_1.into_boxed_str()),
+            iso_code_639_3: Box::leak(iso_code_639_3.into_boxed_str()),
+            alphabets,
+            models_directory: models_directory.to_path_buf(),
+        })));
+
+        Ok(Self((custom_languages.len() - 1) as u32))
+    }
+
+    pub(crate) fn find_by_name(name: &str) -> Option<Self> {
+        Self::find(|definition| definition.name.eq_ignore_ascii_case(name))
+    }
+
+    pub(crate) fn find_by_iso_code_639_1(iso_code: &str) -> Option<Self> {
+        Self::find(|definition| definition.iso_code_639_1.eq_ignore_ascii_case(iso_code))
+    }
+
+    pub(crate) fn find_by_iso_code_639_3(iso_code: &str) -> Option<Self> {
+        Self::find(|definition| definition.iso_code_639_3.eq_ignore_ascii_case(iso_code))
+    }
+
+    fn find(predicate: impl Fn(&CustomLanguageDefinition) -> bool) -> Option<Self> {
+        CUSTOM_LANGUAGES
+            .read()
+            .unwrap()
+            .iter()
+            .position(|definition| predicate(definition))
+            .map(|index| Self(index as u32))
+    }
+
+    fn definition(&self) -> &'static CustomLanguageDefinition {
+        CUSTOM_LANGUAGES.read().unwrap()[self.0 as usize]
+    }
+}
+
+impl Debug for CustomLanguage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Custom languages are serialized by their names, which identify them
+/// as long as they are registered in the deserializing process as well.
+impl Serialize for CustomLanguage {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl<'de> Deserialize<'de> for CustomLanguage {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Self::find_by_name(&name).ok_or_else(|| {
+            serde::de::Error::custom(format!("Custom language '{name}' has not been registered"))
+        })
+    }
+}
+
+fn is_valid_iso_code(iso_code: &str, length: usize) -> bool {
+    iso_code.len() == length && iso_code.chars().all(|ch| ch.is_ascii_alphabetic())
+}
+
+#[cfg(test)]
+mod tests {
+    use strum::IntoEnumIterator;
+
+    use crate::language::Language;
+
+    use super::*;
+
+    #[test]
+    fn assert_custom_language_can_be_registered() {
+        let directory = Path::new("/models/galician");
+        let language =
+            CustomLanguage::register("Galician", "GL", "glg", &[Alphabet::Latin], directory)
+                .unwrap();
+
+        assert_eq!(language.name(), "Galician");
+        assert_eq!(language.iso_code_639_1(), "gl");
+        assert_eq!(language.iso_code_639_3(), "glg");
+        assert_eq!(language.alphabets(), HashSet::from([Alphabet::Latin]));
+        assert_eq!(language.models_directory(), directory);
+
+        assert_eq!(CustomLanguage::find_by_name("galician"), Some(language));
+        assert_eq!(CustomLanguage::find_by_iso_code_639_1("gl"), Some(language));
+        assert_eq!(
+            CustomLanguage::find_by_iso_code_639_3("GLG"),
+            Some(language)
+        );
+    }
+
+    #[test]
+    fn assert_identical_registration_returns_same_custom_language() {
+        let register = || {
+            CustomLanguage::register(
+                "Luxembourgish",
+                "lb",
+                "ltz",
+                &[Alphabet::Latin],
+                Path::new("/models/luxembourgish"),
+            )
+        };
+        assert_eq!(register(), register());
+    }
+
+    #[test]
+    fn assert_conflicting_registration_is_rejected() {
+        let register = |name, iso_code_639_1, iso_code_639_3| {
+            CustomLanguage::register(
+                name,
+                iso_code_639_1,
+                iso_code_639_3,
+                &[Alphabet::Latin],
+                Path::new("/models/kurmanji"),
+            )
+        };
+        assert!(register("Kurmanji", "ku", "kmr").is_ok());
+        assert_eq!(
+            register("Kurmanji", "ku", "kur"),
+            Err(CUSTOM_LANGUAGE_CONFLICT_MESSAGE)
+        );
+        assert_eq!(
+            register("Kurdish", "ku", "kur"),
+            Err(CUSTOM_LANGUAGE_CONFLICT_MESSAGE)
+        );
+        assert_eq!(
+            register("English", "xx", "xxx"),
+            Err(CUSTOM_LANGUAGE_CONFLICT_MESSAGE)
+        );
+        assert_eq!(
+            register("Klingon", "en", "tlh"),
+            Err(CUSTOM_LANGUAGE_CONFLICT_MESSAGE)
+        );
+    }
+
+    #[test]
+    fn assert_registration_conflicting_with_any_built_in_language_is_rejected() {
+        let register = |name, iso_code_639_1, iso_code_639_3| {
+            CustomLanguage::register(
+                name,
+                iso_code_639_1,
+                iso_code_639_3,
+                &[Alphabet::Latin],
+                Path::new("/models/conflicting"),
+            )
+        };
+
+        for (name, iso_code_639_1, iso_code_639_3) in BUILT_IN_LANGUAGES {
+            assert_eq!(
+                register(name, "xx", "xxx"),
+                Err(CUSTOM_LANGUAGE_CONFLICT_MESSAGE)
+            );
+            assert_eq!(
+                register("Conflicting", iso_code_639_1, "xxx"),
+                Err(CUSTOM_LANGUAGE_CONFLICT_MESSAGE)
+            );
+            assert_eq!(
+                register("Conflicting", "xx", iso_code_639_3),
+                Err(CUSTOM_LANGUAGE_CONFLICT_MESSAGE)
+            );
+        }
+    }
+
+    #[test]
+    fn assert_built_in_languages_of_current_build_are_known() {
+        for language in Language::iter() {
+            assert!(
+                BUILT_IN_LANGUAGES.contains(&(
+                    language.to_string().as_str(),
+                    language.iso_code_639_1().to_string().as_str(),
+                    language.iso_code_639_3().to_string().as_str(),
+                )),
+                "{language} is missing"
+            );
+        }
+    }
+
+    #[test]
+    fn assert_custom_language_is_serialized_by_name() {
+        let language = CustomLanguage::register(
+            "Faroese",
+            "fo",
+            "fao",
+            &[Alphabet::Latin],
+            Path::new("/models/faroese"),
+        )
+        .unwrap();
+
+        assert_eq!(serde_json::to_string(&language).unwrap(), "\"Faroese\"");
+        assert_eq!(
+            serde_json::from_str::<CustomLanguage>("\"Faroese\"").unwrap(),
+            language
+        );
+        assert!(serde_json::from_str::<CustomLanguage>("\"Unregistered\"").is_err());
+    }
+
+    #[test]
+    fn assert_invalid_registration_is_rejected() {
+        let directory = Path::new("/models/invalid");
+        let latin = &[Alphabet::Latin];
+
+        assert_eq!(
+            CustomLanguage::register("Old Norse", "xx", "xxx", latin, directory),
+            Err(CUSTOM_LANGUAGE_NAME_MESSAGE)
+        );
+        assert_eq!(
+            CustomLanguage::register("Invalid", "xxx", "xxx", latin, directory),
+            Err(CUSTOM_ISO_CODE_639_1_MESSAGE)
+        );
+        assert_eq!(
+            CustomLanguage::register("Invalid", "xx", "x1x", latin, directory),
+            Err(CUSTOM_ISO_CODE_639_3_MESSAGE)
+        );
+        assert_eq!(
+            CustomLanguage::register("Invalid", "xx", "xxx", &[], directory),
+            Err(CUSTOM_ALPHABETS_MESSAGE)
+        );
+    }
+}
@@ -14,11 +14,18 @@
  * limitations under the License.
  */
 
+use std::cell::Cell;
 use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
+use std::fs::File;
 use std::hash::Hash;
+use std::io;
+use std::io::{BufReader, BufWriter};
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
-use std::sync::RwLock;
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+use std::sync::{Arc, Mutex, RwLock};
 
 use ahash::AHashMap;
 use compact_str::CompactString;
@@ -30,24 +37,134 @@ use rayon::prelude::*;
 use strum::IntoEnumIterator;
 
 use crate::alphabet::Alphabet;
-use crate::constant::{
-    CHARS_TO_LANGUAGES_MAPPING, JAPANESE_CHARACTER_SET, LETTERS, TOKENS_WITHOUT_WHITESPACE,
-    TOKENS_WITH_OPTIONAL_WHITESPACE,
+use crate::cache::ModelCache;
+use crate::chinese::ChineseScript;
+#[cfg(feature = "compiled-models")]
+use crate::compiled::CompiledTable;
+use crate::constant::JAPANESE_CHARACTER_SET;
+use crate::discriminator::PairDiscriminator;
+use crate::folding::{
+    fold_case_into, fold_diacritics, fold_turkic_case_into, has_dotless_capital_i,
+};
+use crate::json::{
+    has_unigram_model_only, load_counts_json, load_json, load_word_boundary_json, load_word_json,
 };
-use crate::json::load_json;
 use crate::language::Language;
-use crate::model::{TestDataLanguageModel, TrainingDataLanguageModel};
+use crate::macrolanguage::{CollapsedLanguage, MacroLanguage};
+use crate::model::{
+    mark_word_boundaries, TestDataLanguageModel, TrainingDataLanguageModel, WordModel,
+};
+use crate::ngram::MAXIMUM_NGRAM_LENGTH;
+use crate::normalizer::{normalize, NormalizedText, Normalizer};
+use crate::outcome::DetectionOutcome;
+use crate::progress::PreloadingProgress;
 use crate::result::DetectionResult;
+use crate::rule::CharacterRules;
+use crate::smoothing::{NgramCounts, Smoothing};
+use crate::snapshot::{invalid_data, SnapshotReader, SnapshotWriter};
+use crate::status::{ModelKind, ModelLoadError, ModelLoadFailure, ModelStatus};
+use crate::table::{NgramScorer, NgramTable, NgramTableRef};
+use crate::tokenizer::{DefaultTokenizer, Tokenizer};
+use crate::variant::{LanguageVariant, LanguageVariantModel};
+
+type LazyNgramTable = Lazy<RwLock<NgramTable>>;
+type StaticNgramTable = &'static RwLock<NgramTable>;
+type NgramTableArray<'a> = [Option<&'a NgramTable>; MAXIMUM_NGRAM_LENGTH];
+
+/// The ngram models of all languages for each supported ngram length, merged into one table
+/// per ngram length and cached separately for each combination of the accent-insensitive mode
+/// and the word boundary mode.
+static LANGUAGE_MODELS: [[LazyNgramTable; MAXIMUM_NGRAM_LENGTH]; 4] =
+    [const { [const { Lazy::new(|| RwLock::new(NgramTable::default())) }; MAXIMUM_NGRAM_LENGTH] };
+        4];
+
+type ModelLoadFailureMap = HashMap<(Language, ModelKind, usize), ModelLoadError>;
+type StaticModelLoadFailureMap = &'static RwLock<ModelLoadFailureMap>;
+
+/// The language models which have failed to load, kept in the same variants as the
+/// language models. Locked only after the language models of the same variant.
+static MODEL_LOAD_FAILURES: [Lazy<RwLock<ModelLoadFailureMap>>; 4] =
+    [const { Lazy::new(|| RwLock::new(HashMap::new())) }; 4];
+
+/// Held for reading while language models are looked up and for writing while they are
+/// evicted, so that no model is evicted between being loaded and being read.
+static MODEL_EVICTION_LOCK: RwLock<()> = RwLock::new(());
+
+type LazyNgramCountsMap = Lazy<RwLock<HashMap<Language, NgramCounts>>>;
+type StaticNgramCountsMap = &'static RwLock<HashMap<Language, NgramCounts>>;
+
+/// The absolute ngram frequencies of all languages which the smoothing schemes are
+/// computed from, cached in the same variants as the language models.
+static NGRAM_COUNTS: [LazyNgramCountsMap; 4] =
+    [const { Lazy::new(|| RwLock::new(HashMap::new())) }; 4];
+
+type LazyWordModelMap = Lazy<RwLock<HashMap<Language, WordModel>>>;
+type StaticWordModelMap = &'static RwLock<HashMap<Language, WordModel>>;
+
+/// The word models of all languages, cached separately for the accent-insensitive mode.
+static WORD_MODELS: [LazyWordModelMap; 2] =
+    [const { Lazy::new(|| RwLock::new(HashMap::new())) }; 2];
+
+/// The maximum number of words of a text for which the word models are consulted.
+const WORD_MODEL_MAXIMUM_WORD_COUNT: usize = 2;
+
+/// The number of characters from which on the text is regarded as long text.
+const LONG_TEXT_CHARACTER_COUNT: usize = 120;
+
+/// The maximum capacity of the buffer for case-folded text which is kept for reuse,
+/// so that a single very long text does not occupy memory for the lifetime of its thread.
+const MAXIMUM_REUSED_BUFFER_CAPACITY: usize = 4096;
+
+thread_local! {
+    /// The buffer which text is case-folded into before it is split into words,
+    /// reused by all detections on the same thread.
+    static FOLDED_TEXT_BUFFER: Cell<String> = const { Cell::new(String::new()) };
+}
 
-type LazyLanguageModelMap = Lazy<RwLock<HashMap<Language, AHashMap<CompactString, f64>>>>;
-type StaticLanguageModelMap = &'static RwLock<HashMap<Language, AHashMap<CompactString, f64>>>;
-type LanguageModelArray<'a> = [Option<&'a HashMap<Language, AHashMap<CompactString, f64>>>; 5];
+/// The settings which a [LanguageDetector] is created with.
+pub(crate) struct DetectorSettings {
+    pub(crate) minimum_relative_distance: f64,
+    pub(crate) is_every_language_model_preloaded: bool,
+    pub(crate) is_every_language_model_preloaded_in_background: bool,
+    pub(crate) is_low_accuracy_mode_enabled: bool,
+    pub(crate) is_accent_insensitive_mode_enabled: bool,
+    pub(crate) is_derived_character_rules_enabled: bool,
+    pub(crate) is_word_boundary_mode_enabled: bool,
+    pub(crate) short_text_ngram_lengths: RangeInclusive<usize>,
+    pub(crate) long_text_ngram_lengths: RangeInclusive<usize>,
+    pub(crate) smoothing: Smoothing,
+    pub(crate) word_model_weight: f64,
+    pub(crate) normalizers: Vec<Normalizer>,
+    pub(crate) tokenizer: Arc<dyn Tokenizer>,
+    pub(crate) language_variants_directory: Option<PathBuf>,
+    pub(crate) pair_discriminators_directory: Option<PathBuf>,
+    pub(crate) macro_languages: HashSet<MacroLanguage>,
+    pub(crate) memory_budget: Option<usize>,
+}
 
-static UNIGRAM_MODELS: LazyLanguageModelMap = Lazy::new(|| RwLock::new(HashMap::new()));
-static BIGRAM_MODELS: LazyLanguageModelMap = Lazy::new(|| RwLock::new(HashMap::new()));
-static TRIGRAM_MODELS: LazyLanguageModelMap = Lazy::new(|| RwLock::new(HashMap::new()));
-static QUADRIGRAM_MODELS: LazyLanguageModelMap = Lazy::new(|| RwLock::new(HashMap::new()));
-static FIVEGRAM_MODELS: LazyLanguageModelMap = Lazy::new(|| RwLock::new(HashMap::new()));
+impl Default for DetectorSettings {
+    fn default() -> Self {
+        Self {
+            minimum_relative_distance: 0.0,
+            is_every_language_model_preloaded: false,
+            is_every_language_model_preloaded_in_background: false,
+            is_low_accuracy_mode_enabled: false,
+            is_accent_insensitive_mode_enabled: false,
+            is_derived_character_rules_enabled: false,
+            is_word_boundary_mode_enabled: false,
+            short_text_ngram_lengths: 1..=5,
+            long_text_ngram_lengths: 3..=3,
+            smoothing: Smoothing::None,
+            word_model_weight: 0.0,
+            normalizers: vec![],
+            tokenizer: Arc::new(DefaultTokenizer),
+            language_variants_directory: None,
+            pair_discriminators_directory: None,
+            macro_languages: HashSet::new(),
+            memory_budget: None,
+        }
+    }
+}
 
 /// This struct detects the language of given input text.
 #[cfg_attr(feature = "python", pyo3::prelude::pyclass)]
@@ -55,58 +172,263 @@ pub struct LanguageDetector {
     languages: HashSet<Language>,
     minimum_relative_distance: f64,
     is_low_accuracy_mode_enabled: bool,
-    languages_with_unique_characters: HashSet<Language>,
+    is_accent_insensitive_mode_enabled: bool,
+    is_word_boundary_mode_enabled: bool,
+    short_text_ngram_lengths: RangeInclusive<usize>,
+    long_text_ngram_lengths: RangeInclusive<usize>,
+    smoothing: Smoothing,
+    word_model_weight: f64,
+    normalizers: Vec<Normalizer>,
+    tokenizer: Arc<dyn Tokenizer>,
+    character_rules: CharacterRules,
     one_language_alphabets: HashMap<Alphabet, Language>,
-    unigram_language_models: StaticLanguageModelMap,
-    bigram_language_models: StaticLanguageModelMap,
-    trigram_language_models: StaticLanguageModelMap,
-    quadrigram_language_models: StaticLanguageModelMap,
-    fivegram_language_models: StaticLanguageModelMap,
+    language_models: [StaticNgramTable; MAXIMUM_NGRAM_LENGTH],
+    is_compiled_model_lookup_enabled: bool,
+    memory_budget: Option<usize>,
+    model_cache: Arc<Mutex<ModelCache>>,
+    model_load_failures: StaticModelLoadFailureMap,
+    preloaded_language_count: Option<Arc<AtomicUsize>>,
+    ngram_counts: StaticNgramCountsMap,
+    word_models: StaticWordModelMap,
+    language_variant_models: Vec<LanguageVariantModel>,
+    pair_discriminators: Vec<PairDiscriminator>,
+    macro_languages: HashSet<MacroLanguage>,
 }
 
 impl LanguageDetector {
-    pub(crate) fn from(
-        languages: HashSet<Language>,
-        minimum_relative_distance: f64,
-        is_every_language_model_preloaded: bool,
-        is_low_accuracy_mode_enabled: bool,
-    ) -> Self {
-        let mut detector = Self {
+    pub(crate) fn from(languages: HashSet<Language>, settings: DetectorSettings) -> Self {
+        let DetectorSettings {
+            minimum_relative_distance,
+            is_every_language_model_preloaded,
+            is_every_language_model_preloaded_in_background,
+            is_low_accuracy_mode_enabled,
+            is_accent_insensitive_mode_enabled,
+            is_derived_character_rules_enabled,
+            is_word_boundary_mode_enabled,
+            short_text_ngram_lengths,
+            long_text_ngram_lengths,
+            smoothing,
+            word_model_weight,
+            normalizers,
+            tokenizer,
+            language_variants_directory,
+            pair_discriminators_directory,
+            macro_languages,
+            memory_budget,
+        } = settings;
+
+        let variant_index = usize::from(is_accent_insensitive_mode_enabled)
+            + 2 * usize::from(is_word_boundary_mode_enabled);
+        let language_models = &LANGUAGE_MODELS[variant_index];
+
+        let character_rules = if is_derived_character_rules_enabled {
+            CharacterRules::derived_from_unigram_models(&languages)
+        } else {
+            CharacterRules::built_in(&languages)
+        };
+
+        let detector = Self {
             languages: languages.clone(),
             minimum_relative_distance,
             is_low_accuracy_mode_enabled,
-            languages_with_unique_characters: collect_languages_with_unique_characters(&languages),
+            is_accent_insensitive_mode_enabled,
+            is_word_boundary_mode_enabled,
+            short_text_ngram_lengths,
+            long_text_ngram_lengths,
+            smoothing,
+            word_model_weight,
+            normalizers,
+            tokenizer,
+            character_rules,
             one_language_alphabets: collect_one_language_alphabets(&languages),
-            unigram_language_models: &UNIGRAM_MODELS,
-            bigram_language_models: &BIGRAM_MODELS,
-            trigram_language_models: &TRIGRAM_MODELS,
-            quadrigram_language_models: &QUADRIGRAM_MODELS,
-            fivegram_language_models: &FIVEGRAM_MODELS,
+            language_models: std::array::from_fn(|i| &*language_models[i]),
+            is_compiled_model_lookup_enabled: is_compiled_model_lookup_enabled(
+                is_accent_insensitive_mode_enabled,
+            ),
+            memory_budget,
+            model_cache: Arc::new(Mutex::new(ModelCache::default())),
+            model_load_failures: &MODEL_LOAD_FAILURES[variant_index],
+            preloaded_language_count: (is_every_language_model_preloaded
+                || is_every_language_model_preloaded_in_background)
+                .then(|| Arc::new(AtomicUsize::new(0))),
+            ngram_counts: &NGRAM_COUNTS[variant_index],
+            word_models: &WORD_MODELS[usize::from(is_accent_insensitive_mode_enabled)],
+            language_variant_models: language_variants_directory
+                .map(|directory| LanguageVariantModel::load_all(&directory, &languages))
+                .unwrap_or_default(),
+            pair_discriminators: pair_discriminators_directory
+                .map(|directory| PairDiscriminator::load_all(&directory, &languages))
+                .unwrap_or_default(),
+            macro_languages,
         };
 
         if is_every_language_model_preloaded {
-            detector.preload_language_models(&languages);
+            detector.preload_language_models();
+        } else if is_every_language_model_preloaded_in_background {
+            detector.preload_language_models_in_background();
         }
 
         detector
     }
 
-    fn preload_language_models(&mut self, languages: &HashSet<Language>) {
+    fn preload_language_models(&self) {
         #[cfg(not(target_family = "wasm"))]
-        let languages_iter = languages.par_iter();
+        let languages_iter = self.languages.par_iter();
         #[cfg(target_family = "wasm")]
-        let languages_iter = languages.iter();
+        let languages_iter = self.languages.iter();
 
         languages_iter.for_each(|language| {
-            self.load_language_models(self.trigram_language_models, language, 3);
-
-            if !self.is_low_accuracy_mode_enabled {
-                self.load_language_models(self.unigram_language_models, language, 1);
-                self.load_language_models(self.bigram_language_models, language, 2);
-                self.load_language_models(self.quadrigram_language_models, language, 4);
-                self.load_language_models(self.fivegram_language_models, language, 5);
+            self.load_all_models(language);
+            if let Some(preloaded_language_count) = &self.preloaded_language_count {
+                preloaded_language_count.fetch_add(1, AtomicOrdering::SeqCst);
             }
         });
+
+        if self.smoothing == Smoothing::None {
+            self.enforce_memory_budget(&self.languages, &self.used_ngram_lengths());
+        }
+    }
+
+    /// Loads all models of the given language which this detector uses.
+    fn load_all_models(&self, language: &Language) {
+        if self.word_model_weight > 0.0 {
+            self.load_word_model(language);
+        }
+        if self.smoothing != Smoothing::None {
+            self.load_ngram_counts(language);
+        } else {
+            for ngram_length in self.used_ngram_lengths() {
+                self.load_language_models(
+                    self.language_models[ngram_length - 1],
+                    language,
+                    ngram_length,
+                );
+            }
+        }
+    }
+
+    /// Preloads the language models on a separate thread with a copy of this detector,
+    /// which shares the same language models and progress counter.
+    fn preload_language_models_in_background(&self) {
+        let loader = Self {
+            languages: self.languages.clone(),
+            minimum_relative_distance: self.minimum_relative_distance,
+            is_low_accuracy_mode_enabled: self.is_low_accuracy_mode_enabled,
+            is_accent_insensitive_mode_enabled: self.is_accent_insensitive_mode_enabled,
+            is_word_boundary_mode_enabled: self.is_word_boundary_mode_enabled,
+            short_text_ngram_lengths: self.short_text_ngram_lengths.clone(),
+            long_text_ngram_lengths: self.long_text_ngram_lengths.clone(),
+            smoothing: self.smoothing,
+            word_model_weight: self.word_model_weight,
+            normalizers: self.normalizers.clone(),
+            tokenizer: self.tokenizer.clone(),
+            character_rules: self.character_rules.clone(),
+            one_language_alphabets: self.one_language_alphabets.clone(),
+            language_models: self.language_models,
+            is_compiled_model_lookup_enabled: self.is_compiled_model_lookup_enabled,
+            memory_budget: self.memory_budget,
+            model_cache: self.model_cache.clone(),
+            model_load_failures: self.model_load_failures,
+            preloaded_language_count: self.preloaded_language_count.clone(),
+            ngram_counts: self.ngram_counts,
+            word_models: self.word_models,
+            language_variant_models: vec![],
+            pair_discriminators: vec![],
+            macro_languages: self.macro_languages.clone(),
+        };
+
+        #[cfg(not(target_family = "wasm"))]
+        std::thread::spawn(move || loader.preload_language_models());
+        #[cfg(target_family = "wasm")]
+        loader.preload_language_models();
+    }
+
+    /// Returns how far the language models have been preloaded if this detector has been
+    /// built with
+    /// [`LanguageDetectorBuilder::with_preloaded_language_models`](crate::LanguageDetectorBuilder::with_preloaded_language_models)
+    /// or
+    /// [`LanguageDetectorBuilder::with_preloaded_language_models_in_background`](crate::LanguageDetectorBuilder::with_preloaded_language_models_in_background),
+    /// or `None` otherwise.
+    pub fn preloading_progress(&self) -> Option<PreloadingProgress> {
+        self.preloaded_language_count
+            .as_ref()
+            .map(|preloaded_language_count| PreloadingProgress {
+                loaded_language_count: preloaded_language_count.load(AtomicOrdering::SeqCst),
+                total_language_count: self.languages.len(),
+            })
+    }
+
+    /// Reports which ngram models of the languages of this detector have been loaded so far
+    /// and which models have failed to load, for instance because a model file is missing
+    /// or corrupt. Besides the ngram models, this includes the ngram counts used for
+    /// smoothing and the word models.
+    ///
+    /// Languages whose models have failed to load are never detected by these models.
+    /// Failed models are not loaded again until
+    /// [`unload_language_models`](#method.unload_language_models) is called.
+    pub fn model_status(&self) -> ModelStatus {
+        let model_load_failures = self.model_load_failures.read().unwrap().clone();
+        let mut loaded_models = vec![];
+        let mut failures = vec![];
+
+        for language in self.languages.iter().sorted() {
+            for ngram_length in self.used_ngram_lengths() {
+                if ngram_length > 1 && has_unigram_model_only(*language) {
+                    continue;
+                }
+                if let Some(error) =
+                    model_load_failures.get(&(*language, ModelKind::Ngrams, ngram_length))
+                {
+                    failures.push(ModelLoadFailure {
+                        language: *language,
+                        kind: ModelKind::Ngrams,
+                        ngram_length,
+                        error: error.clone(),
+                    });
+                } else if self.has_compiled_models(language)
+                    || self.language_models[ngram_length - 1]
+                        .read()
+                        .unwrap()
+                        .contains(language)
+                {
+                    loaded_models.push((*language, ngram_length));
+                }
+            }
+        }
+
+        for ((language, kind, ngram_length), error) in model_load_failures {
+            if kind != ModelKind::Ngrams && self.languages.contains(&language) {
+                failures.push(ModelLoadFailure {
+                    language,
+                    kind,
+                    ngram_length,
+                    error,
+                });
+            }
+        }
+        failures.sort_by_key(|failure| (failure.language, failure.kind, failure.ngram_length));
+
+        ModelStatus {
+            loaded_models,
+            failures,
+        }
+    }
+
+    fn maximum_ngram_length(&self) -> usize {
+        *self
+            .short_text_ngram_lengths
+            .end()
+            .max(self.long_text_ngram_lengths.end())
+    }
+
+    fn used_ngram_lengths(&self) -> Vec<usize> {
+        let mut ngram_lengths = self.long_text_ngram_lengths.clone().collect_vec();
+        if !self.is_low_accuracy_mode_enabled {
+            ngram_lengths.extend(self.short_text_ngram_lengths.clone());
+        }
+        ngram_lengths.sort_unstable();
+        ngram_lengths.dedup();
+        ngram_lengths
     }
 
     /// Clears all language models loaded by this [`LanguageDetector`] instance
@@ -117,52 +439,370 @@ impl LanguageDetector {
         #[cfg(target_family = "wasm")]
         let languages_iter = self.languages.iter();
 
-        languages_iter.for_each(|language| {
-            self.trigram_language_models
-                .write()
-                .unwrap()
-                .remove(language);
+        let ngram_lengths = self.used_ngram_lengths();
 
-            if !self.is_low_accuracy_mode_enabled {
-                self.unigram_language_models
-                    .write()
-                    .unwrap()
-                    .remove(language);
-                self.bigram_language_models
-                    .write()
-                    .unwrap()
-                    .remove(language);
-                self.quadrigram_language_models
-                    .write()
-                    .unwrap()
-                    .remove(language);
-                self.fivegram_language_models
-                    .write()
+        languages_iter.for_each(|language| {
+            for ngram_length in ngram_lengths.iter() {
+                self.model_cache
+                    .lock()
                     .unwrap()
-                    .remove(language);
+                    .remove(*language, *ngram_length);
             }
+            self.ngram_counts.write().unwrap().remove(language);
+            self.word_models.write().unwrap().remove(language);
         });
 
-        self.trigram_language_models
+        self.model_load_failures
             .write()
             .unwrap()
-            .shrink_to_fit();
+            .retain(|(language, _, _), _| !self.languages.contains(language));
 
-        if !self.is_low_accuracy_mode_enabled {
-            self.unigram_language_models
+        self.ngram_counts.write().unwrap().shrink_to_fit();
+        self.word_models.write().unwrap().shrink_to_fit();
+
+        #[cfg(not(target_family = "wasm"))]
+        let ngram_lengths_iter = ngram_lengths.par_iter();
+        #[cfg(target_family = "wasm")]
+        let ngram_lengths_iter = ngram_lengths.iter();
+
+        ngram_lengths_iter.for_each(|ngram_length| {
+            self.language_models[ngram_length - 1]
                 .write()
                 .unwrap()
-                .shrink_to_fit();
-            self.bigram_language_models.write().unwrap().shrink_to_fit();
-            self.quadrigram_language_models
+                .remove(&self.languages);
+        });
+    }
+
+    /// Writes this detector to a snapshot file from which it can be restored with
+    /// [`from_snapshot`](#method.from_snapshot) without decompressing and parsing
+    /// the language models again.
+    ///
+    /// The snapshot contains the settings of this detector and all models it uses,
+    /// including the models of the language variants and the pair discriminators.
+    /// Models which have not been loaded yet are loaded before. A custom tokenizer
+    /// cannot be stored, so restored detectors use the default tokenizer.
+    pub fn write_snapshot<P: AsRef<Path>>(&self, file_path: P) -> io::Result<()> {
+        let _eviction_lock = MODEL_EVICTION_LOCK.read().unwrap();
+
+        for language in self.languages.iter() {
+            self.load_all_models(language);
+        }
+
+        let mut writer = SnapshotWriter::new(BufWriter::new(File::create(file_path)?));
+        writer.write_header(&self.languages)?;
+        self.write_snapshot_settings(&mut writer)?;
+        self.write_snapshot_models(&mut writer)?;
+        writer.flush()
+    }
+
+    /// Restores a detector from a snapshot file written by
+    /// [`write_snapshot`](#method.write_snapshot).
+    ///
+    /// The language models of the snapshot are added to the models shared by all
+    /// detectors. Custom languages must have been registered before with the same
+    /// names and ISO codes as when the snapshot has been written.
+    ///
+    /// ⚠ Returns an error of kind [`InvalidData`](io::ErrorKind::InvalidData) if the file
+    /// is not a snapshot or if it has been written by another version of this library
+    /// or with other language models or built-in languages enabled by crate features.
+    pub fn from_snapshot<P: AsRef<Path>>(file_path: P) -> io::Result<Self> {
+        let mut reader = SnapshotReader::new(BufReader::new(File::open(file_path)?));
+        reader.read_header()?;
+
+        let mut detector = Self::read_snapshot_settings(&mut reader)?;
+        detector.read_snapshot_models(&mut reader)?;
+
+        if detector.smoothing == Smoothing::None {
+            detector.enforce_memory_budget(&detector.languages, &detector.used_ngram_lengths());
+        }
+
+        Ok(detector)
+    }
+
+    fn write_snapshot_settings<W: io::Write>(
+        &self,
+        writer: &mut SnapshotWriter<W>,
+    ) -> io::Result<()> {
+        writer.write_usize(self.languages.len())?;
+        for language in self.languages.iter().sorted() {
+            writer.write_language(language)?;
+        }
+
+        writer.write_f64(self.minimum_relative_distance)?;
+        writer.write_bool(self.is_low_accuracy_mode_enabled)?;
+        writer.write_bool(self.is_accent_insensitive_mode_enabled)?;
+        writer.write_bool(self.is_word_boundary_mode_enabled)?;
+
+        for ngram_lengths in [
+            &self.short_text_ngram_lengths,
+            &self.long_text_ngram_lengths,
+        ] {
+            writer.write_usize(*ngram_lengths.start())?;
+            writer.write_usize(*ngram_lengths.end())?;
+        }
+
+        let (smoothing_tag, smoothing_parameter) = match self.smoothing {
+            Smoothing::None => (0, 0.0),
+            Smoothing::AddK(k) => (1, k),
+            Smoothing::WittenBell => (2, 0.0),
+            Smoothing::KneserNey(discount) => (3, discount),
+        };
+        writer.write_u8(smoothing_tag)?;
+        writer.write_f64(smoothing_parameter)?;
+        writer.write_f64(self.word_model_weight)?;
+
+        writer.write_usize(self.normalizers.len())?;
+        for normalizer in self.normalizers.iter() {
+            writer.write_str(&format!("{normalizer:?}"))?;
+        }
+
+        let unique_characters = &self.character_rules.unique_characters;
+        writer.write_usize(unique_characters.len())?;
+        for (language, characters) in unique_characters.iter().sorted() {
+            writer.write_language(language)?;
+            writer.write_str(characters)?;
+        }
+
+        let chars_to_languages_mapping = &self.character_rules.chars_to_languages_mapping;
+        writer.write_usize(chars_to_languages_mapping.len())?;
+        for (characters, languages) in chars_to_languages_mapping.iter().sorted_by_key(|it| it.0) {
+            writer.write_str(characters)?;
+            writer.write_usize(languages.len())?;
+            for language in languages.iter().sorted() {
+                writer.write_language(language)?;
+            }
+        }
+
+        writer.write_usize(self.macro_languages.len())?;
+        for macro_language in self.macro_languages.iter().sorted() {
+            writer.write_str(&macro_language.to_string())?;
+        }
+
+        writer.write_bool(self.memory_budget.is_some())?;
+        writer.write_usize(self.memory_budget.unwrap_or(0))?;
+
+        writer.write_usize(self.language_variant_models.len())?;
+        for variant_model in self.language_variant_models.iter() {
+            variant_model.write_snapshot(writer)?;
+        }
+
+        writer.write_usize(self.pair_discriminators.len())?;
+        for discriminator in self.pair_discriminators.iter() {
+            writer.write_str(&discriminator.to_json())?;
+        }
+
+        Ok(())
+    }
+
+    fn read_snapshot_settings<R: io::Read>(reader: &mut SnapshotReader<R>) -> io::Result<Self> {
+        let language_count = reader.read_usize()?;
+        let languages = (0..language_count)
+            .map(|_| reader.read_language())
+            .collect::<io::Result<HashSet<_>>>()?;
+
+        let minimum_relative_distance = reader.read_f64()?;
+        let is_low_accuracy_mode_enabled = reader.read_bool()?;
+        let is_accent_insensitive_mode_enabled = reader.read_bool()?;
+        let is_word_boundary_mode_enabled = reader.read_bool()?;
+
+        let mut ngram_lengths = vec![];
+        for _ in 0..2 {
+            let start = reader.read_usize()?;
+            let end = reader.read_usize()?;
+            if !(1..=end).contains(&start) || end > MAXIMUM_NGRAM_LENGTH {
+                return Err(invalid_data(format!(
+                    "ngram lengths {start}..={end} are not supported"
+                )));
+            }
+            ngram_lengths.push(start..=end);
+        }
+        let long_text_ngram_lengths = ngram_lengths.pop().unwrap();
+        let short_text_ngram_lengths = ngram_lengths.pop().unwrap();
+
+        let smoothing_tag = reader.read_u8()?;
+        let smoothing_parameter = reader.read_f64()?;
+        let smoothing = match smoothing_tag {
+            0 => Smoothing::None,
+            1 => Smoothing::AddK(smoothing_parameter),
+            2 => Smoothing::WittenBell,
+            3 => Smoothing::KneserNey(smoothing_parameter),
+            _ => {
+                return Err(invalid_data(format!(
+                    "smoothing scheme {smoothing_tag} is not supported"
+                )))
+            }
+        };
+        let word_model_weight = reader.read_f64()?;
+
+        let normalizer_count = reader.read_usize()?;
+        let normalizers = (0..normalizer_count)
+            .map(|_| {
+                let name = reader.read_string()?;
+                Normalizer::from_str(&name)
+                    .map_err(|_| invalid_data(format!("normalizer '{name}' is not supported")))
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let mut unique_characters = HashMap::new();
+        for _ in 0..reader.read_usize()? {
+            let language = reader.read_language()?;
+            unique_characters.insert(language, reader.read_string()?);
+        }
+
+        let mut chars_to_languages_mapping = HashMap::new();
+        for _ in 0..reader.read_usize()? {
+            let characters = reader.read_string()?;
+            let language_count = reader.read_usize()?;
+            let languages = (0..language_count)
+                .map(|_| reader.read_language())
+                .collect::<io::Result<HashSet<_>>>()?;
+            chars_to_languages_mapping.insert(characters, languages);
+        }
+
+        let macro_language_count = reader.read_usize()?;
+        let macro_languages = (0..macro_language_count)
+            .map(|_| {
+                let name = reader.read_string()?;
+                MacroLanguage::from_str(&name)
+                    .map_err(|_| invalid_data(format!("macro language '{name}' is not supported")))
+            })
+            .collect::<io::Result<HashSet<_>>>()?;
+
+        let has_memory_budget = reader.read_bool()?;
+        let memory_budget = Some(reader.read_usize()?).filter(|_| has_memory_budget);
+
+        let variant_model_count = reader.read_usize()?;
+        let language_variant_models = (0..variant_model_count)
+            .map(|_| LanguageVariantModel::read_snapshot(reader))
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let discriminator_count = reader.read_usize()?;
+        let pair_discriminators = (0..discriminator_count)
+            .map(|_| {
+                PairDiscriminator::from_json(&reader.read_string()?)
+                    .ok_or_else(|| invalid_data("pair discriminator is invalid".to_string()))
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let variant_index = usize::from(is_accent_insensitive_mode_enabled)
+            + 2 * usize::from(is_word_boundary_mode_enabled);
+        let language_models = &LANGUAGE_MODELS[variant_index];
+
+        Ok(Self {
+            languages: languages.clone(),
+            minimum_relative_distance,
+            is_low_accuracy_mode_enabled,
+            is_accent_insensitive_mode_enabled,
+            is_word_boundary_mode_enabled,
+            short_text_ngram_lengths,
+            long_text_ngram_lengths,
+            smoothing,
+            word_model_weight,
+            normalizers,
+            tokenizer: Arc::new(DefaultTokenizer),
+            character_rules: CharacterRules {
+                unique_characters,
+                chars_to_languages_mapping,
+            },
+            one_language_alphabets: collect_one_language_alphabets(&languages),
+            language_models: std::array::from_fn(|i| &*language_models[i]),
+            is_compiled_model_lookup_enabled: is_compiled_model_lookup_enabled(
+                is_accent_insensitive_mode_enabled,
+            ),
+            memory_budget,
+            model_cache: Arc::new(Mutex::new(ModelCache::default())),
+            model_load_failures: &MODEL_LOAD_FAILURES[variant_index],
+            preloaded_language_count: None,
+            ngram_counts: &NGRAM_COUNTS[variant_index],
+            word_models: &WORD_MODELS[usize::from(is_accent_insensitive_mode_enabled)],
+            language_variant_models,
+            pair_discriminators,
+            macro_languages,
+        })
+    }
+
+    fn write_snapshot_models<W: io::Write>(
+        &self,
+        writer: &mut SnapshotWriter<W>,
+    ) -> io::Result<()> {
+        for language_models in self.language_models {
+            let language_models = language_models.read().unwrap();
+            let models = language_models.log_probabilities_of(&self.languages);
+            writer.write_usize(models.len())?;
+            for (language, log_probabilities) in models {
+                writer.write_language(&language)?;
+                writer.write_log_probabilities(&log_probabilities)?;
+            }
+        }
+
+        let ngram_counts = self.ngram_counts.read().unwrap();
+        let counts = self.loaded_models_of_languages(&ngram_counts);
+        writer.write_usize(counts.len())?;
+        for (language, ngram_counts) in counts {
+            writer.write_language(language)?;
+            ngram_counts.write_snapshot(writer)?;
+        }
+
+        let word_models = self.word_models.read().unwrap();
+        let models = self.loaded_models_of_languages(&word_models);
+        writer.write_usize(models.len())?;
+        for (language, word_model) in models {
+            writer.write_language(language)?;
+            word_model.write_snapshot(writer)?;
+        }
+
+        Ok(())
+    }
+
+    fn read_snapshot_models<R: io::Read>(
+        &mut self,
+        reader: &mut SnapshotReader<R>,
+    ) -> io::Result<()> {
+        for language_models in self.language_models {
+            for _ in 0..reader.read_usize()? {
+                let language = reader.read_language()?;
+                let log_probabilities = reader.read_log_probabilities()?;
+                language_models
+                    .write()
+                    .unwrap()
+                    .insert_log_probabilities(language, log_probabilities);
+            }
+        }
+
+        for _ in 0..reader.read_usize()? {
+            let language = reader.read_language()?;
+            let counts = NgramCounts::read_snapshot(reader)?;
+            self.ngram_counts
                 .write()
                 .unwrap()
-                .shrink_to_fit();
-            self.fivegram_language_models
+                .entry(language)
+                .or_insert(counts);
+        }
+
+        for _ in 0..reader.read_usize()? {
+            let language = reader.read_language()?;
+            let word_model = WordModel::read_snapshot(reader)?;
+            self.word_models
                 .write()
                 .unwrap()
-                .shrink_to_fit();
+                .entry(language)
+                .or_insert(word_model);
         }
+
+        Ok(())
+    }
+
+    /// Returns the models of the languages of this detector in the given map,
+    /// sorted by language.
+    fn loaded_models_of_languages<'a, M>(
+        &self,
+        models: &'a HashMap<Language, M>,
+    ) -> Vec<(&'a Language, &'a M)> {
+        models
+            .iter()
+            .filter(|(language, _)| self.languages.contains(language))
+            .sorted_by_key(|(language, _)| **language)
+            .collect()
     }
 
     /// Detects the language of given input text.
@@ -190,9 +830,74 @@ impl LanguageDetector {
     /// assert_eq!(detected_language, Some(English));
     /// ```
     pub fn detect_language_of<T: Into<String>>(&self, text: T) -> Option<Language> {
+        self.detect_language_of_str(&text.into())
+    }
+
+    /// Detects the language of given borrowed input text.
+    /// If the language cannot be reliably detected, [`None`] is returned.
+    ///
+    /// Unlike [`detect_language_of`](#method.detect_language_of), this method does not
+    /// convert the text into a [`String`] first, which saves copying each text when
+    /// classifying many short texts. The detection itself still allocates memory for
+    /// each text, such as for the list of its words and the probabilities of each language.
+    ///
+    /// ```
+    /// use lingua::Language::{English, French, German, Spanish};
+    /// use lingua::LanguageDetectorBuilder;
+    ///
+    /// let detector = LanguageDetectorBuilder::from_languages(&[
+    ///     English,
+    ///     French,
+    ///     German,
+    ///     Spanish
+    /// ])
+    /// .build();
+    ///
+    /// let detected_language = detector.detect_language_of_str("languages are awesome");
+    ///
+    /// assert_eq!(detected_language, Some(English));
+    /// ```
+    pub fn detect_language_of_str(&self, text: &str) -> Option<Language> {
         self.detect_language_from_languages(text, &self.languages)
     }
 
+    /// Detects the language of given input text and tells apart why no language has been
+    /// detected, if so.
+    ///
+    /// [`detect_language_of`](#method.detect_language_of) returns [`None`] both for text
+    /// whose language cannot be reliably decided and for text written in an alphabet which
+    /// none of the languages of this detector uses. This method returns
+    /// [`DetectionOutcome::Undecided`] in the first case and
+    /// [`DetectionOutcome::UnsupportedAlphabet`] in the second one.
+    ///
+    /// ```
+    /// use lingua::Language::{English, Hindi, Tamil};
+    /// use lingua::{Alphabet, DetectionOutcome, LanguageDetectorBuilder};
+    ///
+    /// let detector = LanguageDetectorBuilder::from_languages(&[English, Hindi, Tamil]).build();
+    ///
+    /// assert_eq!(
+    ///     detector.detect_language_outcome_of("languages are awesome"),
+    ///     DetectionOutcome::Detected(English)
+    /// );
+    /// assert_eq!(
+    ///     detector.detect_language_outcome_of("ಕನ್ನಡ ಭಾಷೆ"),
+    ///     DetectionOutcome::UnsupportedAlphabet(Alphabet::Kannada)
+    /// );
+    /// ```
+    pub fn detect_language_outcome_of<T: Into<String>>(&self, text: T) -> DetectionOutcome {
+        let text = text.into();
+
+        if let Some(language) = self.detect_language_of_str(&text) {
+            return DetectionOutcome::Detected(language);
+        }
+
+        match self.detect_unsupported_alphabet_of_str(&text) {
+            Some(alphabet) => DetectionOutcome::UnsupportedAlphabet(alphabet),
+            None => DetectionOutcome::Undecided,
+        }
+    }
+
     /// Detects the languages of all given input texts.
     /// If the language cannot be reliably detected for a text,
     /// [`None`] is put into the result vector.
@@ -244,14 +949,31 @@ impl LanguageDetector {
             .collect()
     }
 
-    fn detect_language_from_languages<T: Into<String>>(
+    fn detect_language_from_languages(
         &self,
-        text: T,
+        text: &str,
         languages: &HashSet<Language>,
     ) -> Option<Language> {
         let confidence_values =
             self.compute_language_confidence_values_for_languages(text, languages);
 
+        self.select_most_likely_language(&confidence_values)
+    }
+
+    /// Detects the language of the given text, to which the normalizers
+    /// of this detector have been applied already.
+    fn detect_language_of_normalized_text(
+        &self,
+        text: &str,
+        languages: &HashSet<Language>,
+    ) -> Option<Language> {
+        let confidence_values =
+            self.compute_language_confidence_values_for_normalized_text(text.trim(), languages);
+
+        self.select_most_likely_language(&confidence_values)
+    }
+
+    fn select_most_likely_language<L: Copy>(&self, confidence_values: &[(L, f64)]) -> Option<L> {
         if confidence_values.is_empty() {
             return None;
         }
@@ -331,16 +1053,16 @@ impl LanguageDetector {
     /// }
     /// ```
     pub fn detect_multiple_languages_of<T: Into<String>>(&self, text: T) -> Vec<DetectionResult> {
-        let text_str = text.into();
+        let original_text = text.into();
 
-        if text_str.is_empty() {
+        if original_text.is_empty() {
             return vec![];
         }
 
-        let tokens_without_whitespace = TOKENS_WITHOUT_WHITESPACE
-            .find_iter(&text_str)
-            .map(|mat| mat.as_str())
-            .collect_vec();
+        let normalized_text = NormalizedText::new(&original_text, &self.normalizers);
+        let text_str = &normalized_text.text;
+
+        let tokens_without_whitespace = self.tokenizer.words(text_str);
 
         if tokens_without_whitespace.is_empty() {
             return vec![];
@@ -349,7 +1071,7 @@ impl LanguageDetector {
         let mut results = vec![];
         let mut language_counts = HashMap::new();
 
-        let language = self.detect_language_of(&text_str);
+        let language = self.detect_language_of_normalized_text(text_str, &self.languages);
         if let Some(lang) = language {
             self.increment_counter(&mut language_counts, lang, 1);
         }
@@ -358,7 +1080,7 @@ impl LanguageDetector {
             if word.chars().count() < 5 {
                 continue;
             }
-            let language = self.detect_language_of(*word);
+            let language = self.detect_language_of_normalized_text(word, &self.languages);
             if let Some(lang) = language {
                 self.increment_counter(&mut language_counts, lang, 1);
             }
@@ -372,7 +1094,7 @@ impl LanguageDetector {
         if languages.len() == 1 {
             let result = DetectionResult {
                 start_index: 0,
-                end_index: text_str.len(),
+                end_index: original_text.len(),
                 word_count: tokens_without_whitespace.len(),
                 language: *languages.iter().next().unwrap(),
             };
@@ -383,12 +1105,12 @@ impl LanguageDetector {
             let mut word_count = 0;
             let mut current_language = None;
 
-            let last_index = TOKENS_WITH_OPTIONAL_WHITESPACE.find_iter(&text_str).count() - 1;
-            let token_matches = TOKENS_WITH_OPTIONAL_WHITESPACE.find_iter(&text_str);
+            let token_ranges = self.tokenizer.token_ranges(text_str);
+            let last_index = token_ranges.len().saturating_sub(1);
 
-            for (i, token_match) in token_matches.enumerate() {
-                let word = token_match.as_str();
-                let language = self.detect_language_from_languages(word, &languages);
+            for (i, token_range) in token_ranges.into_iter().enumerate() {
+                let word = &text_str[token_range.clone()];
+                let language = self.detect_language_of_normalized_text(word, &languages);
 
                 if i == 0 || (current_language.is_none() && language.is_some()) {
                     current_language = language;
@@ -411,7 +1133,7 @@ impl LanguageDetector {
                     }
                 }
 
-                current_end_index = token_match.end();
+                current_end_index = token_range.end;
                 word_count += 1;
 
                 if i == last_index {
@@ -450,6 +1172,11 @@ impl LanguageDetector {
                     merge_adjacent_results(&mut results, &mut mergeable_result_indices);
                 }
             }
+
+            for result in results.iter_mut() {
+                result.start_index = normalized_text.original_index(result.start_index);
+                result.end_index = normalized_text.original_index(result.end_index);
+            }
         }
 
         results
@@ -532,6 +1259,35 @@ impl LanguageDetector {
         &self,
         text: T,
     ) -> Vec<(Language, f64)> {
+        self.compute_language_confidence_values_of_str(&text.into())
+    }
+
+    /// Computes confidence values for each language supported by this detector for the given
+    /// borrowed input text. The values are the same as those of
+    /// [`compute_language_confidence_values`](#method.compute_language_confidence_values).
+    ///
+    /// Unlike that method, this method does not convert the text into a [`String`] first,
+    /// which saves copying each text when classifying many short texts. The computation
+    /// itself still allocates memory for each text, such as for the list of its words and
+    /// the probabilities of each language.
+    ///
+    /// ```
+    /// use lingua::Language::{English, French, German, Spanish};
+    /// use lingua::LanguageDetectorBuilder;
+    ///
+    /// let detector = LanguageDetectorBuilder::from_languages(&[
+    ///     English,
+    ///     French,
+    ///     German,
+    ///     Spanish
+    /// ])
+    /// .build();
+    ///
+    /// let confidence_values = detector.compute_language_confidence_values_of_str("Haus");
+    ///
+    /// assert_eq!(confidence_values[0].0, German);
+    /// ```
+    pub fn compute_language_confidence_values_of_str(&self, text: &str) -> Vec<(Language, f64)> {
         self.compute_language_confidence_values_for_languages(text, &self.languages)
     }
 
@@ -603,9 +1359,49 @@ impl LanguageDetector {
             .collect()
     }
 
-    fn compute_language_confidence_values_for_languages<T: Into<String>>(
+    fn compute_language_confidence_values_for_languages(
         &self,
-        text: T,
+        text: &str,
+        languages: &HashSet<Language>,
+    ) -> Vec<(Language, f64)> {
+        let normalized_text = normalize(text.trim(), &self.normalizers);
+        self.compute_language_confidence_values_for_normalized_text(&normalized_text, languages)
+    }
+
+    /// Computes the confidence values of the given text, to which the normalizers
+    /// of this detector have been applied already.
+    fn compute_language_confidence_values_for_normalized_text(
+        &self,
+        text: &str,
+        languages: &HashSet<Language>,
+    ) -> Vec<(Language, f64)> {
+        let tokenizer = self.tokenizer.as_ref();
+
+        with_folded_words_of(text, fold_case_into, tokenizer, |words| {
+            // The bundled models of Turkish and Azerbaijani were built from text whose capital I
+            // was folded to the dotted i, whereas the model writer now folds it to the dotless ı.
+            // Both foldings are scored for these languages, so either kind of model is matched.
+            if text.contains('I') && languages.iter().any(has_dotless_capital_i) {
+                with_folded_words_of(text, fold_turkic_case_into, tokenizer, |turkic_words| {
+                    self.compute_language_confidence_values_for_words(
+                        words,
+                        Some(turkic_words),
+                        languages,
+                    )
+                })
+            } else {
+                self.compute_language_confidence_values_for_words(words, None, languages)
+            }
+        })
+    }
+
+    /// Computes the confidence values of the given words. The words folded for Turkic
+    /// languages are only given if they differ from the others. In this case, the
+    /// higher sum of both foldings is taken for Turkish and Azerbaijani.
+    fn compute_language_confidence_values_for_words(
+        &self,
+        words: &[&str],
+        turkic_words: Option<&[&str]>,
         languages: &HashSet<Language>,
     ) -> Vec<(Language, f64)> {
         let mut values = Vec::with_capacity(languages.len());
@@ -614,15 +1410,12 @@ impl LanguageDetector {
             values.push((*language, 0.0));
         }
 
-        let text_str = text.into();
-        let words = split_text_into_words(&text_str);
-
         if words.is_empty() {
             values.sort_by(confidence_values_comparator);
             return values;
         }
 
-        let language_detected_by_rules = self.detect_language_with_rules(&words, languages);
+        let language_detected_by_rules = self.detect_language_with_rules(words, languages);
 
         if let Some(language) = language_detected_by_rules {
             update_confidence_values(&mut values, language, 1.0);
@@ -630,7 +1423,7 @@ impl LanguageDetector {
             return values;
         }
 
-        let filtered_languages = self.filter_languages_by_rules(&words, languages);
+        let filtered_languages = self.filter_languages_by_rules(words, languages);
 
         if filtered_languages.len() == 1 {
             let filtered_language = filtered_languages.into_iter().next().unwrap();
@@ -639,28 +1432,103 @@ impl LanguageDetector {
             return values;
         }
 
-        let character_count: usize = words.iter().map(|word| word.chars().count()).sum();
+        let summed_up_log_probabilities = match turkic_words {
+            Some(turkic_words) => {
+                let (turkic_languages, other_languages): (HashSet<_>, HashSet<_>) =
+                    filtered_languages
+                        .into_iter()
+                        .partition(has_dotless_capital_i);
+                let mut log_probabilities =
+                    self.sum_up_log_probabilities_of_words(words, other_languages);
+                let turkic_log_probabilities = self
+                    .sum_up_log_probabilities_of_words(words, turkic_languages.clone())
+                    .into_iter()
+                    .chain(self.sum_up_log_probabilities_of_words(turkic_words, turkic_languages));
+                for (language, sum) in turkic_log_probabilities {
+                    log_probabilities
+                        .entry(language)
+                        .and_modify(|it| *it = it.max(sum))
+                        .or_insert(sum);
+                }
+                log_probabilities
+            }
+            None => self.sum_up_log_probabilities_of_words(words, filtered_languages),
+        };
 
-        if self.is_low_accuracy_mode_enabled && character_count < 3 {
+        if summed_up_log_probabilities.is_empty() {
             values.sort_by(confidence_values_comparator);
             return values;
         }
 
-        let ngram_length_range = if character_count >= 120 || self.is_low_accuracy_mode_enabled {
-            3..4usize
-        } else {
-            1..6usize
-        };
+        self.compute_confidence_values(&mut values, summed_up_log_probabilities);
+        self.apply_pair_discriminator(&mut values, words);
 
-        #[allow(clippy::type_complexity)]
-        let all_probabilities_and_unigram_counts: Vec<(
+        values
+    }
+
+    /// Sums up the log-probabilities of the given words in each of the given languages
+    /// with the ngram models and, for short text, the word models.
+    fn sum_up_log_probabilities_of_words(
+        &self,
+        words: &[&str],
+        filtered_languages: HashSet<Language>,
+    ) -> HashMap<Language, f64> {
+        if filtered_languages.is_empty() {
+            return HashMap::new();
+        }
+
+        let folded_words;
+        let folded_word_refs;
+        let words = if self.is_accent_insensitive_mode_enabled {
+            folded_words = words.iter().map(|word| fold_diacritics(word)).collect_vec();
+            folded_word_refs = folded_words.iter().map(String::as_str).collect_vec();
+            &folded_word_refs
+        } else {
+            words
+        };
+
+        let character_count: usize = words.iter().map(|word| word.chars().count()).sum();
+
+        if self.is_low_accuracy_mode_enabled && character_count < 3 {
+            return HashMap::new();
+        }
+
+        let ngram_length_range =
+            if character_count >= LONG_TEXT_CHARACTER_COUNT || self.is_low_accuracy_mode_enabled {
+                self.long_text_ngram_lengths.clone()
+            } else {
+                self.short_text_ngram_lengths.clone()
+            };
+
+        let word_log_probabilities =
+            if self.word_model_weight > 0.0 && words.len() <= WORD_MODEL_MAXIMUM_WORD_COUNT {
+                Some(self.compute_word_log_probabilities(words, &filtered_languages))
+            } else {
+                None
+            };
+
+        let marked_words;
+        let marked_word_refs;
+        let words = if self.is_word_boundary_mode_enabled {
+            marked_words = words
+                .iter()
+                .map(|word| mark_word_boundaries(word))
+                .collect_vec();
+            marked_word_refs = marked_words.iter().map(String::as_str).collect_vec();
+            &marked_word_refs
+        } else {
+            words
+        };
+
+        #[allow(clippy::type_complexity)]
+        let all_probabilities_and_unigram_counts: Vec<(
             HashMap<Language, f64>,
             Option<HashMap<Language, u32>>,
         )> = ngram_length_range
             .into_iter()
             .filter(|i| character_count >= *i)
             .map(|ngram_length| {
-                self.look_up_language_models(&words, ngram_length, &filtered_languages)
+                self.look_up_language_models(words, ngram_length, &filtered_languages)
             })
             .collect();
 
@@ -669,19 +1537,92 @@ impl LanguageDetector {
             .map(|(probabilities, _)| probabilities)
             .collect::<Vec<_>>();
 
-        let unigram_counts = &all_probabilities_and_unigram_counts[0].1;
+        let unigram_counts = all_probabilities_and_unigram_counts
+            .first()
+            .and_then(|(_, unigram_counts)| unigram_counts.as_ref());
 
-        let summed_up_probabilities =
-            self.sum_up_probabilities(&probability_maps, unigram_counts, filtered_languages);
+        self.sum_up_log_probabilities(
+            &probability_maps,
+            unigram_counts,
+            word_log_probabilities.as_ref(),
+            filtered_languages,
+        )
+    }
 
-        if summed_up_probabilities.is_empty() {
-            values.sort_by(confidence_values_comparator);
-            return values;
+    /// Detects the alphabet of given input text if none of the languages of this
+    /// [`LanguageDetector`] instance is written in it.
+    ///
+    /// Text written in an alphabet that is not used by any of the configured languages
+    /// cannot be classified. In such cases,
+    /// [`detect_language_of`](#method.detect_language_of) returns [`None`] and all confidence
+    /// values are 0.0. This method allows to tell such text apart from text whose language
+    /// is merely ambiguous, as does
+    /// [`detect_language_outcome_of`](#method.detect_language_outcome_of) along with the
+    /// detection itself. If the predominant alphabet of the text is used by at least one
+    /// of the configured languages, or if no alphabet can be determined, [`None`] is returned.
+    ///
+    /// ```
+    /// use lingua::Language::{English, Hindi, Tamil};
+    /// use lingua::{Alphabet, LanguageDetectorBuilder};
+    ///
+    /// let detector = LanguageDetectorBuilder::from_languages(&[English, Hindi, Tamil]).build();
+    ///
+    /// assert_eq!(detector.detect_unsupported_alphabet_of("ಕನ್ನಡ ಭಾಷೆ"), Some(Alphabet::Kannada));
+    /// assert_eq!(detector.detect_language_of("ಕನ್ನಡ ಭಾಷೆ"), None);
+    /// assert_eq!(detector.detect_unsupported_alphabet_of("languages are awesome"), None);
+    /// ```
+    pub fn detect_unsupported_alphabet_of<T: Into<String>>(&self, text: T) -> Option<Alphabet> {
+        self.detect_unsupported_alphabet_of_str(&text.into())
+    }
+
+    fn detect_unsupported_alphabet_of_str(&self, text: &str) -> Option<Alphabet> {
+        let alphabet = with_words_of(text, &self.normalizers, self.tokenizer.as_ref(), |words| {
+            self.detect_most_frequent_alphabet(words)
+        })?;
+
+        if self
+            .languages
+            .iter()
+            .any(|language| language.alphabets().contains(&alphabet))
+        {
+            None
+        } else {
+            Some(alphabet)
         }
+    }
+
+    /// Detects whether the given input text is written in Simplified or Traditional Chinese.
+    ///
+    /// The language is detected first as by [`detect_language_of`](#method.detect_language_of).
+    /// If it is Chinese, the characters which only occur in one of both scripts are counted.
+    /// [`ChineseScript::Mixed`] is returned if both scripts make up a substantial share of
+    /// them. If the language is not Chinese or if the text consists of characters shared by
+    /// both scripts only, [`None`] is returned.
+    ///
+    /// ```
+    /// use lingua::Language::{Chinese, English, Japanese};
+    /// use lingua::{ChineseScript, LanguageDetectorBuilder};
+    ///
+    /// let detector = LanguageDetectorBuilder::from_languages(&[Chinese, English, Japanese]).build();
+    ///
+    /// assert_eq!(
+    ///     detector.detect_chinese_script_of("我们学习中文已经三年了"),
+    ///     Some(ChineseScript::Simplified)
+    /// );
+    /// assert_eq!(
+    ///     detector.detect_chinese_script_of("我們學習中文已經三年了"),
+    ///     Some(ChineseScript::Traditional)
+    /// );
+    /// assert_eq!(detector.detect_chinese_script_of("languages are awesome"), None);
+    /// ```
+    pub fn detect_chinese_script_of<T: Into<String>>(&self, text: T) -> Option<ChineseScript> {
+        let text = text.into();
 
-        self.compute_confidence_values(&mut values, probability_maps, summed_up_probabilities);
+        if self.detect_language_of_str(&text) != Some(Language::from_str("Chinese").unwrap()) {
+            return None;
+        }
 
-        values
+        ChineseScript::classify(&text)
     }
 
     /// Computes the confidence value for the given language and input text. This value denotes
@@ -788,9 +1729,150 @@ impl LanguageDetector {
             .collect()
     }
 
+    /// Detects the language of the given input text, collapsing the languages of the
+    /// macrolanguages configured with
+    /// [`LanguageDetectorBuilder::with_macro_languages`](crate::LanguageDetectorBuilder::with_macro_languages)
+    /// into their macrolanguage.
+    ///
+    /// The most likely language or macrolanguage is selected from the values computed by
+    /// [`compute_collapsed_language_confidence_values`](#method.compute_collapsed_language_confidence_values)
+    /// in the same way as by [`detect_language_of`](#method.detect_language_of).
+    ///
+    /// ```
+    /// use lingua::Language::{Bokmal, English, Nynorsk};
+    /// use lingua::{CollapsedLanguage, LanguageDetectorBuilder, MacroLanguage};
+    ///
+    /// let detector = LanguageDetectorBuilder::from_languages(&[Bokmal, English, Nynorsk])
+    ///     .with_macro_languages(&[MacroLanguage::Norwegian])
+    ///     .build();
+    ///
+    /// assert_eq!(
+    ///     detector.detect_collapsed_language_of("Jeg vet ikke hva du mener"),
+    ///     Some(CollapsedLanguage::Macro(MacroLanguage::Norwegian))
+    /// );
+    /// assert_eq!(
+    ///     detector.detect_collapsed_language_of("languages are awesome"),
+    ///     Some(CollapsedLanguage::Individual(English))
+    /// );
+    /// ```
+    pub fn detect_collapsed_language_of<T: Into<String>>(
+        &self,
+        text: T,
+    ) -> Option<CollapsedLanguage> {
+        let confidence_values = self.compute_collapsed_language_confidence_values(text);
+        self.select_most_likely_language(&confidence_values)
+    }
+
+    /// Computes confidence values for each language supported by this detector for the given
+    /// input text, collapsing the languages of the configured macrolanguages into their
+    /// macrolanguage.
+    ///
+    /// The confidence value of a macrolanguage is the sum of the confidence values of its
+    /// individual languages. The entries are sorted by their confidence values in descending
+    /// order. The individual confidence values remain available through
+    /// [`compute_language_confidence_values`](#method.compute_language_confidence_values).
+    pub fn compute_collapsed_language_confidence_values<T: Into<String>>(
+        &self,
+        text: T,
+    ) -> Vec<(CollapsedLanguage, f64)> {
+        let mut collapsed_values: HashMap<CollapsedLanguage, f64> = HashMap::new();
+
+        for (language, value) in self.compute_language_confidence_values(text) {
+            *collapsed_values
+                .entry(CollapsedLanguage::collapse(language, &self.macro_languages))
+                .or_insert(0.0) += value;
+        }
+
+        collapsed_values
+            .into_iter()
+            .sorted_by(
+                |(first_language, first_value), (second_language, second_value)| {
+                    second_value
+                        .total_cmp(first_value)
+                        .then_with(|| first_language.cmp(second_language))
+                },
+            )
+            .collect()
+    }
+
+    /// Detects the regional variant of the language of the given input text, such as
+    /// `pt-BR` or `pt-PT`.
+    ///
+    /// The language is detected first as by [`detect_language_of`](#method.detect_language_of).
+    /// Afterwards, the text is compared with the models of the variants of this language
+    /// loaded with
+    /// [`LanguageDetectorBuilder::with_language_variants`](crate::LanguageDetectorBuilder::with_language_variants).
+    /// If the language cannot be reliably detected, if no variants of it have been loaded
+    /// or if its most likely variants are equally likely, [`None`] is returned.
+    pub fn detect_language_variant_of<T: Into<String>>(&self, text: T) -> Option<LanguageVariant> {
+        let confidence_values = self.compute_language_variant_confidence_values(text);
+
+        match &confidence_values[..] {
+            [] => None,
+            [(variant, _)] => Some(variant.clone()),
+            [(variant, first_probability), (_, second_probability), ..] => {
+                if (first_probability - second_probability).abs() < f64::EPSILON {
+                    None
+                } else {
+                    Some(variant.clone())
+                }
+            }
+        }
+    }
+
+    /// Computes confidence values for the regional variants of the language of the
+    /// given input text.
+    ///
+    /// The language is detected first as by [`detect_language_of`](#method.detect_language_of).
+    /// A vector of all loaded variants of this language is returned, sorted by their
+    /// confidence values in descending order. The values add up to 1.0. If the language
+    /// cannot be reliably detected or if no variants of it have been loaded, an empty
+    /// vector is returned.
+    pub fn compute_language_variant_confidence_values<T: Into<String>>(
+        &self,
+        text: T,
+    ) -> Vec<(LanguageVariant, f64)> {
+        let text = text.into();
+
+        let Some(language) = self.detect_language_of_str(&text) else {
+            return vec![];
+        };
+
+        let variant_models = self
+            .language_variant_models
+            .iter()
+            .filter(|model| model.variant.language() == language)
+            .collect_vec();
+
+        if variant_models.is_empty() {
+            return vec![];
+        }
+
+        let log_probabilities =
+            with_words_of(&text, &self.normalizers, self.tokenizer.as_ref(), |words| {
+                variant_models
+                    .iter()
+                    .map(|model| model.log_probability(words))
+                    .collect_vec()
+            });
+
+        variant_models
+            .into_iter()
+            .zip(normalize_log_probabilities(&log_probabilities))
+            .map(|(model, probability)| (model.variant.clone(), probability))
+            .sorted_by(
+                |(first_variant, first_probability), (second_variant, second_probability)| {
+                    second_probability
+                        .total_cmp(first_probability)
+                        .then_with(|| first_variant.cmp(second_variant))
+                },
+            )
+            .collect()
+    }
+
     fn detect_language_with_rules(
         &self,
-        words: &[String],
+        words: &[&str],
         languages: &HashSet<Language>,
     ) -> Option<Language> {
         let mut total_language_counts = HashMap::<Option<Language>, u32>::new();
@@ -825,15 +1907,13 @@ impl LanguageDetector {
                             Language::from_str("Japanese").unwrap(),
                             1,
                         );
-                    } else if Alphabet::Latin.matches_char(character)
-                        || Alphabet::Cyrillic.matches_char(character)
-                        || Alphabet::Devanagari.matches_char(character)
-                    {
-                        self.languages_with_unique_characters
+                    } else {
+                        self.character_rules
+                            .unique_characters
                             .iter()
-                            .filter(|it| it.unique_characters().unwrap().contains(character))
-                            .for_each(|it| {
-                                self.increment_counter(&mut word_language_counts, *it, 1)
+                            .filter(|(_, characters)| characters.contains(character))
+                            .for_each(|(language, _)| {
+                                self.increment_counter(&mut word_language_counts, *language, 1)
                             });
                     }
                 }
@@ -918,45 +1998,15 @@ impl LanguageDetector {
 
     fn filter_languages_by_rules(
         &self,
-        words: &[String],
+        words: &[&str],
         languages: &HashSet<Language>,
     ) -> HashSet<Language> {
-        let mut detected_alphabets = HashMap::<Alphabet, u32>::new();
         let half_word_count = (words.len() as f64) * 0.5;
 
-        for word in words.iter() {
-            for alphabet in Alphabet::iter() {
-                if alphabet.matches(word) {
-                    self.increment_counter(
-                        &mut detected_alphabets,
-                        alphabet,
-                        word.chars().count() as u32,
-                    );
-                    break;
-                }
-            }
-        }
-
-        if detected_alphabets.is_empty() {
-            return languages.clone();
-        }
-
-        if detected_alphabets.len() > 1 {
-            let mut distinct_alphabets = hashset!();
-            for count in detected_alphabets.values() {
-                distinct_alphabets.insert(count);
-            }
-            if distinct_alphabets.len() == 1 {
-                return languages.clone();
-            }
-        }
-
-        let most_frequent_alphabet = detected_alphabets
-            .into_iter()
-            .sorted_by(|(_, first_count), (_, second_count)| second_count.cmp(first_count))
-            .next()
-            .unwrap()
-            .0;
+        let most_frequent_alphabet = match self.detect_most_frequent_alphabet(words) {
+            Some(alphabet) => alphabet,
+            None => return languages.clone(),
+        };
 
         let filtered_languages = languages
             .iter()
@@ -966,7 +2016,7 @@ impl LanguageDetector {
 
         let mut language_counts = HashMap::<&Language, u32>::new();
 
-        for (characters, langs) in CHARS_TO_LANGUAGES_MAPPING.iter() {
+        for (characters, langs) in self.character_rules.chars_to_languages_mapping.iter() {
             let relevant_languages = filtered_languages
                 .intersection(langs)
                 .collect::<HashSet<_>>();
@@ -995,74 +2045,137 @@ impl LanguageDetector {
         }
     }
 
-    fn get_language_models<R>(
-        &self,
-        ngram_length: usize,
-        filtered_languages: &HashSet<Language>,
-        callback_handler: impl FnOnce(LanguageModelArray) -> R,
-    ) -> R {
-        let mut model_read_locks = [None, None, None, None, None];
+    fn detect_most_frequent_alphabet(&self, words: &[&str]) -> Option<Alphabet> {
+        let mut detected_alphabets = HashMap::<Alphabet, u32>::new();
 
-        if ngram_length >= 1 {
-            for language in filtered_languages {
-                self.load_language_models(self.unigram_language_models, language, 1);
+        for word in words.iter() {
+            for alphabet in Alphabet::iter() {
+                if alphabet.matches(word) {
+                    self.increment_counter(
+                        &mut detected_alphabets,
+                        alphabet,
+                        word.chars().count() as u32,
+                    );
+                    break;
+                }
             }
-            model_read_locks[0] = Some(self.unigram_language_models.read().unwrap());
         }
 
-        if ngram_length >= 2 {
-            for language in filtered_languages {
-                self.load_language_models(self.bigram_language_models, language, 2);
-            }
-            model_read_locks[1] = Some(self.bigram_language_models.read().unwrap());
+        if detected_alphabets.is_empty() {
+            return None;
         }
 
-        if ngram_length >= 3 {
-            for language in filtered_languages {
-                self.load_language_models(self.trigram_language_models, language, 3);
+        if detected_alphabets.len() > 1 {
+            let mut distinct_alphabets = hashset!();
+            for count in detected_alphabets.values() {
+                distinct_alphabets.insert(count);
+            }
+            if distinct_alphabets.len() == 1 {
+                return None;
             }
-            model_read_locks[2] = Some(self.trigram_language_models.read().unwrap());
         }
 
-        if ngram_length >= 4 {
-            for language in filtered_languages {
-                self.load_language_models(self.quadrigram_language_models, language, 4);
+        detected_alphabets
+            .into_iter()
+            .sorted_by(|(_, first_count), (_, second_count)| second_count.cmp(first_count))
+            .next()
+            .map(|(alphabet, _)| alphabet)
+    }
+
+    fn get_language_models<R>(
+        &self,
+        ngram_length: usize,
+        filtered_languages: &HashSet<Language>,
+        callback_handler: impl FnOnce(NgramTableArray) -> R,
+    ) -> R {
+        let eviction_lock = MODEL_EVICTION_LOCK.read().unwrap();
+
+        let model_read_locks: [_; MAXIMUM_NGRAM_LENGTH] = std::array::from_fn(|i| {
+            if ngram_length > i {
+                for language in filtered_languages {
+                    self.load_language_models(self.language_models[i], language, i + 1);
+                }
+                Some(self.language_models[i].read().unwrap())
+            } else {
+                None
             }
-            model_read_locks[3] = Some(self.quadrigram_language_models.read().unwrap());
-        }
+        });
+
+        let models = std::array::from_fn(|i| model_read_locks[i].as_deref());
+        let result = callback_handler(models);
+
+        drop(model_read_locks);
+        drop(eviction_lock);
+
+        self.enforce_memory_budget(filtered_languages, &(1..=ngram_length).collect_vec());
+
+        result
+    }
+
+    /// Marks the language models of the given languages and ngram lengths as most recently
+    /// used and evicts the least recently used language models until the memory budget of
+    /// this detector is met. Only the models used by this detector count towards its budget
+    /// and are evicted by it. As the models are shared by all detectors, an evicted model is
+    /// gone for every other detector of the same language as well, even a preloaded one,
+    /// which loads it again once it needs it. Eviction waits for all ongoing lookups, so that
+    /// no detector sharing the same models loses a model while reading it.
+    fn enforce_memory_budget(&self, languages: &HashSet<Language>, ngram_lengths: &[usize]) {
+        let Some(memory_budget) = self.memory_budget else {
+            return;
+        };
+
+        let mut model_cache = self.model_cache.lock().unwrap();
 
-        if ngram_length >= 5 {
-            for language in filtered_languages {
-                self.load_language_models(self.fivegram_language_models, language, 5);
+        for ngram_length in ngram_lengths {
+            let models = self.language_models[ngram_length - 1].read().unwrap();
+            for language in languages {
+                if models.contains(language) {
+                    model_cache.touch(*language, *ngram_length, || models.estimate_size(language));
+                }
             }
-            model_read_locks[4] = Some(self.fivegram_language_models.read().unwrap());
         }
 
-        let models = [
-            model_read_locks[0].as_deref(),
-            model_read_locks[1].as_deref(),
-            model_read_locks[2].as_deref(),
-            model_read_locks[3].as_deref(),
-            model_read_locks[4].as_deref(),
-        ];
+        model_cache.retain(|language, ngram_length| {
+            self.language_models[ngram_length - 1]
+                .read()
+                .unwrap()
+                .contains(&language)
+        });
+
+        let evicted_models = model_cache.evict(memory_budget);
 
-        callback_handler(models)
+        if !evicted_models.is_empty() {
+            let _eviction_lock = MODEL_EVICTION_LOCK.write().unwrap();
+            let evicted_languages = evicted_models
+                .into_iter()
+                .into_group_map_by(|model| model.1);
+            for (ngram_length, models) in evicted_languages {
+                let languages = models.into_iter().map(|(language, _)| language).collect();
+                self.language_models[ngram_length - 1]
+                    .write()
+                    .unwrap()
+                    .remove(&languages);
+            }
+        }
     }
 
     fn look_up_language_models(
         &self,
-        words: &[String],
+        words: &[&str],
         ngram_length: usize,
         filtered_languages: &HashSet<Language>,
     ) -> (HashMap<Language, f64>, Option<HashMap<Language, u32>>) {
         let test_data_model = TestDataLanguageModel::from(words, ngram_length);
 
+        if self.smoothing != Smoothing::None {
+            let probabilities =
+                self.compute_smoothed_language_probabilities(&test_data_model, filtered_languages);
+            return (probabilities, None);
+        }
+
         self.get_language_models(ngram_length, filtered_languages, |language_models| {
-            let probabilities = self.compute_language_probabilities(
-                &test_data_model,
-                filtered_languages,
-                &language_models,
-            );
+            let scorer = self.ngram_scorer(filtered_languages, &language_models);
+            let probabilities = self.compute_language_probabilities(&test_data_model, &scorer);
 
             let unigram_counts = if ngram_length == 1 {
                 let languages = probabilities.keys().collect_vec();
@@ -1075,11 +2188,7 @@ impl LanguageDetector {
                 } else {
                     filtered_languages.clone()
                 };
-                Some(self.count_unigrams(
-                    &test_data_model,
-                    &intersected_languages,
-                    language_models[0].unwrap(),
-                ))
+                Some(self.count_unigrams(&test_data_model, &intersected_languages, &scorer))
             } else {
                 None
             };
@@ -1088,126 +2197,184 @@ impl LanguageDetector {
         })
     }
 
-    fn compute_language_probabilities(
+    fn compute_smoothed_language_probabilities(
         &self,
         model: &TestDataLanguageModel,
         filtered_languages: &HashSet<Language>,
-        language_models: &LanguageModelArray,
     ) -> HashMap<Language, f64> {
+        for language in filtered_languages {
+            self.load_ngram_counts(language);
+        }
+
+        let ngram_counts = self.ngram_counts.read().unwrap();
         let mut probabilities = hashmap!();
+
         for language in filtered_languages.iter() {
-            let sum = self.compute_sum_of_ngram_probabilities(language, model, language_models);
-            if sum < 0.0 {
-                probabilities.insert(*language, sum);
+            if let Some(counts) = ngram_counts.get(language) {
+                let sum: f64 = model
+                    .ngrams
+                    .iter()
+                    .map(|ngram| counts.probability(self.smoothing, ngram.value).ln())
+                    .sum();
+                if sum < 0.0 {
+                    probabilities.insert(*language, sum);
+                }
             }
         }
         probabilities
     }
 
+    fn compute_word_log_probabilities(
+        &self,
+        words: &[&str],
+        filtered_languages: &HashSet<Language>,
+    ) -> HashMap<Language, f64> {
+        for language in filtered_languages {
+            self.load_word_model(language);
+        }
+
+        let word_models = self.word_models.read().unwrap();
+
+        filtered_languages
+            .iter()
+            .filter_map(|language| {
+                let word_model = word_models.get(language)?;
+                let sum = words
+                    .iter()
+                    .map(|word| word_model.log_probability(word))
+                    .sum();
+                Some((*language, sum))
+            })
+            .collect()
+    }
+
+    fn compute_language_probabilities(
+        &self,
+        model: &TestDataLanguageModel,
+        scorer: &NgramScorer,
+    ) -> HashMap<Language, f64> {
+        scorer
+            .sum_up_log_probabilities(&model.ngrams)
+            .into_iter()
+            .filter(|(_, sum)| *sum < 0.0)
+            .collect()
+    }
+
     fn compute_confidence_values(
         &self,
         values: &mut Vec<(Language, f64)>,
-        probability_maps: Vec<&HashMap<Language, f64>>,
-        probabilities: HashMap<Language, f64>,
+        log_probabilities: HashMap<Language, f64>,
     ) {
-        let denominator: f64 = probabilities.values().sum();
-
-        // If the denominator is still zero, the exponent of the summed
-        // log probabilities is too large to be computed for very long input strings.
-        // So we simply set the probability of the most likely language to 1.0 and
-        // leave the other languages at 0.0.
-        if denominator.is_zero() {
-            // For very long inputs, only trigrams are used, so we safely access them at index 0.
-            let probability_map = probability_maps[0];
-            let most_likely_language = *probability_map
-                .iter()
-                .max_by(|(_, first_probability), (_, second_probability)| {
-                    first_probability.total_cmp(second_probability)
-                })
-                .unwrap()
-                .0;
+        let (languages, log_probabilities): (Vec<_>, Vec<_>) =
+            log_probabilities.into_iter().unzip();
 
-            update_confidence_values(values, most_likely_language, 1.0);
-        } else {
-            for (language, probability) in probabilities {
-                for value in &mut *values {
-                    if value.0 == language {
-                        // Apply softmax function
-                        let normalized_probability = probability / denominator;
-                        value.1 = normalized_probability;
-                        break;
-                    }
-                }
-            }
+        for (language, probability) in languages
+            .into_iter()
+            .zip(normalize_log_probabilities(&log_probabilities))
+        {
+            update_confidence_values(values, language, probability);
         }
 
         values.sort_by(confidence_values_comparator);
     }
 
-    fn compute_sum_of_ngram_probabilities(
-        &self,
-        language: &Language,
-        ngram_model: &TestDataLanguageModel,
-        language_models: &LanguageModelArray,
-    ) -> f64 {
-        let models = [
-            language_models[0].as_ref().and_then(|m| m.get(language)),
-            language_models[1].as_ref().and_then(|m| m.get(language)),
-            language_models[2].as_ref().and_then(|m| m.get(language)),
-            language_models[3].as_ref().and_then(|m| m.get(language)),
-            language_models[4].as_ref().and_then(|m| m.get(language)),
-        ];
-        let mut sum = 0.0;
-        for ngrams in ngram_model.ngrams.iter() {
-            for ngram in ngrams {
-                let probability = models[ngram.char_count - 1]
-                    .and_then(|m| m.get(ngram.value))
-                    .copied()
-                    .unwrap_or(0.0);
-
-                if probability > 0.0 {
-                    sum += probability.ln();
-                    break;
-                }
-            }
+    /// Splits the combined confidence value of the two most likely languages anew
+    /// if a discriminator has been loaded for them.
+    fn apply_pair_discriminator(&self, values: &mut [(Language, f64)], words: &[&str]) {
+        let [(first_language, first_value), (second_language, second_value), ..] = values[..]
+        else {
+            return;
+        };
+
+        if second_value.is_zero() {
+            return;
         }
-        sum
-    }
 
-    fn count_unigrams(
-        &self,
-        unigram_model: &TestDataLanguageModel,
-        filtered_languages: &HashSet<Language>,
-        language_models: &HashMap<Language, AHashMap<CompactString, f64>>,
-    ) -> HashMap<Language, u32> {
-        let mut unigram_counts = HashMap::new();
-        for language in filtered_languages.iter() {
-            let model = match language_models.get(language) {
-                Some(model) => model,
-                None => continue,
-            };
+        let Some(discriminator) = self
+            .pair_discriminators
+            .iter()
+            .find(|discriminator| discriminator.discriminates(first_language, second_language))
+        else {
+            return;
+        };
+
+        let pair_value = first_value + second_value;
+        let first_probability = discriminator.probability_of(first_language, words);
+
+        values[0].1 = pair_value * first_probability;
+        values[1].1 = pair_value * (1.0 - first_probability);
+        values.sort_by(confidence_values_comparator);
+    }
 
-            for unigrams in unigram_model.ngrams.iter() {
-                let probability = model
-                    .get(unigrams.first().unwrap().value)
-                    .copied()
-                    .unwrap_or(0.0);
+    fn count_unigrams(
+        &self,
+        unigram_model: &TestDataLanguageModel,
+        filtered_languages: &HashSet<Language>,
+        scorer: &NgramScorer,
+    ) -> HashMap<Language, u32> {
+        scorer
+            .count_known_ngrams(&unigram_model.ngrams)
+            .into_iter()
+            .filter(|(language, count)| *count > 0 && filtered_languages.contains(language))
+            .collect()
+    }
 
-                if probability > 0.0 {
-                    self.increment_counter(&mut unigram_counts, *language, 1);
+    /// Returns a scorer for the given languages which looks up ngrams in the given tables
+    /// and, if this detector looks up compiled models, in the tables compiled into the binary.
+    fn ngram_scorer<'a>(
+        &self,
+        languages: &HashSet<Language>,
+        language_models: &NgramTableArray<'a>,
+    ) -> NgramScorer<'a> {
+        let mut scorer = NgramScorer::new(languages);
+        for (i, table) in language_models.iter().enumerate() {
+            if let Some(table) = table {
+                scorer.add(i + 1, NgramTableRef::Loaded(table));
+                if let Some(compiled_table) = self.compiled_table(i + 1) {
+                    scorer.add(i + 1, compiled_table);
                 }
             }
         }
-        unigram_counts
+        scorer
+    }
+
+    /// Returns `true` if this detector looks up the models of the given language
+    /// in the tables compiled into the binary.
+    #[cfg(feature = "compiled-models")]
+    fn has_compiled_models(&self, language: &Language) -> bool {
+        self.is_compiled_model_lookup_enabled
+            && CompiledTable::contains(self.is_word_boundary_mode_enabled, language)
+    }
+
+    #[cfg(not(feature = "compiled-models"))]
+    fn has_compiled_models(&self, _: &Language) -> bool {
+        false
+    }
+
+    /// Returns the table of the given ngram length which has been compiled
+    /// into the binary, if this detector looks up compiled models at all.
+    #[cfg(feature = "compiled-models")]
+    fn compiled_table(&self, ngram_length: usize) -> Option<NgramTableRef<'static>> {
+        if !self.is_compiled_model_lookup_enabled {
+            return None;
+        }
+        CompiledTable::of(self.is_word_boundary_mode_enabled, ngram_length)
+            .map(NgramTableRef::Compiled)
+    }
+
+    #[cfg(not(feature = "compiled-models"))]
+    fn compiled_table(&self, _: usize) -> Option<NgramTableRef<'static>> {
+        None
     }
 
-    fn sum_up_probabilities(
+    fn sum_up_log_probabilities(
         &self,
         probability_maps: &[&HashMap<Language, f64>],
-        unigram_counts: &Option<HashMap<Language, u32>>,
+        unigram_counts: Option<&HashMap<Language, u32>>,
+        word_log_probabilities: Option<&HashMap<Language, f64>>,
         filtered_languages: HashSet<Language>,
     ) -> HashMap<Language, f64> {
-        let mut summed_up_probabilities = hashmap!();
+        let mut summed_up_log_probabilities = hashmap!();
         for language in filtered_languages.iter() {
             let mut sum: f64 = probability_maps
                 .iter()
@@ -1223,59 +2390,267 @@ impl LanguageDetector {
                 }
             }
 
+            // Languages without any ngram evidence are not rescued by their word models,
+            // as the interpolation would otherwise rank them above the others.
+            if let Some(word_log_probability) = word_log_probabilities
+                .filter(|_| sum < 0.0)
+                .and_then(|probabilities| probabilities.get(language))
+            {
+                sum = (1.0 - self.word_model_weight) * sum
+                    + self.word_model_weight * word_log_probability;
+            }
+
             if sum != 0.0 {
-                summed_up_probabilities.insert(*language, sum.exp());
+                summed_up_log_probabilities.insert(*language, sum);
             }
         }
 
-        summed_up_probabilities
+        summed_up_log_probabilities
     }
 
     fn load_language_models(
         &self,
-        language_models: StaticLanguageModelMap,
+        language_models: StaticNgramTable,
         language: &Language,
         ngram_length: usize,
     ) {
+        if self.has_compiled_models(language)
+            || (ngram_length > 1 && has_unigram_model_only(*language))
+        {
+            return;
+        }
+
+        // Models which have failed to load are not retried until they are unloaded.
+        let is_unavailable = |models: &NgramTable| {
+            models.contains(language)
+                || self.model_load_failures.read().unwrap().contains_key(&(
+                    *language,
+                    ModelKind::Ngrams,
+                    ngram_length,
+                ))
+        };
+
         let models = language_models.read().unwrap();
-        if !models.contains_key(language) {
+        if !is_unavailable(&models) {
             drop(models);
             let mut models = language_models.write().unwrap();
-            let json = load_json(*language, ngram_length);
-            if let Ok(json_content) = json {
-                models.insert(
+            if !is_unavailable(&models) {
+                match self.load_language_model(language, ngram_length) {
+                    Ok(model) => {
+                        models.insert(*language, model);
+                    }
+                    Err(error) => {
+                        self.model_load_failures
+                            .write()
+                            .unwrap()
+                            .insert((*language, ModelKind::Ngrams, ngram_length), error);
+                    }
+                }
+            }
+        }
+    }
+
+    fn load_language_model(
+        &self,
+        language: &Language,
+        ngram_length: usize,
+    ) -> Result<AHashMap<CompactString, f64>, ModelLoadError> {
+        let load = if self.is_word_boundary_mode_enabled {
+            load_word_boundary_json
+        } else {
+            load_json
+        };
+
+        if self.is_accent_insensitive_mode_enabled {
+            let mut models = Vec::with_capacity(ngram_length);
+            for i in 1..=ngram_length {
+                let json_content = load(*language, i)?;
+                models.push(TrainingDataLanguageModel::from_json(&json_content)?);
+            }
+            Ok(TrainingDataLanguageModel::fold_diacritics(&models))
+        } else {
+            let json_content = load(*language, ngram_length)?;
+            TrainingDataLanguageModel::from_json(&json_content)
+        }
+    }
+
+    fn load_word_model(&self, language: &Language) {
+        // Word models which have failed to load are not retried until they are unloaded.
+        let is_unavailable = |word_models: &HashMap<Language, WordModel>| {
+            word_models.contains_key(language)
+                || self.model_load_failures.read().unwrap().contains_key(&(
                     *language,
-                    TrainingDataLanguageModel::from_json(&json_content),
-                );
+                    ModelKind::Words,
+                    0,
+                ))
+        };
+
+        if !is_unavailable(&self.word_models.read().unwrap()) {
+            let mut word_models = self.word_models.write().unwrap();
+            if !is_unavailable(&word_models) {
+                let word_model = load_word_json(*language)
+                    .map_err(ModelLoadError::from)
+                    .and_then(|json_content| {
+                        WordModel::from_json(&json_content, self.is_accent_insensitive_mode_enabled)
+                    });
+                match word_model {
+                    Ok(word_model) => {
+                        word_models.insert(*language, word_model);
+                    }
+                    Err(error) => {
+                        self.model_load_failures
+                            .write()
+                            .unwrap()
+                            .insert((*language, ModelKind::Words, 0), error);
+                    }
+                }
             }
         }
     }
 
+    fn load_ngram_counts(&self, language: &Language) {
+        let maximum_ngram_length = self.maximum_ngram_length();
+        let is_loaded = |ngram_counts: &HashMap<Language, NgramCounts>| {
+            ngram_counts
+                .get(language)
+                .is_some_and(|counts| counts.maximum_ngram_length() >= maximum_ngram_length)
+        };
+
+        // Ngram counts which have failed to load are not retried until they are unloaded.
+        let has_failed = || {
+            self.model_load_failures
+                .read()
+                .unwrap()
+                .keys()
+                .any(|(failed_language, kind, _)| {
+                    failed_language == language && *kind == ModelKind::NgramCounts
+                })
+        };
+
+        if !is_loaded(&self.ngram_counts.read().unwrap()) && !has_failed() {
+            let mut ngram_counts = self.ngram_counts.write().unwrap();
+            if !is_loaded(&ngram_counts) && !has_failed() {
+                match self.load_ngram_count_model(language, maximum_ngram_length) {
+                    Ok(counts) => {
+                        ngram_counts.insert(*language, counts);
+                    }
+                    Err((ngram_length, error)) => {
+                        self.model_load_failures
+                            .write()
+                            .unwrap()
+                            .insert((*language, ModelKind::NgramCounts, ngram_length), error);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Loads the ngram counts of the given language up to the given ngram length.
+    /// If they cannot be loaded, the ngram length of the failed file is returned
+    /// together with the error.
+    fn load_ngram_count_model(
+        &self,
+        language: &Language,
+        maximum_ngram_length: usize,
+    ) -> Result<NgramCounts, (usize, ModelLoadError)> {
+        let mut counts = Vec::with_capacity(maximum_ngram_length);
+        for ngram_length in 1..=maximum_ngram_length {
+            let absolute_frequencies =
+                load_counts_json(*language, ngram_length, self.is_word_boundary_mode_enabled)
+                    .map_err(ModelLoadError::from)
+                    .and_then(|json_content| {
+                        TrainingDataLanguageModel::counts_from_json(&json_content)
+                    })
+                    .map_err(|error| (ngram_length, error))?;
+
+            if self.is_accent_insensitive_mode_enabled {
+                let mut folded_frequencies = AHashMap::new();
+                for (ngram, count) in absolute_frequencies {
+                    *folded_frequencies
+                        .entry(CompactString::from(fold_diacritics(&ngram)))
+                        .or_insert(0) += count;
+                }
+                counts.push(folded_frequencies);
+            } else {
+                counts.push(absolute_frequencies);
+            }
+        }
+        Ok(NgramCounts::new(counts))
+    }
+
     fn increment_counter<T: Eq + Hash>(&self, counts: &mut HashMap<T, u32>, key: T, value: u32) {
         let counter = counts.entry(key).or_insert(0);
         *counter += value;
     }
 }
 
-pub(crate) fn split_text_into_words(text: &str) -> Vec<String> {
-    LETTERS
-        .find_iter(&text.trim().to_lowercase())
-        .map(|mat| mat.as_str().to_string())
-        .collect()
+/// Splits the given text into words and passes them to the given closure. The text is
+/// case-folded into a buffer which is reused by later calls on the same thread, and the
+/// words are slices of this buffer. The list of words itself is still allocated anew.
+pub(crate) fn with_words_of<R>(
+    text: &str,
+    normalizers: &[Normalizer],
+    tokenizer: &dyn Tokenizer,
+    callback: impl FnOnce(&[&str]) -> R,
+) -> R {
+    with_folded_words_of(
+        &normalize(text.trim(), normalizers),
+        fold_case_into,
+        tokenizer,
+        callback,
+    )
 }
 
-fn collect_languages_with_unique_characters(languages: &HashSet<Language>) -> HashSet<Language> {
-    languages
-        .iter()
-        .filter(|it| it.unique_characters().is_some())
-        .cloned()
-        .collect()
+/// Splits the given text, which has been normalized already, into words after case-folding
+/// it with the given function, and passes them to the given closure.
+fn with_folded_words_of<R>(
+    text: &str,
+    fold: fn(&str, &mut String),
+    tokenizer: &dyn Tokenizer,
+    callback: impl FnOnce(&[&str]) -> R,
+) -> R {
+    // Taking the buffer out of its cell leaves an empty one for nested calls,
+    // such as from a custom tokenizer which uses a detector itself.
+    let mut folded_text = FOLDED_TEXT_BUFFER.take();
+    folded_text.clear();
+    fold(text, &mut folded_text);
+
+    let result = callback(&tokenizer.words(&folded_text));
+
+    if folded_text.capacity() <= MAXIMUM_REUSED_BUFFER_CAPACITY {
+        FOLDED_TEXT_BUFFER.set(folded_text);
+    }
+    result
+}
+
+#[cfg(test)]
+pub(crate) fn split_text_into_words(
+    text: &str,
+    normalizers: &[Normalizer],
+    tokenizer: &dyn Tokenizer,
+) -> Vec<String> {
+    with_words_of(text, normalizers, tokenizer, |words| {
+        words.iter().map(|word| word.to_string()).collect()
+    })
+}
+
+/// Compiled models are only available for the models without folded diacritics,
+/// which are derived from the other models when they are loaded.
+fn is_compiled_model_lookup_enabled(is_accent_insensitive_mode_enabled: bool) -> bool {
+    cfg!(feature = "compiled-models") && !is_accent_insensitive_mode_enabled
 }
 
 fn collect_one_language_alphabets(languages: &HashSet<Language>) -> HashMap<Alphabet, Language> {
+    // Custom languages may be written in an alphabet which is used by
+    // a single built-in language only, so that it is no longer unique.
     Alphabet::all_supporting_single_language()
         .into_iter()
-        .filter(|(_, language)| languages.contains(language))
+        .filter(|(alphabet, language)| {
+            languages.contains(language)
+                && !languages.iter().any(|other| {
+                    matches!(other, Language::Custom(_)) && other.alphabets().contains(alphabet)
+                })
+        })
         .collect()
 }
 
@@ -1285,6 +2660,26 @@ fn confidence_values_comparator(first: &(Language, f64), second: &(Language, f64
     sorted_by_probability.then(sorted_by_language)
 }
 
+/// Converts the given log-probabilities into probabilities which sum up to 1.0
+/// by applying the softmax function. Subtracting the maximum beforehand keeps
+/// the exponents computable even for the tiny probabilities of very long text.
+fn normalize_log_probabilities(log_probabilities: &[f64]) -> Vec<f64> {
+    let maximum_log_probability = log_probabilities
+        .iter()
+        .copied()
+        .fold(f64::NEG_INFINITY, f64::max);
+    let probabilities = log_probabilities
+        .iter()
+        .map(|log_probability| (log_probability - maximum_log_probability).exp())
+        .collect_vec();
+    let denominator: f64 = probabilities.iter().sum();
+
+    probabilities
+        .into_iter()
+        .map(|probability| probability / denominator)
+        .collect()
+}
+
 fn update_confidence_values(
     values: &mut Vec<(Language, f64)>,
     language: Language,
@@ -1333,14 +2728,21 @@ fn merge_adjacent_results(
 }
 
 #[cfg(test)]
+#[allow(clippy::too_many_arguments)]
 mod tests {
+    use std::io::Write;
+
     use float_cmp::approx_eq;
     use once_cell::sync::OnceCell;
     use rstest::*;
+    use tempfile::{tempdir, NamedTempFile, TempDir};
 
     use crate::builder::LanguageDetectorBuilder;
+    use crate::json::{counts_file_name, WORD_MODEL_FILE_NAME};
     use crate::language::Language::*;
     use crate::ngram::NgramRef;
+    use crate::tokenizer::DefaultTokenizer;
+    use crate::writer::LanguageModelFilesWriter;
 
     use super::*;
 
@@ -1490,15 +2892,13 @@ mod tests {
     fn unigram_language_models(
         unigram_language_model_for_english: AHashMap<CompactString, f64>,
         unigram_language_model_for_german: AHashMap<CompactString, f64>,
-    ) -> StaticLanguageModelMap {
-        static UNIGRAM_MODELS_FIXTURE: OnceCell<
-            RwLock<HashMap<Language, AHashMap<CompactString, f64>>>,
-        > = OnceCell::new();
+    ) -> StaticNgramTable {
+        static UNIGRAM_MODELS_FIXTURE: OnceCell<RwLock<NgramTable>> = OnceCell::new();
         UNIGRAM_MODELS_FIXTURE.get_or_init(|| {
-            RwLock::new(hashmap!(
-                English => unigram_language_model_for_english,
-                German => unigram_language_model_for_german
-            ))
+            let mut table = NgramTable::default();
+            table.insert(English, unigram_language_model_for_english);
+            table.insert(German, unigram_language_model_for_german);
+            RwLock::new(table)
         })
     }
 
@@ -1506,15 +2906,13 @@ mod tests {
     fn bigram_language_models(
         bigram_language_model_for_english: AHashMap<CompactString, f64>,
         bigram_language_model_for_german: AHashMap<CompactString, f64>,
-    ) -> StaticLanguageModelMap {
-        static BIGRAM_MODELS_FIXTURE: OnceCell<
-            RwLock<HashMap<Language, AHashMap<CompactString, f64>>>,
-        > = OnceCell::new();
+    ) -> StaticNgramTable {
+        static BIGRAM_MODELS_FIXTURE: OnceCell<RwLock<NgramTable>> = OnceCell::new();
         BIGRAM_MODELS_FIXTURE.get_or_init(|| {
-            RwLock::new(hashmap!(
-                English => bigram_language_model_for_english,
-                German => bigram_language_model_for_german
-            ))
+            let mut table = NgramTable::default();
+            table.insert(English, bigram_language_model_for_english);
+            table.insert(German, bigram_language_model_for_german);
+            RwLock::new(table)
         })
     }
 
@@ -1522,15 +2920,13 @@ mod tests {
     fn trigram_language_models(
         trigram_language_model_for_english: AHashMap<CompactString, f64>,
         trigram_language_model_for_german: AHashMap<CompactString, f64>,
-    ) -> StaticLanguageModelMap {
-        static TRIGRAM_MODELS_FIXTURE: OnceCell<
-            RwLock<HashMap<Language, AHashMap<CompactString, f64>>>,
-        > = OnceCell::new();
+    ) -> StaticNgramTable {
+        static TRIGRAM_MODELS_FIXTURE: OnceCell<RwLock<NgramTable>> = OnceCell::new();
         TRIGRAM_MODELS_FIXTURE.get_or_init(|| {
-            RwLock::new(hashmap!(
-                English => trigram_language_model_for_english,
-                German => trigram_language_model_for_german
-            ))
+            let mut table = NgramTable::default();
+            table.insert(English, trigram_language_model_for_english);
+            table.insert(German, trigram_language_model_for_german);
+            RwLock::new(table)
         })
     }
 
@@ -1538,15 +2934,13 @@ mod tests {
     fn quadrigram_language_models(
         quadrigram_language_model_for_english: AHashMap<CompactString, f64>,
         quadrigram_language_model_for_german: AHashMap<CompactString, f64>,
-    ) -> StaticLanguageModelMap {
-        static QUADRIGRAM_MODELS_FIXTURE: OnceCell<
-            RwLock<HashMap<Language, AHashMap<CompactString, f64>>>,
-        > = OnceCell::new();
+    ) -> StaticNgramTable {
+        static QUADRIGRAM_MODELS_FIXTURE: OnceCell<RwLock<NgramTable>> = OnceCell::new();
         QUADRIGRAM_MODELS_FIXTURE.get_or_init(|| {
-            RwLock::new(hashmap!(
-                English => quadrigram_language_model_for_english,
-                German => quadrigram_language_model_for_german
-            ))
+            let mut table = NgramTable::default();
+            table.insert(English, quadrigram_language_model_for_english);
+            table.insert(German, quadrigram_language_model_for_german);
+            RwLock::new(table)
         })
     }
 
@@ -1554,33 +2948,43 @@ mod tests {
     fn fivegram_language_models(
         fivegram_language_model_for_english: AHashMap<CompactString, f64>,
         fivegram_language_model_for_german: AHashMap<CompactString, f64>,
-    ) -> StaticLanguageModelMap {
-        static FIVEGRAM_MODELS_FIXTURE: OnceCell<
-            RwLock<HashMap<Language, AHashMap<CompactString, f64>>>,
-        > = OnceCell::new();
+    ) -> StaticNgramTable {
+        static FIVEGRAM_MODELS_FIXTURE: OnceCell<RwLock<NgramTable>> = OnceCell::new();
         FIVEGRAM_MODELS_FIXTURE.get_or_init(|| {
-            RwLock::new(hashmap!(
-                English => fivegram_language_model_for_english,
-                German => fivegram_language_model_for_german
-            ))
+            let mut table = NgramTable::default();
+            table.insert(English, fivegram_language_model_for_english);
+            table.insert(German, fivegram_language_model_for_german);
+            RwLock::new(table)
         })
     }
 
+    #[fixture]
+    fn empty_language_models() -> StaticNgramTable {
+        static EMPTY_MODELS_FIXTURE: OnceCell<RwLock<NgramTable>> = OnceCell::new();
+        EMPTY_MODELS_FIXTURE.get_or_init(|| RwLock::new(NgramTable::default()))
+    }
+
+    #[fixture]
+    fn empty_ngram_counts() -> StaticNgramCountsMap {
+        static EMPTY_NGRAM_COUNTS_FIXTURE: OnceCell<RwLock<HashMap<Language, NgramCounts>>> =
+            OnceCell::new();
+        EMPTY_NGRAM_COUNTS_FIXTURE.get_or_init(|| RwLock::new(hashmap!()))
+    }
+
+    #[fixture]
+    fn empty_word_models() -> StaticWordModelMap {
+        static EMPTY_WORD_MODELS_FIXTURE: OnceCell<RwLock<HashMap<Language, WordModel>>> =
+            OnceCell::new();
+        EMPTY_WORD_MODELS_FIXTURE.get_or_init(|| RwLock::new(hashmap!()))
+    }
+
     // ##############################
     // TEST DATA MODELS
     // ##############################
 
     #[fixture(strs=vec![])]
-    fn test_data_model(strs: Vec<Vec<&'static str>>) -> TestDataLanguageModel<'static> {
-        let ngrams = strs
-            .iter()
-            .map(|ngram_strs| {
-                ngram_strs
-                    .iter()
-                    .map(|&it| NgramRef::new(it))
-                    .collect::<Vec<_>>()
-            })
-            .collect::<Vec<_>>();
+    fn test_data_model(strs: Vec<&'static str>) -> TestDataLanguageModel<'static> {
+        let ngrams = strs.iter().map(|&it| NgramRef::new(it)).collect();
 
         TestDataLanguageModel { ngrams }
     }
@@ -1591,33 +2995,65 @@ mod tests {
 
     #[fixture]
     fn detector_for_english_and_german(
-        unigram_language_models: StaticLanguageModelMap,
-        bigram_language_models: StaticLanguageModelMap,
-        trigram_language_models: StaticLanguageModelMap,
-        quadrigram_language_models: StaticLanguageModelMap,
-        fivegram_language_models: StaticLanguageModelMap,
+        unigram_language_models: StaticNgramTable,
+        bigram_language_models: StaticNgramTable,
+        trigram_language_models: StaticNgramTable,
+        quadrigram_language_models: StaticNgramTable,
+        fivegram_language_models: StaticNgramTable,
+        empty_language_models: StaticNgramTable,
+        empty_ngram_counts: StaticNgramCountsMap,
+        empty_word_models: StaticWordModelMap,
     ) -> LanguageDetector {
         let languages = hashset!(English, German);
-        let languages_with_unique_characters = collect_languages_with_unique_characters(&languages);
+        let character_rules = CharacterRules::built_in(&languages);
         let one_language_alphabets = collect_one_language_alphabets(&languages);
 
         LanguageDetector {
             languages,
             minimum_relative_distance: 0.0,
             is_low_accuracy_mode_enabled: false,
-            languages_with_unique_characters,
+            is_accent_insensitive_mode_enabled: false,
+            is_word_boundary_mode_enabled: false,
+            short_text_ngram_lengths: 1..=5,
+            long_text_ngram_lengths: 3..=3,
+            smoothing: Smoothing::None,
+            word_model_weight: 0.0,
+            normalizers: vec![],
+            tokenizer: Arc::new(DefaultTokenizer),
+            character_rules,
             one_language_alphabets,
-            unigram_language_models,
-            bigram_language_models,
-            trigram_language_models,
-            quadrigram_language_models,
-            fivegram_language_models,
+            language_models: [
+                unigram_language_models,
+                bigram_language_models,
+                trigram_language_models,
+                quadrigram_language_models,
+                fivegram_language_models,
+                empty_language_models,
+                empty_language_models,
+                empty_language_models,
+            ],
+            is_compiled_model_lookup_enabled: false,
+            memory_budget: None,
+            model_cache: Arc::new(Mutex::new(ModelCache::default())),
+            model_load_failures: &MODEL_LOAD_FAILURES[0],
+            preloaded_language_count: None,
+            ngram_counts: empty_ngram_counts,
+            word_models: empty_word_models,
+            language_variant_models: vec![],
+            pair_discriminators: vec![],
+            macro_languages: hashset!(),
         }
     }
 
     #[fixture]
     fn detector_for_all_languages() -> LanguageDetector {
-        LanguageDetector::from(Language::all(), 0.0, true, false)
+        LanguageDetector::from(
+            Language::all(),
+            DetectorSettings {
+                is_every_language_model_preloaded: true,
+                ..DetectorSettings::default()
+            },
+        )
     }
 
     // ##############################
@@ -1639,10 +3075,113 @@ mod tests {
         case(
             "Weltweit    gibt es ungefähr 6.000 Sprachen.",
             vec!["weltweit", "gibt", "es", "ungefähr", "sprachen"]
+        ),
+        case(
+            "İSTANBUL'DA IŞIK YOK",
+            vec!["istanbul", "da", "işik", "yok"]
         )
     )]
     fn test_split_text_into_words(text: &str, expected_words: Vec<&str>) {
-        assert_eq!(split_text_into_words(text), expected_words);
+        assert_eq!(
+            split_text_into_words(text, &[], &DefaultTokenizer),
+            expected_words
+        );
+    }
+
+    #[test]
+    fn assert_nested_word_splitting_does_not_share_buffer() {
+        let (outer_words, inner_words) =
+            with_words_of("Outer Text", &[], &DefaultTokenizer, |words| {
+                let inner_words = split_text_into_words("Inner", &[], &DefaultTokenizer);
+                (
+                    words.iter().map(|word| word.to_string()).collect_vec(),
+                    inner_words,
+                )
+            });
+
+        assert_eq!(outer_words, vec!["outer", "text"]);
+        assert_eq!(inner_words, vec!["inner"]);
+        assert_eq!(
+            split_text_into_words("Next", &[], &DefaultTokenizer),
+            vec!["next"]
+        );
+    }
+
+    #[cfg(all(feature = "azerbaijani", feature = "turkish"))]
+    #[rstest(
+        testdata_directory,
+        case(&lingua_azerbaijani_language_model::AZERBAIJANI_TESTDATA_DIRECTORY),
+        case(&lingua_turkish_language_model::TURKISH_TESTDATA_DIRECTORY)
+    )]
+    fn assert_capital_i_of_turkic_test_data_is_folded_to_turkic_lowercase(
+        testdata_directory: &include_dir::Dir,
+    ) {
+        let sentences = testdata_directory
+            .get_file("sentences.txt")
+            .unwrap()
+            .contents_utf8()
+            .unwrap();
+
+        for sentence in sentences.lines().filter(|it| it.contains(['I', 'İ'])) {
+            let mut folded_sentence = String::new();
+            fold_turkic_case_into(sentence, &mut folded_sentence);
+
+            assert_eq!(folded_sentence.chars().count(), sentence.chars().count());
+            for (ch, folded_ch) in sentence.chars().zip(folded_sentence.chars()) {
+                match ch {
+                    'I' => assert_eq!(folded_ch, 'ı'),
+                    'İ' => assert_eq!(folded_ch, 'i'),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    #[rstest(
+        language,
+        testdata_directory,
+        case(Azerbaijani, &lingua_azerbaijani_language_model::AZERBAIJANI_TESTDATA_DIRECTORY),
+        case(Turkish, &lingua_turkish_language_model::TURKISH_TESTDATA_DIRECTORY)
+    )]
+    fn assert_turkic_case_folding_does_not_reduce_accuracy_of_turkic_test_data(
+        language: Language,
+        testdata_directory: &include_dir::Dir,
+    ) {
+        let detector = LanguageDetector::from(
+            hashset!(Azerbaijani, English, French, German, Turkish),
+            DetectorSettings::default(),
+        );
+        let sentences = testdata_directory
+            .get_file("sentences.txt")
+            .unwrap()
+            .contents_utf8()
+            .unwrap();
+        let is_detected =
+            |values: Vec<(Language, f64)>| values.first().map(|(it, _)| *it) == Some(language);
+
+        let mut count_before = 0;
+        let mut count_after = 0;
+
+        for sentence in sentences.lines() {
+            // Without any Turkic case folding, the standard lowercase mapping
+            // splits words at the combining dot above of the dotted capital İ.
+            let lowercase_sentence = sentence.to_lowercase();
+            let words = DefaultTokenizer.words(&lowercase_sentence);
+            if is_detected(detector.compute_language_confidence_values_for_words(
+                &words,
+                None,
+                &detector.languages,
+            )) {
+                count_before += 1;
+            }
+            if is_detected(detector.compute_language_confidence_values_of_str(sentence)) {
+                count_after += 1;
+            }
+        }
+
+        let line_count = sentences.lines().count();
+        assert!(count_after >= count_before);
+        assert!(count_after * 100 >= line_count * 99);
     }
 
     #[rstest(
@@ -1667,24 +3206,24 @@ mod tests {
         expected_probability: f64,
     ) {
         let ngram_length = ngram.chars().count();
-        let probability = detector_for_english_and_german.get_language_models(
+        let log_probability = detector_for_english_and_german.get_language_models(
             ngram_length,
-            &hashset!(language.clone()),
+            &hashset!(language),
             |language_models| {
                 language_models[ngram_length - 1]
                     .unwrap()
-                    .get(&language)
-                    .unwrap()
-                    .get(ngram)
-                    .copied()
-                    .unwrap_or(0.0)
+                    .log_probability(&language, ngram)
             },
         );
 
         assert_eq!(
-            probability, expected_probability,
-            "expected probability {} for language '{:?}' and ngram '{}', got {}",
-            expected_probability, language, ngram, probability
+            log_probability,
+            Some(expected_probability.ln()),
+            "expected probability {} for language '{:?}' and ngram '{}', got {:?}",
+            expected_probability,
+            language,
+            ngram,
+            log_probability.map(f64::exp)
         );
     }
 
@@ -1692,17 +3231,17 @@ mod tests {
         test_data_model,
         expected_sum_of_probabilities,
         case(
-            test_data_model(vec![vec!["a"], vec!["l"], vec!["t"], vec!["e"], vec!["r"]]),
+            test_data_model(vec!["a", "l", "t", "e", "r"]),
             0.01_f64.ln() + 0.02_f64.ln() + 0.03_f64.ln() + 0.04_f64.ln() + 0.05_f64.ln()
         ),
         case(
             // back off unknown Trigram("tez") to known Bigram("te")
-            test_data_model(vec![vec!["alt", "al", "a"], vec!["lte", "lt", "l"], vec!["tez", "te", "t"]]),
+            test_data_model(vec!["alt", "lte", "tez"]),
             0.19_f64.ln() + 0.2_f64.ln() + 0.13_f64.ln()
         ),
         case(
             // back off unknown Fivegram("aquas") to known Unigram("a")
-            test_data_model(vec![vec!["aquas", "aqua", "aqu", "aq", "a"]]),
+            test_data_model(vec!["aquas"]),
             0.01_f64.ln()
         )
     )]
@@ -1715,11 +3254,10 @@ mod tests {
             5,
             &hashset!(English),
             |language_models| {
-                detector_for_english_and_german.compute_sum_of_ngram_probabilities(
-                    &English,
-                    &test_data_model,
-                    &language_models,
-                )
+                let scorer = detector_for_english_and_german
+                    .ngram_scorer(&hashset!(English), &language_models);
+                detector_for_english_and_german
+                    .compute_language_probabilities(&test_data_model, &scorer)[&English]
             },
         );
 
@@ -1742,21 +3280,21 @@ mod tests {
         test_data_model,
         expected_probabilities,
         case::unigram_model(
-            test_data_model(vec![vec!["a"], vec!["l"], vec!["t"], vec!["e"], vec!["r"]]),
+            test_data_model(vec!["a", "l", "t", "e", "r"]),
             hashmap!(
                 English => 0.01_f64.ln() + 0.02_f64.ln() + 0.03_f64.ln() + 0.04_f64.ln() + 0.05_f64.ln(),
                 German => 0.06_f64.ln() + 0.07_f64.ln() + 0.08_f64.ln() + 0.09_f64.ln() + 0.1_f64.ln()
             )
         ),
         case::trigram_model(
-            test_data_model(vec![vec!["alt", "al", "a"], vec!["lte", "lt", "l"], vec!["ter", "te", "t"], vec!["wxy", "wx", "w"]]),
+            test_data_model(vec!["alt", "lte", "ter", "wxy"]),
             hashmap!(
                 English => 0.19_f64.ln() + 0.2_f64.ln() + 0.21_f64.ln(),
                 German => 0.22_f64.ln() + 0.23_f64.ln() + 0.24_f64.ln()
             )
         ),
         case::quadrigram_model(
-            test_data_model(vec![vec!["alte", "alt", "al", "a"], vec!["lter", "lte", "lt", "l"], vec!["wxyz", "wxy", "wx", "w"]]),
+            test_data_model(vec!["alte", "lter", "wxyz"]),
             hashmap!(
                 English => 0.25_f64.ln() + 0.26_f64.ln(),
                 German => 0.27_f64.ln() + 0.28_f64.ln()
@@ -1771,11 +3309,10 @@ mod tests {
         let languages = hashset!(English, German);
         let probabilities =
             detector_for_english_and_german.get_language_models(5, &languages, |language_models| {
-                detector_for_english_and_german.compute_language_probabilities(
-                    &test_data_model,
-                    &languages,
-                    &language_models,
-                )
+                let scorer =
+                    detector_for_english_and_german.ngram_scorer(&languages, &language_models);
+                detector_for_english_and_german
+                    .compute_language_probabilities(&test_data_model, &scorer)
             });
 
         for (language, probability) in probabilities {
@@ -1806,7 +3343,7 @@ mod tests {
         let confidence_values = detector_for_english_and_german
             .compute_language_confidence_values(text)
             .iter()
-            .map(|(language, value)| (language.clone(), round_to_two_decimal_places(*value)))
+            .map(|(language, value)| (*language, round_to_two_decimal_places(*value)))
             .collect::<Vec<(Language, f64)>>();
 
         assert_eq!(confidence_values, expected_confidence_values);
@@ -1814,12 +3351,27 @@ mod tests {
 
     #[rstest]
     fn test_compute_language_confidence_values_for_very_large_input_text() {
-        let detector = LanguageDetector::from(hashset!(English, German), 0.0, true, false);
+        let detector = LanguageDetector::from(
+            hashset!(English, German),
+            DetectorSettings {
+                is_every_language_model_preloaded: true,
+                ..DetectorSettings::default()
+            },
+        );
         let confidence_values = detector.compute_language_confidence_values(VERY_LARGE_INPUT_TEXT);
         let expected_confidence_values = vec![(German, 1.0), (English, 0.0)];
         assert_eq!(confidence_values, expected_confidence_values);
     }
 
+    #[test]
+    fn assert_tiny_probabilities_are_normalized_in_log_space() {
+        let probabilities = normalize_log_probabilities(&[-5000.0, -5000.0 - 3f64.ln(), -9000.0]);
+
+        assert!(approx_eq!(f64, probabilities[0], 0.75, epsilon = 1e-9));
+        assert!(approx_eq!(f64, probabilities[1], 0.25, epsilon = 1e-9));
+        assert_eq!(probabilities[2], 0.0);
+    }
+
     #[rstest(
         text,
         language,
@@ -1857,6 +3409,44 @@ mod tests {
     ) {
         let detected_language = detector_for_english_and_german.detect_language_of(word);
         assert_eq!(detected_language, expected_language);
+
+        let detected_language = detector_for_english_and_german.detect_language_of_str(word);
+        assert_eq!(detected_language, expected_language);
+    }
+
+    #[rstest(
+        text,
+        expected_outcome,
+        case("Alter", DetectionOutcome::Detected(German)),
+        case("проарплап", DetectionOutcome::UnsupportedAlphabet(Alphabet::Cyrillic)),
+        case("", DetectionOutcome::Undecided),
+        case("12345", DetectionOutcome::Undecided)
+    )]
+    fn test_detect_language_outcome(
+        detector_for_english_and_german: LanguageDetector,
+        text: &str,
+        expected_outcome: DetectionOutcome,
+    ) {
+        let outcome = detector_for_english_and_german.detect_language_outcome_of(text);
+        assert_eq!(outcome, expected_outcome);
+        assert_eq!(
+            outcome.language(),
+            detector_for_english_and_german.detect_language_of(text)
+        );
+    }
+
+    #[rstest]
+    fn assert_language_of_owned_text_and_char_can_be_detected(
+        detector_for_english_and_german: LanguageDetector,
+    ) {
+        assert_eq!(
+            detector_for_english_and_german.detect_language_of(String::from("Alter")),
+            Some(German)
+        );
+        assert_eq!(
+            detector_for_english_and_german.detect_language_of('ß'),
+            Some(German)
+        );
     }
 
     #[rstest]
@@ -2172,10 +3762,8 @@ mod tests {
         word: &str,
         expected_language: Option<Language>,
     ) {
-        let detected_language = detector_for_all_languages.detect_language_with_rules(
-            &vec![word.to_string()],
-            &detector_for_all_languages.languages,
-        );
+        let detected_language = detector_for_all_languages
+            .detect_language_with_rules(&[word], &detector_for_all_languages.languages);
         assert_eq!(
             detected_language, expected_language,
             "expected {:?} for word '{}', got {:?}",
@@ -2305,10 +3893,8 @@ mod tests {
         word: &str,
         expected_languages: HashSet<Language>,
     ) {
-        let filtered_languages = detector_for_all_languages.filter_languages_by_rules(
-            &vec![word.to_string()],
-            &detector_for_all_languages.languages,
-        );
+        let filtered_languages = detector_for_all_languages
+            .filter_languages_by_rules(&[word], &detector_for_all_languages.languages);
         assert_eq!(
             filtered_languages, expected_languages,
             "expected {:?} for word '{}', got {:?}",
@@ -2338,8 +3924,13 @@ mod tests {
         )
     )]
     fn assert_language_detection_is_deterministic(text: &str, languages: Vec<Language>) {
-        let detector =
-            LanguageDetector::from(languages.iter().cloned().collect(), 0.0, true, false);
+        let detector = LanguageDetector::from(
+            languages.iter().cloned().collect(),
+            DetectorSettings {
+                is_every_language_model_preloaded: true,
+                ..DetectorSettings::default()
+            },
+        );
         let mut detected_languages = hashset!();
         for _ in 0..100 {
             let language = detector.detect_language_of(text);
@@ -2353,13 +3944,1077 @@ mod tests {
         );
     }
 
+    #[rstest(
+        word,
+        expected_language,
+        case("weiß", Some(German)),
+        case("źdźbło", Some(Polish)),
+        case("hello", None)
+    )]
+    fn assert_language_of_word_can_be_detected_with_derived_character_rules(
+        word: &str,
+        expected_language: Option<Language>,
+    ) {
+        let detector = LanguageDetector::from(
+            Language::all(),
+            DetectorSettings {
+                is_derived_character_rules_enabled: true,
+                ..DetectorSettings::default()
+            },
+        );
+        assert_eq!(
+            detector.detect_language_with_rules(&[word], &detector.languages),
+            expected_language
+        );
+    }
+
+    #[rstest(
+        text,
+        expected_alphabet,
+        case("አማርኛ ቋንቋ", Alphabet::Ethiopic),
+        case("ಕನ್ನಡ ಭಾಷೆ", Alphabet::Kannada),
+        case("ភាសាខ្មែរ", Alphabet::Khmer),
+        case("ພາສາລາວ", Alphabet::Lao),
+        case("മലയാളം ഭാഷ", Alphabet::Malayalam),
+        case("မြန်မာဘာသာ", Alphabet::Myanmar),
+        case("සිංහල භාෂාව", Alphabet::Sinhala),
+        case("བོད་ཡིག", Alphabet::Tibetan)
+    )]
+    fn assert_text_in_unsupported_alphabet_is_rejected(
+        detector_for_all_languages: LanguageDetector,
+        text: &str,
+        expected_alphabet: Alphabet,
+    ) {
+        assert_eq!(
+            detector_for_all_languages.detect_unsupported_alphabet_of(text),
+            Some(expected_alphabet)
+        );
+        assert_eq!(detector_for_all_languages.detect_language_of(text), None);
+        assert!(detector_for_all_languages
+            .compute_language_confidence_values(text)
+            .iter()
+            .all(|(_, confidence)| *confidence == 0.0));
+    }
+
+    #[rstest(
+        text,
+        expected_alphabet,
+        case("Привет, как дела?", Some(Alphabet::Cyrillic)),
+        case("languages are awesome", None),
+        case("12345 !?", None)
+    )]
+    fn assert_alphabet_unsupported_by_configured_languages_is_detected(
+        detector_for_english_and_german: LanguageDetector,
+        text: &str,
+        expected_alphabet: Option<Alphabet>,
+    ) {
+        assert_eq!(
+            detector_for_english_and_german.detect_unsupported_alphabet_of(text),
+            expected_alphabet
+        );
+    }
+
     #[rstest]
     fn assert_low_accuracy_mode_returns_no_language_for_unigrams_and_bigrams() {
-        let detector = LanguageDetector::from(hashset!(English, German), 0.0, true, true);
+        let detector = LanguageDetector::from(
+            hashset!(English, German),
+            DetectorSettings {
+                is_every_language_model_preloaded: true,
+                is_low_accuracy_mode_enabled: true,
+                ..DetectorSettings::default()
+            },
+        );
 
         assert_ne!(detector.detect_language_of("bed"), None);
         assert_eq!(detector.detect_language_of("be"), None);
         assert_eq!(detector.detect_language_of("b"), None);
         assert_eq!(detector.detect_language_of(""), None);
     }
+
+    #[rstest(
+        text,
+        expected_language,
+        case("zażółć gęślą jaźń", Polish),
+        case("zazolc gesla jazn", Polish),
+        case("příliš žluťoučký kůň", Czech),
+        case("prilis zlutoucky kun", Czech)
+    )]
+    fn assert_accent_insensitive_mode_detects_text_without_diacritics(
+        text: &str,
+        expected_language: Language,
+    ) {
+        let detector = LanguageDetector::from(
+            hashset!(Czech, Polish, Slovak),
+            DetectorSettings {
+                is_accent_insensitive_mode_enabled: true,
+                ..DetectorSettings::default()
+            },
+        );
+        assert_eq!(detector.detect_language_of(text), Some(expected_language));
+    }
+
+    #[test]
+    fn assert_capital_i_of_turkic_text_is_also_scored_as_dotless_i() {
+        let detector = LanguageDetectorBuilder::from_languages(&[Azerbaijani, Turkish]).build();
+        assert_eq!(detector.detect_language_of("IŞIK"), Some(Turkish));
+        assert_eq!(detector.detect_language_of("ışık"), Some(Turkish));
+    }
+
+    #[test]
+    fn assert_normalizers_are_applied_before_splitting_text_into_words() {
+        assert_eq!(
+            split_text_into_words(
+                "Ｓｏｏｏｏｏ\u{200B}ｏ ｃｏｏｌ 2024",
+                &[
+                    Normalizer::RemoveInvisibleCharacters,
+                    Normalizer::FoldFullwidthCharacters,
+                    Normalizer::SquashElongation,
+                    Normalizer::StripNumerals,
+                ],
+                &DefaultTokenizer
+            ),
+            vec!["soo", "cool"]
+        );
+    }
+
+    /// Registers a custom language whose models are written with the given writer
+    /// from the given text to a temporary directory.
+    fn custom_language_with_models(
+        name: &str,
+        iso_code_639_1: &str,
+        iso_code_639_3: &str,
+        text: &str,
+        writer: &LanguageModelFilesWriter,
+    ) -> (Language, TempDir) {
+        let models_directory = tempdir().unwrap();
+        let language = Language::register_custom(
+            name,
+            iso_code_639_1,
+            iso_code_639_3,
+            &[Alphabet::Latin],
+            models_directory.path(),
+        );
+        let mut input_file = NamedTempFile::new().unwrap();
+        input_file.write_all(text.as_bytes()).unwrap();
+        writer
+            .write_language_model_files(
+                input_file.path(),
+                models_directory.path(),
+                &language,
+                "\\p{L}",
+            )
+            .unwrap();
+        (language, models_directory)
+    }
+
+    #[test]
+    fn assert_word_boundary_models_are_used_if_available_for_all_languages() {
+        let mut writer = LanguageModelFilesWriter::new();
+        writer.with_word_boundary_ngrams();
+        let (corsican, _corsican_models) = custom_language_with_models(
+            "Corsican",
+            "co",
+            "cos",
+            "Tutti l'esseri umani nascinu libari è pari in dignità è in diritti.",
+            &writer,
+        );
+        let (walloon, _walloon_models) = custom_language_with_models(
+            "Walloon",
+            "wa",
+            "wln",
+            "Tos les djins vénèt å monde libes èt égåls po çou qu'è dè dignité èt d' dreûts.",
+            &writer,
+        );
+
+        let detector = LanguageDetectorBuilder::from_languages(&[corsican, walloon])
+            .with_word_boundary_ngrams()
+            .build();
+
+        assert!(detector.is_word_boundary_mode_enabled);
+        assert_eq!(detector.detect_language_of("umani nascinu"), Some(corsican));
+    }
+
+    #[test]
+    fn assert_smoothing_changes_classification_of_words_with_unseen_ngrams() {
+        let mut writer = LanguageModelFilesWriter::new();
+        writer.with_ngram_counts();
+        let (interlingua, _interlingua_models) = custom_language_with_models(
+            "Interlingua",
+            "ia",
+            "ina",
+            "Tote le esseres human nasce libere e equal in dignitate e in derectos. \
+             Illes es dotate de ration e de conscientia e debe ager le unes verso le alteres \
+             in un spirito de fraternitate.",
+            &writer,
+        );
+        let (ido, _ido_models) = custom_language_with_models(
+            "Ido",
+            "io",
+            "ido",
+            "Omna homi naskas libera ed egala relate digneso e yuri. \
+             Li esas dotita per racioneso e koncienco e devas agar vers l'una l'altra \
+             en spirito di frateso.",
+            &writer,
+        );
+
+        let detector = LanguageDetectorBuilder::from_languages(&[interlingua, ido]).build();
+
+        assert_eq!(detector.detect_language_of("liberta"), Some(ido));
+        assert_eq!(detector.detect_language_of("fratero"), Some(ido));
+
+        for smoothing in [
+            Smoothing::AddK(0.5),
+            Smoothing::WittenBell,
+            Smoothing::KneserNey(0.75),
+        ] {
+            let detector = LanguageDetectorBuilder::from_languages(&[interlingua, ido])
+                .with_smoothing(smoothing)
+                .build();
+
+            assert_eq!(detector.smoothing, smoothing);
+            assert_eq!(detector.detect_language_of("liberta"), Some(interlingua));
+            assert_eq!(detector.detect_language_of("fratero"), Some(interlingua));
+        }
+    }
+
+    struct DictionaryTokenizer;
+
+    impl Tokenizer for DictionaryTokenizer {
+        fn words<'a>(&self, text: &'a str) -> Vec<&'a str> {
+            let mut words = vec![];
+            let mut rest = text;
+            while let Some(ch) = rest.chars().next() {
+                let word = ["北京", "大学", "東京", "大学"]
+                    .into_iter()
+                    .find(|word| rest.starts_with(word))
+                    .unwrap_or(&rest[..ch.len_utf8()]);
+                if word.chars().all(char::is_alphabetic) {
+                    words.push(&rest[..word.len()]);
+                }
+                rest = &rest[word.len()..];
+            }
+            words
+        }
+
+        fn token_ranges(&self, text: &str) -> Vec<std::ops::Range<usize>> {
+            let mut start = 0;
+            self.words(text)
+                .into_iter()
+                .map(|word| {
+                    let end = word.as_ptr() as usize - text.as_ptr() as usize + word.len();
+                    let range = start..end;
+                    start = end;
+                    range
+                })
+                .collect()
+        }
+    }
+
+    fn create_ngram_counts(text: &str) -> NgramCounts {
+        let counts = (1..=3)
+            .map(|ngram_length| {
+                let mut counts = AHashMap::new();
+                for word in text.split(' ') {
+                    let chars = word.chars().collect_vec();
+                    for ngram in chars.windows(ngram_length) {
+                        *counts
+                            .entry(ngram.iter().collect::<CompactString>())
+                            .or_insert(0) += 1;
+                    }
+                }
+                counts
+            })
+            .collect();
+        NgramCounts::new(counts)
+    }
+
+    #[rstest(
+        smoothing,
+        case(Smoothing::AddK(0.5)),
+        case(Smoothing::WittenBell),
+        case(Smoothing::KneserNey(0.75))
+    )]
+    fn assert_smoothing_assigns_probabilities_to_unseen_ngrams(
+        mut detector_for_english_and_german: LanguageDetector,
+        smoothing: Smoothing,
+    ) {
+        static NGRAM_COUNTS_FIXTURE: OnceCell<RwLock<HashMap<Language, NgramCounts>>> =
+            OnceCell::new();
+
+        detector_for_english_and_german.short_text_ngram_lengths = 1..=3;
+        detector_for_english_and_german.smoothing = smoothing;
+        detector_for_english_and_german.ngram_counts = NGRAM_COUNTS_FIXTURE.get_or_init(|| {
+            RwLock::new(hashmap!(
+                English => create_ngram_counts("these terms are great and the weather is fine"),
+                German => create_ngram_counts("das alter ist hier und das wetter ist gut")
+            ))
+        });
+
+        let confidence_values =
+            detector_for_english_and_german.compute_language_confidence_values("alter");
+
+        assert_eq!(confidence_values[0].0, German);
+        assert!(confidence_values[1].1 > 0.0);
+    }
+
+    #[rstest(
+        word_model_weight,
+        text,
+        expected_language,
+        case(0.0, "alter", German),
+        case(0.9, "alter", English),
+        case(0.9, "alter alter", English),
+        case(0.9, "alter alter alter", German)
+    )]
+    fn assert_word_model_is_interpolated_for_one_or_two_words(
+        mut detector_for_english_and_german: LanguageDetector,
+        word_model_weight: f64,
+        text: &str,
+        expected_language: Language,
+    ) {
+        static WORD_MODELS_FIXTURE: OnceCell<RwLock<HashMap<Language, WordModel>>> =
+            OnceCell::new();
+
+        detector_for_english_and_german.word_model_weight = word_model_weight;
+        detector_for_english_and_german.word_models = WORD_MODELS_FIXTURE.get_or_init(|| {
+            RwLock::new(hashmap!(
+                English => WordModel::new(create_language_model_map(
+                    hashmap!("alter" => 0.5, "the" => 0.5)
+                )),
+                German => WordModel::new(create_language_model_map(
+                    hashmap!("der" => 0.999, "alte" => 0.001)
+                ))
+            ))
+        });
+
+        let confidence_values =
+            detector_for_english_and_german.compute_language_confidence_values(text);
+
+        assert_eq!(confidence_values[0].0, expected_language);
+    }
+
+    #[test]
+    fn assert_word_models_are_used_if_available_for_all_languages() {
+        let mut writer = LanguageModelFilesWriter::new();
+        writer.with_word_model();
+        let (interlingue, _interlingue_models) = custom_language_with_models(
+            "Interlingue",
+            "ie",
+            "ile",
+            "Omni homes nasce liber e egal in dignitá e in jures. \
+             Ili es dotat de rason e conscientie e deve agir unaltru in un spiritu de fratrie.",
+            &writer,
+        );
+        let (volapuk, _volapuk_models) = custom_language_with_models(
+            "Volapuk",
+            "vo",
+            "vol",
+            "Mens valik pemotons libiko e leigiko as dinit e gitäts. \
+             Labons tikäli e konsieni e sötons kondötön ad ods in tikamod svistäla.",
+            &writer,
+        );
+
+        let detector = LanguageDetectorBuilder::from_languages(&[interlingue, volapuk])
+            .with_word_model(0.5)
+            .build();
+
+        assert_eq!(detector.word_model_weight, 0.5);
+        assert_eq!(
+            detector.detect_language_of("homes liber"),
+            Some(interlingue)
+        );
+        assert_eq!(detector.detect_language_of("mens valik"), Some(volapuk));
+    }
+
+    #[rstest(
+        short_text_ngram_lengths,
+        long_text_ngram_lengths,
+        case(1..=6, 3..=7),
+        case(2..=4, 4..=4),
+        case(6..=8, 6..=8)
+    )]
+    fn assert_language_is_detected_with_custom_ngram_lengths(
+        short_text_ngram_lengths: RangeInclusive<usize>,
+        long_text_ngram_lengths: RangeInclusive<usize>,
+    ) {
+        let detector = LanguageDetector::from(
+            hashset!(English, German),
+            DetectorSettings {
+                short_text_ngram_lengths,
+                long_text_ngram_lengths,
+                ..DetectorSettings::default()
+            },
+        );
+
+        assert_eq!(
+            detector.detect_language_of("Ich spreche eine andere Sprache"),
+            Some(German)
+        );
+        assert_eq!(detector.compute_language_confidence_values("ja").len(), 2);
+    }
+
+    #[test]
+    fn assert_custom_tokenizer_is_used_for_splitting_text_into_words() {
+        let detector = LanguageDetector::from(
+            hashset!(Chinese, Japanese),
+            DetectorSettings {
+                tokenizer: Arc::new(DictionaryTokenizer),
+                ..DetectorSettings::default()
+            },
+        );
+
+        assert_eq!(
+            split_text_into_words("北京大学。", &[], detector.tokenizer.as_ref()),
+            vec!["北京", "大学"]
+        );
+        assert_eq!(detector.detect_language_of("北京大学"), Some(Chinese));
+
+        let results = detector.detect_multiple_languages_of("北京大学");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].word_count(), 2);
+    }
+
+    #[test]
+    fn assert_markup_is_ignored_when_cleaning_markup() {
+        let text = "Schau mal <a href=\"https://example.com/the-best-things-ever\">hier</a> \
+            @thebestthingsever #WirSehenUnsMorgen 👍";
+        let detector = LanguageDetector::from(
+            hashset!(English, German),
+            DetectorSettings {
+                normalizers: vec![Normalizer::CleanMarkup],
+                ..DetectorSettings::default()
+            },
+        );
+
+        assert_eq!(
+            split_text_into_words(text, &detector.normalizers, &DefaultTokenizer),
+            vec!["schau", "mal", "hier", "wir", "sehen", "uns", "morgen"]
+        );
+        assert_eq!(detector.detect_language_of(text), Some(German));
+    }
+
+    #[test]
+    fn assert_indices_of_multiple_languages_are_correct_with_normalizers() {
+        let text =
+            "<b>Parlez-vous</b> frrr4rrançais? Ich spreche Französisch nur ein bisssss7sschen.";
+        let detector = LanguageDetectorBuilder::from_languages(&[English, French, German])
+            .with_normalizers(&[
+                Normalizer::CleanMarkup,
+                Normalizer::StripNumerals,
+                Normalizer::SquashElongation,
+            ])
+            .build();
+        let results = detector.detect_multiple_languages_of(text);
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(
+            &text[results[0].start_index()..results[0].end_index()],
+            "<b>Parlez-vous</b> frrr4rrançais? "
+        );
+        assert_eq!(results[0].language(), French);
+        assert_eq!(
+            &text[results[1].start_index()..results[1].end_index()],
+            "Ich spreche Französisch nur ein bisssss7sschen."
+        );
+        assert_eq!(results[1].language(), German);
+    }
+
+    #[test]
+    fn assert_indices_of_multiple_languages_refer_to_original_text() {
+        let text =
+            "Parlez-vous\u{200B}\u{200B} français? Ich spreche Französisch nur ein bisschen.";
+        let detector = LanguageDetector::from(
+            hashset!(English, French, German),
+            DetectorSettings {
+                normalizers: vec![Normalizer::RemoveInvisibleCharacters],
+                ..DetectorSettings::default()
+            },
+        );
+        let results = detector.detect_multiple_languages_of(text);
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(
+            &text[results[0].start_index()..results[0].end_index()],
+            "Parlez-vous\u{200B}\u{200B} français? "
+        );
+        assert_eq!(results[0].language(), French);
+        assert_eq!(
+            &text[results[1].start_index()..results[1].end_index()],
+            "Ich spreche Französisch nur ein bisschen."
+        );
+        assert_eq!(results[1].language(), German);
+    }
+
+    #[test]
+    fn assert_custom_language_is_detected() {
+        let models_directory = tempdir().unwrap();
+        let occitan = Language::register_custom(
+            "Occitan",
+            "oc",
+            "oci",
+            &[Alphabet::Latin],
+            models_directory.path(),
+        );
+
+        let mut input_file = NamedTempFile::new().unwrap();
+        input_file
+            .write_all(
+                "Totas las personas naisson liuras e egalas en dignitat e en dreches. \
+                 Son dotadas de rason e de consciéncia e se devon comportar \
+                 las unas amb las autras dins un esperit de fraternitat."
+                    .as_bytes(),
+            )
+            .unwrap();
+        LanguageModelFilesWriter::create_and_write_language_model_files(
+            input_file.path(),
+            models_directory.path(),
+            &occitan,
+            "\\p{L}",
+        )
+        .unwrap();
+
+        let detector = LanguageDetectorBuilder::from_languages(&[English, German, occitan]).build();
+        let text = "las personas naisson liuras";
+
+        assert_eq!(detector.detect_language_of(text), Some(occitan));
+        assert_eq!(
+            detector.compute_language_confidence_values(text)[0].0,
+            occitan
+        );
+    }
+
+    #[test]
+    fn assert_model_load_failures_are_reported() {
+        let models_directory = tempdir().unwrap();
+        let sindarin = Language::register_custom(
+            "Sindarin",
+            "sj",
+            "sjn",
+            &[Alphabet::Latin],
+            models_directory.path(),
+        );
+        std::fs::write(
+            models_directory.path().join("unigrams.json.br"),
+            "not a compressed model",
+        )
+        .unwrap();
+
+        let detector = LanguageDetectorBuilder::from_languages(&[English, sindarin]).build();
+        detector.detect_language_of("mae govannen");
+
+        let status = detector.model_status();
+        assert!(!status.is_healthy());
+        assert!(status
+            .loaded_models()
+            .iter()
+            .all(|(language, _)| *language == English));
+
+        let failures = status.failures();
+        assert!(failures
+            .iter()
+            .all(|failure| failure.language() == sindarin));
+        assert!(matches!(failures[0].error(), ModelLoadError::Unreadable(_)));
+        assert_eq!(failures[0].ngram_length(), 1);
+        assert!(failures[1..]
+            .iter()
+            .all(|failure| *failure.error() == ModelLoadError::Missing));
+
+        let mut input_file = NamedTempFile::new().unwrap();
+        input_file
+            .write_all("Mae govannen, mellon nin. Le suilon.".as_bytes())
+            .unwrap();
+        LanguageModelFilesWriter::create_and_write_language_model_files(
+            input_file.path(),
+            models_directory.path(),
+            &sindarin,
+            "\\p{L}",
+        )
+        .unwrap();
+
+        // Failed models are not retried until they are unloaded.
+        detector.detect_language_of("mae govannen");
+        assert_eq!(detector.model_status().failures(), failures);
+
+        detector.unload_language_models();
+        detector.detect_language_of("mae govannen");
+        let status = detector.model_status();
+        assert!(status.is_healthy());
+        assert!(status.loaded_models().contains(&(sindarin, 1)));
+    }
+
+    #[test]
+    fn assert_ngram_count_load_failures_are_reported() {
+        let models_directory = tempdir().unwrap();
+        let register_custom_language = |name, iso_code_639_1, iso_code_639_3| {
+            let language_directory = models_directory.path().join(iso_code_639_1);
+            std::fs::create_dir(&language_directory).unwrap();
+            for ngram_length in 1..=MAXIMUM_NGRAM_LENGTH {
+                std::fs::write(
+                    language_directory.join(counts_file_name(ngram_length, false)),
+                    "not a compressed model",
+                )
+                .unwrap();
+            }
+            Language::register_custom(
+                name,
+                iso_code_639_1,
+                iso_code_639_3,
+                &[Alphabet::Latin],
+                language_directory,
+            )
+        };
+        let quenya = register_custom_language("Quenya", "qy", "qya");
+        let telerin = register_custom_language("Telerin", "tx", "tlx");
+
+        let detector = LanguageDetectorBuilder::from_languages(&[quenya, telerin])
+            .with_smoothing(Smoothing::WittenBell)
+            .build();
+        assert_eq!(detector.detect_language_of("elen síla lúmenn"), None);
+
+        let status = detector.model_status();
+        assert!(!status.is_healthy());
+        assert_eq!(
+            status
+                .failures()
+                .iter()
+                .map(|failure| failure.language())
+                .collect::<HashSet<_>>(),
+            hashset!(quenya, telerin)
+        );
+        for failure in status.failures() {
+            assert_eq!(failure.kind(), ModelKind::NgramCounts);
+            assert_eq!(failure.ngram_length(), 1);
+            assert!(matches!(failure.error(), ModelLoadError::Unreadable(_)));
+        }
+    }
+
+    #[test]
+    fn assert_word_model_load_failures_are_reported() {
+        let models_directory = tempdir().unwrap();
+        let register_custom_language = |name, iso_code_639_1, iso_code_639_3| {
+            let language_directory = models_directory.path().join(iso_code_639_1);
+            std::fs::create_dir(&language_directory).unwrap();
+            std::fs::write(
+                language_directory.join(WORD_MODEL_FILE_NAME),
+                "not a compressed model",
+            )
+            .unwrap();
+            Language::register_custom(
+                name,
+                iso_code_639_1,
+                iso_code_639_3,
+                &[Alphabet::Latin],
+                language_directory,
+            )
+        };
+        let doriathrin = register_custom_language("Doriathrin", "dx", "drx");
+        let nandorin = register_custom_language("Nandorin", "nx", "nnx");
+
+        let detector = LanguageDetectorBuilder::from_languages(&[doriathrin, nandorin])
+            .with_word_model(0.5)
+            .build();
+        detector.detect_language_of("elen síla");
+
+        let word_model_failures = detector
+            .model_status()
+            .failures()
+            .iter()
+            .filter(|failure| failure.kind() == ModelKind::Words)
+            .cloned()
+            .collect_vec();
+        assert_eq!(word_model_failures.len(), 2);
+        for failure in word_model_failures {
+            assert_eq!(failure.ngram_length(), 0);
+            assert!(matches!(failure.error(), ModelLoadError::Unreadable(_)));
+        }
+
+        detector.unload_language_models();
+        assert!(detector.model_status().is_healthy());
+    }
+
+    #[rstest]
+    fn assert_word_model_is_only_interpolated_for_languages_with_ngram_evidence(
+        mut detector_for_english_and_german: LanguageDetector,
+    ) {
+        detector_for_english_and_german.word_model_weight = 0.9;
+
+        let ngram_log_probabilities = hashmap!(English => -20.0);
+        let word_log_probabilities = hashmap!(English => -10.0, German => -1.0);
+        let summed_up_log_probabilities = detector_for_english_and_german.sum_up_log_probabilities(
+            &[&ngram_log_probabilities],
+            None,
+            Some(&word_log_probabilities),
+            hashset!(English, German),
+        );
+
+        assert_eq!(summed_up_log_probabilities.len(), 1);
+        assert!(approx_eq!(
+            f64,
+            summed_up_log_probabilities[&English],
+            0.1 * -20.0 + 0.9 * -10.0,
+            ulps = 2
+        ));
+    }
+
+    #[rstest(
+        text,
+        expected_script,
+        case("北京大学的图书馆很大", Some(ChineseScript::Simplified)),
+        case("臺灣大學的圖書館很大", Some(ChineseScript::Traditional)),
+        case("这个問題很難回答", Some(ChineseScript::Mixed)),
+        case("山上有人", None),
+        case("東京の大学に行きます", None)
+    )]
+    fn assert_chinese_script_is_detected(text: &str, expected_script: Option<ChineseScript>) {
+        let detector =
+            LanguageDetectorBuilder::from_languages(&[Chinese, English, Japanese]).build();
+        assert_eq!(detector.detect_chinese_script_of(text), expected_script);
+    }
+
+    #[test]
+    fn assert_pair_discriminator_overrides_ranking() {
+        let models_directory = tempdir().unwrap();
+        let mut bokmal_input_file = NamedTempFile::new().unwrap();
+        let mut nynorsk_input_file = NamedTempFile::new().unwrap();
+        bokmal_input_file
+            .write_all("Jeg vet ikke hva du mener.\nHva gjør du nå?".as_bytes())
+            .unwrap();
+        nynorsk_input_file
+            .write_all("Eg veit ikkje kva du meiner.\nKva gjer du no?".as_bytes())
+            .unwrap();
+
+        // The training data is deliberately swapped so that the discriminator
+        // contradicts the language models.
+        LanguageModelFilesWriter::new()
+            .write_pair_discriminator_file(
+                nynorsk_input_file.path(),
+                bokmal_input_file.path(),
+                models_directory.path(),
+                &Bokmal,
+                &Nynorsk,
+                "\\p{L}",
+            )
+            .unwrap();
+
+        let text = "eg veit ikkje kva du meiner";
+        let languages = [Bokmal, English, Nynorsk];
+        let detector = LanguageDetectorBuilder::from_languages(&languages).build();
+        let detector_with_discriminator = LanguageDetectorBuilder::from_languages(&languages)
+            .with_pair_discriminators(models_directory.path())
+            .build();
+
+        let confidence_values = detector.compute_language_confidence_values(text);
+        let discriminated_confidence_values =
+            detector_with_discriminator.compute_language_confidence_values(text);
+
+        assert_eq!(confidence_values[0].0, Nynorsk);
+        assert_eq!(confidence_values[1].0, Bokmal);
+        assert_eq!(discriminated_confidence_values[0].0, Bokmal);
+        assert_eq!(discriminated_confidence_values[1].0, Nynorsk);
+        assert!(
+            (confidence_values[0].1 + confidence_values[1].1
+                - discriminated_confidence_values[0].1
+                - discriminated_confidence_values[1].1)
+                .abs()
+                < 1e-9
+        );
+        assert_eq!(confidence_values[2].0, discriminated_confidence_values[2].0);
+        assert!((confidence_values[2].1 - discriminated_confidence_values[2].1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn assert_macro_languages_are_collapsed() {
+        let text = "Jeg vet ikke hva du mener";
+        let detector = LanguageDetectorBuilder::from_languages(&[Bokmal, English, Nynorsk])
+            .with_macro_languages(&[MacroLanguage::Norwegian])
+            .build();
+
+        let confidence_values = detector.compute_language_confidence_values(text);
+        let collapsed_confidence_values =
+            detector.compute_collapsed_language_confidence_values(text);
+
+        assert_eq!(collapsed_confidence_values.len(), 2);
+        assert_eq!(
+            collapsed_confidence_values[0].0,
+            CollapsedLanguage::Macro(MacroLanguage::Norwegian)
+        );
+        assert_eq!(
+            collapsed_confidence_values[1].0,
+            CollapsedLanguage::Individual(English)
+        );
+
+        let norwegian_value: f64 = confidence_values
+            .iter()
+            .filter(|(language, _)| *language == Bokmal || *language == Nynorsk)
+            .map(|(_, value)| value)
+            .sum();
+
+        assert!((collapsed_confidence_values[0].1 - norwegian_value).abs() < 1e-9);
+        assert_eq!(
+            detector.detect_collapsed_language_of(text),
+            Some(CollapsedLanguage::Macro(MacroLanguage::Norwegian))
+        );
+    }
+
+    #[test]
+    fn assert_languages_are_not_collapsed_without_macro_languages() {
+        let text = "Jeg vet ikke hva du mener";
+        let detector = LanguageDetectorBuilder::from_languages(&[Bokmal, English, Nynorsk]).build();
+
+        let collapsed_languages = detector
+            .compute_collapsed_language_confidence_values(text)
+            .into_iter()
+            .map(|(language, _)| language)
+            .collect::<Vec<_>>();
+        let languages = detector
+            .compute_language_confidence_values(text)
+            .into_iter()
+            .map(|(language, _)| CollapsedLanguage::Individual(language))
+            .collect::<Vec<_>>();
+
+        assert_eq!(collapsed_languages, languages);
+    }
+
+    #[test]
+    fn assert_language_models_are_preloaded_in_background() {
+        let detector = LanguageDetectorBuilder::from_languages(&[English, German])
+            .with_preloaded_language_models_in_background()
+            .build();
+
+        assert_eq!(
+            detector.detect_language_of("languages are awesome"),
+            Some(English)
+        );
+
+        let mut progress = detector.preloading_progress().unwrap();
+        assert_eq!(progress.total_language_count(), 2);
+
+        while !progress.is_complete() {
+            std::thread::sleep(std::time::Duration::from_millis(10));
+            progress = detector.preloading_progress().unwrap();
+        }
+
+        assert_eq!(progress.loaded_language_count(), 2);
+        assert_eq!(
+            detector.detect_language_of("Sprachen sind großartig"),
+            Some(German)
+        );
+    }
+
+    #[test]
+    fn assert_preloading_progress_is_unavailable_without_preloading() {
+        let detector = LanguageDetectorBuilder::from_languages(&[English, German]).build();
+        assert_eq!(detector.preloading_progress(), None);
+    }
+
+    #[test]
+    fn assert_detector_is_restored_from_snapshot() {
+        let snapshot_file = NamedTempFile::new().unwrap();
+        let detector = LanguageDetectorBuilder::from_languages(&[English, French, German])
+            .with_minimum_relative_distance(0.1)
+            .with_normalizers(&[Normalizer::Nfkc, Normalizer::SquashElongation])
+            .with_macro_languages(&[MacroLanguage::Norwegian])
+            .build();
+
+        detector.write_snapshot(snapshot_file.path()).unwrap();
+
+        let restored_detector = LanguageDetector::from_snapshot(snapshot_file.path()).unwrap();
+
+        assert_eq!(restored_detector.languages, detector.languages);
+        assert_eq!(restored_detector.minimum_relative_distance, 0.1);
+        assert_eq!(
+            restored_detector.normalizers,
+            vec![Normalizer::Nfkc, Normalizer::SquashElongation]
+        );
+        assert_eq!(
+            restored_detector.macro_languages,
+            hashset!(MacroLanguage::Norwegian)
+        );
+        assert_eq!(
+            restored_detector.short_text_ngram_lengths,
+            detector.short_text_ngram_lengths
+        );
+        assert_eq!(
+            restored_detector.long_text_ngram_lengths,
+            detector.long_text_ngram_lengths
+        );
+        assert_eq!(
+            restored_detector.character_rules.unique_characters,
+            detector.character_rules.unique_characters
+        );
+
+        for text in [
+            "languages are awesome",
+            "les langues sont géniales",
+            "Sprachen sind großartig",
+        ] {
+            assert_eq!(
+                restored_detector.detect_language_of(text),
+                detector.detect_language_of(text)
+            );
+
+            let values = detector.compute_language_confidence_values(text);
+            let restored_values = restored_detector.compute_language_confidence_values(text);
+
+            for ((language, value), (restored_language, restored_value)) in
+                values.iter().zip(restored_values.iter())
+            {
+                assert_eq!(language, restored_language);
+                assert!((value - restored_value).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn assert_invalid_snapshot_is_rejected() {
+        let mut snapshot_file = NamedTempFile::new().unwrap();
+        snapshot_file.write_all(b"no snapshot").unwrap();
+
+        let error = LanguageDetector::from_snapshot(snapshot_file.path())
+            .err()
+            .unwrap();
+
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn assert_language_models_are_evicted_to_meet_memory_budget() {
+        let languages = [English, French, German];
+        let detector = LanguageDetectorBuilder::from_languages(&languages)
+            .with_accent_insensitive_mode()
+            .with_memory_budget(1)
+            .build();
+
+        for _ in 0..2 {
+            assert_eq!(
+                detector.detect_language_of("languages are awesome"),
+                Some(English)
+            );
+            assert_eq!(detector.model_cache.lock().unwrap().used_memory(), 0);
+
+            for models in detector.language_models {
+                let models = models.read().unwrap();
+                assert!(languages.iter().all(|language| !models.contains(language)));
+            }
+        }
+    }
+
+    #[test]
+    fn assert_memory_budget_only_evicts_language_models_of_its_own_detector() {
+        let other_languages = [Italian, Spanish];
+        let other_detector = LanguageDetectorBuilder::from_languages(&other_languages)
+            .with_accent_insensitive_mode()
+            .with_memory_budget(usize::MAX)
+            .with_preloaded_language_models()
+            .build();
+        let detector = LanguageDetectorBuilder::from_languages(&[English, French, German])
+            .with_accent_insensitive_mode()
+            .with_memory_budget(1)
+            .build();
+
+        assert_eq!(
+            detector.detect_language_of("languages are awesome"),
+            Some(English)
+        );
+        assert_eq!(detector.model_cache.lock().unwrap().used_memory(), 0);
+        assert!(other_detector.model_cache.lock().unwrap().used_memory() > 0);
+
+        for ngram_length in other_detector.used_ngram_lengths() {
+            let models = other_detector.language_models[ngram_length - 1]
+                .read()
+                .unwrap();
+            assert!(other_languages
+                .iter()
+                .all(|language| models.contains(language)));
+        }
+    }
+
+    #[test]
+    fn assert_memory_budget_evicts_shared_language_models_for_all_detectors() {
+        let text = "het weer is vandaag erg mooi";
+        let other_detector = LanguageDetectorBuilder::from_languages(&[Danish, Dutch])
+            .with_accent_insensitive_mode()
+            .with_memory_budget(usize::MAX)
+            .with_preloaded_language_models()
+            .build();
+        let detector = LanguageDetectorBuilder::from_languages(&[Dutch, Swedish])
+            .with_accent_insensitive_mode()
+            .with_memory_budget(1)
+            .build();
+
+        assert_eq!(detector.detect_language_of(text), Some(Dutch));
+
+        let trigram_models = other_detector.language_models[2].read().unwrap();
+        assert!(!trigram_models.contains(&Dutch));
+        assert!(trigram_models.contains(&Danish));
+        drop(trigram_models);
+
+        assert_eq!(other_detector.detect_language_of(text), Some(Dutch));
+        assert!(other_detector.language_models[2]
+            .read()
+            .unwrap()
+            .contains(&Dutch));
+    }
+
+    #[test]
+    fn assert_language_variant_is_detected() {
+        let models_directory = tempdir().unwrap();
+        let brazilian = LanguageVariant::new(Portuguese, "BR");
+        let european = LanguageVariant::new(Portuguese, "PT");
+
+        for (variant, text) in [
+            (
+                &brazilian,
+                "Eu peguei o ônibus e depois o trem. Meu celular ficou no ônibus.",
+            ),
+            (
+                &european,
+                "Eu apanhei o autocarro e depois o comboio. O meu telemóvel ficou no autocarro.",
+            ),
+        ] {
+            let mut input_file = NamedTempFile::new().unwrap();
+            input_file.write_all(text.as_bytes()).unwrap();
+            LanguageModelFilesWriter::new()
+                .with_maximum_ngram_length(3)
+                .write_language_variant_model_files(
+                    input_file.path(),
+                    models_directory.path(),
+                    variant,
+                    "\\p{L}",
+                )
+                .unwrap();
+        }
+
+        let detector = LanguageDetectorBuilder::from_languages(&[English, Portuguese])
+            .with_language_variants(models_directory.path())
+            .build();
+
+        assert_eq!(
+            detector.detect_language_variant_of("Meu celular está no ônibus."),
+            Some(brazilian.clone())
+        );
+        assert_eq!(
+            detector.detect_language_variant_of("O meu telemóvel está no autocarro."),
+            Some(european.clone())
+        );
+        assert_eq!(
+            detector.detect_language_variant_of("The bus is late again."),
+            None
+        );
+
+        let confidence_values =
+            detector.compute_language_variant_confidence_values("Meu celular está no ônibus.");
+
+        assert_eq!(confidence_values.len(), 2);
+        assert_eq!(confidence_values[0].0, brazilian);
+        assert!(
+            (confidence_values
+                .iter()
+                .map(|(_, value)| value)
+                .sum::<f64>()
+                - 1.0)
+                .abs()
+                < 1e-9
+        );
+    }
 }
@@ -0,0 +1,306 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::{BTreeMap, HashSet};
+use std::fs::read_dir;
+use std::path::Path;
+
+use ahash::AHashMap;
+use compact_str::CompactString;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::json::load_compressed_file_from_directory;
+use crate::language::Language;
+use crate::model::mark_word_boundaries;
+
+pub(crate) const PAIR_DISCRIMINATOR_LANGUAGES_MESSAGE: &str =
+    "A pair discriminator must be trained for two different languages";
+
+/// The number of passes over the training data.
+const EPOCHS: usize = 10;
+
+/// The step size of the stochastic gradient descent.
+const LEARNING_RATE: f64 = 0.1;
+
+/// Weights whose absolute value is smaller than this are not written to the model file.
+const MINIMUM_WEIGHT: f64 = 0.001;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct JsonPairDiscriminator {
+    languages: [Language; 2],
+    bias: f64,
+    weights: BTreeMap<String, f64>,
+}
+
+/// A logistic regression model which tells apart two closely related languages,
+/// such as Bokmal and Nynorsk, by the words and character trigrams of a text.
+///
+/// The model predicts the probability of the first of both languages.
+#[derive(Debug, PartialEq)]
+pub(crate) struct PairDiscriminator {
+    languages: [Language; 2],
+    bias: f64,
+    weights: AHashMap<CompactString, f64>,
+}
+
+impl PairDiscriminator {
+    /// Trains a discriminator from the lines of both languages, each given as
+    /// its lowercase words. The languages are stored in their natural order,
+    /// so that the file name of a pair does not depend on the argument order.
+    pub(crate) fn train(
+        first_language: Language,
+        first_lines: &[Vec<String>],
+        second_language: Language,
+        second_lines: &[Vec<String>],
+    ) -> Self {
+        if first_language == second_language {
+            panic!("{}", PAIR_DISCRIMINATOR_LANGUAGES_MESSAGE);
+        }
+
+        let (first_lines, second_lines) = if first_language < second_language {
+            (first_lines, second_lines)
+        } else {
+            (second_lines, first_lines)
+        };
+
+        // The lines of both languages are interleaved so that the gradient
+        // descent does not drift towards the language which comes last.
+        let samples = first_lines
+            .iter()
+            .map(|words| (extract_features(words), 1.0))
+            .interleave(
+                second_lines
+                    .iter()
+                    .map(|words| (extract_features(words), 0.0)),
+            )
+            .filter(|(features, _)| !features.is_empty())
+            .collect_vec();
+
+        let mut bias = 0.0;
+        let mut weights: AHashMap<CompactString, f64> = AHashMap::new();
+
+        for _ in 0..EPOCHS {
+            for (features, label) in samples.iter() {
+                let score = bias
+                    + features
+                        .iter()
+                        .map(|feature| weights.get(feature).copied().unwrap_or(0.0))
+                        .sum::<f64>();
+                let error = label - sigmoid(score);
+
+                bias += LEARNING_RATE * error;
+                for feature in features {
+                    *weights.entry(feature.clone()).or_insert(0.0) += LEARNING_RATE * error;
+                }
+            }
+        }
+
+        weights.retain(|_, weight| weight.abs() >= MINIMUM_WEIGHT);
+
+        Self {
+            languages: [
+                first_language.min(second_language),
+                first_language.max(second_language),
+            ],
+            bias,
+            weights,
+        }
+    }
+
+    /// Loads all discriminators from the given directory whose languages are both
+    /// contained in the given set of languages.
+    pub(crate) fn load_all(directory: &Path, languages: &HashSet<Language>) -> Vec<Self> {
+        let Ok(entries) = read_dir(directory) else {
+            return vec![];
+        };
+
+        entries
+            .filter_map(|entry| {
+                let file_name = entry.ok()?.file_name();
+                let file_name = file_name.to_str()?;
+                if !file_name.ends_with(".json.br") {
+                    return None;
+                }
+                let json = load_compressed_file_from_directory(directory, file_name).ok()?;
+                let discriminator = Self::from_json(&json)?;
+                if discriminator
+                    .languages
+                    .iter()
+                    .all(|language| languages.contains(language))
+                {
+                    Some(discriminator)
+                } else {
+                    None
+                }
+            })
+            .sorted_by_key(|discriminator| discriminator.languages)
+            .collect()
+    }
+
+    pub(crate) fn from_json(json: &str) -> Option<Self> {
+        let json_discriminator = serde_json::from_str::<JsonPairDiscriminator>(json).ok()?;
+        Some(Self {
+            languages: json_discriminator.languages,
+            bias: json_discriminator.bias,
+            weights: json_discriminator
+                .weights
+                .into_iter()
+                .map(|(feature, weight)| (CompactString::from(feature), weight))
+                .collect(),
+        })
+    }
+
+    pub(crate) fn to_json(&self) -> String {
+        let discriminator = JsonPairDiscriminator {
+            languages: self.languages,
+            bias: self.bias,
+            weights: self
+                .weights
+                .iter()
+                .map(|(feature, weight)| (feature.to_string(), *weight))
+                .collect(),
+        };
+        serde_json::to_string(&discriminator).unwrap()
+    }
+
+    /// Returns the name of the file in which the discriminator of the given
+    /// languages is stored, such as `nb-nn.json.br`.
+    pub(crate) fn file_name(first_language: Language, second_language: Language) -> String {
+        format!(
+            "{}-{}.json.br",
+            first_language.min(second_language).iso_code_639_1(),
+            first_language.max(second_language).iso_code_639_1()
+        )
+    }
+
+    /// Returns `true` if this discriminator tells apart the given languages.
+    pub(crate) fn discriminates(
+        &self,
+        first_language: Language,
+        second_language: Language,
+    ) -> bool {
+        self.languages == [first_language, second_language]
+            || self.languages == [second_language, first_language]
+    }
+
+    /// Returns the probability that the given words have been written in the given
+    /// language, which must be one of both languages of this discriminator.
+    pub(crate) fn probability_of(&self, language: Language, words: &[&str]) -> f64 {
+        let score = self.bias
+            + extract_features(words)
+                .iter()
+                .filter_map(|feature| self.weights.get(feature))
+                .sum::<f64>();
+        let probability = sigmoid(score);
+
+        if language == self.languages[0] {
+            probability
+        } else {
+            1.0 - probability
+        }
+    }
+}
+
+/// Extracts each word surrounded by boundary markers and all character
+/// trigrams of the marked word as features.
+fn extract_features(words: &[impl AsRef<str>]) -> Vec<CompactString> {
+    let mut features = vec![];
+
+    for word in words {
+        let marked_word = mark_word_boundaries(word.as_ref());
+        let chars = marked_word.chars().collect_vec();
+
+        for trigram in chars.windows(3) {
+            features.push(trigram.iter().collect());
+        }
+        features.push(CompactString::from(marked_word));
+    }
+
+    features
+}
+
+fn sigmoid(score: f64) -> f64 {
+    1.0 / (1.0 + (-score).exp())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::language::Language::{Bokmal, Nynorsk};
+
+    use super::*;
+
+    fn lines(text: &str) -> Vec<Vec<String>> {
+        text.lines()
+            .map(|line| line.split(' ').map(|word| word.to_string()).collect())
+            .collect()
+    }
+
+    fn words(text: &str) -> Vec<&str> {
+        text.split(' ').collect()
+    }
+
+    fn discriminator() -> PairDiscriminator {
+        PairDiscriminator::train(
+            Nynorsk,
+            &lines("eg er ikkje heime\nkva gjer du no\neg veit ikkje kva du meiner"),
+            Bokmal,
+            &lines("jeg er ikke hjemme\nhva gjør du nå\njeg vet ikke hva du mener"),
+        )
+    }
+
+    #[test]
+    fn assert_features_are_extracted_correctly() {
+        assert_eq!(
+            extract_features(&words("eg er")),
+            vec!["<eg", "eg>", "<eg>", "<er", "er>", "<er>"]
+        );
+    }
+
+    #[test]
+    fn assert_discriminator_tells_apart_both_languages() {
+        let discriminator = discriminator();
+
+        assert_eq!(discriminator.languages, [Bokmal, Nynorsk]);
+        assert!(discriminator.discriminates(Nynorsk, Bokmal));
+        assert!(discriminator.probability_of(Nynorsk, &words("eg veit ikkje")) > 0.8);
+        assert!(discriminator.probability_of(Bokmal, &words("jeg vet ikke")) > 0.8);
+
+        let probability = discriminator.probability_of(Bokmal, &words("du"));
+        assert!(
+            (probability + discriminator.probability_of(Nynorsk, &words("du")) - 1.0).abs() < 1e-9
+        );
+    }
+
+    #[test]
+    fn assert_discriminator_survives_serialization() {
+        let discriminator = discriminator();
+        assert_eq!(
+            PairDiscriminator::from_json(&discriminator.to_json()),
+            Some(discriminator)
+        );
+        assert_eq!(
+            PairDiscriminator::file_name(Nynorsk, Bokmal),
+            "nb-nn.json.br"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "A pair discriminator must be trained for two different languages")]
+    fn assert_discriminator_for_identical_languages_cannot_be_trained() {
+        PairDiscriminator::train(Bokmal, &lines("jeg"), Bokmal, &lines("jeg"));
+    }
+}
@@ -0,0 +1,216 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::alphabet::Alphabet;
+use crate::language::Language;
+
+/// Converts the given text to lowercase in a way that is consistent for Turkic languages.
+///
+/// The standard lowercase mapping turns the Turkish and Azerbaijani dotted capital `İ`
+/// into `i` followed by a combining dot above, which splits words in two during detection
+/// and drops ngrams during training. Here, `İ` is mapped to a plain `i` and combining dots
+/// above following an `i` or `ı` result in a plain `i`. The dotless `ı` is preserved, and
+/// the capital `I` is mapped to `i`. Text of a known language is converted with
+/// [`fold_case_for`] instead, which maps the capital `I` of Turkic languages to `ı`.
+pub(crate) fn fold_case(text: &str) -> String {
+    let mut folded_text = String::with_capacity(text.len());
+    fold_case_into(text, &mut folded_text);
+    folded_text
+}
+
+/// Converts the given text of the given language to lowercase like [`fold_case`],
+/// except that the capital `I` is mapped to the dotless `ı` in Turkish and Azerbaijani.
+pub(crate) fn fold_case_for(text: &str, language: &Language) -> String {
+    if !has_dotless_capital_i(language) {
+        return fold_case(text);
+    }
+    let mut folded_text = String::with_capacity(text.len());
+    fold_turkic_case_into(text, &mut folded_text);
+    folded_text
+}
+
+/// Appends the given text converted by [`fold_case`] to the given buffer,
+/// so that a buffer can be reused for many texts.
+pub(crate) fn fold_case_into(text: &str, folded_text: &mut String) {
+    fold_case_with(text, 'i', folded_text);
+}
+
+/// Appends the given text converted by [`fold_case_for`] for a Turkic language
+/// to the given buffer.
+pub(crate) fn fold_turkic_case_into(text: &str, folded_text: &mut String) {
+    fold_case_with(text, 'ı', folded_text);
+}
+
+/// Returns `true` if the capital `I` is the uppercase form of the dotless `ı`
+/// in the given language, whose dotted `i` is capitalized as `İ` instead.
+pub(crate) fn has_dotless_capital_i(language: &Language) -> bool {
+    match language {
+        #[cfg(feature = "azerbaijani")]
+        Language::Azerbaijani => true,
+
+        #[cfg(feature = "turkish")]
+        Language::Turkish => true,
+
+        _ => false,
+    }
+}
+
+fn fold_case_with(text: &str, lowercase_i: char, folded_text: &mut String) {
+    let start = folded_text.len();
+    let mut push = |ch: char| {
+        if ch != '\u{307}' {
+            folded_text.push(ch);
+        } else if folded_text[start..].ends_with('ı') {
+            folded_text.pop();
+            folded_text.push('i');
+        } else if !folded_text[start..].ends_with('i') {
+            folded_text.push(ch);
+        }
+    };
+
+    // The lowercase form of the capital sigma depends on its position in a word,
+    // which only the lowercase mapping of whole strings takes into account.
+    if text.contains('Σ') {
+        text.replace('İ', "i")
+            .replace('I', lowercase_i.encode_utf8(&mut [0; 4]))
+            .to_lowercase()
+            .chars()
+            .for_each(push);
+    } else {
+        text.chars().for_each(|ch| match ch {
+            'İ' => push('i'),
+            'I' => push(lowercase_i),
+            _ => ch.to_lowercase().for_each(&mut push),
+        });
+    }
+}
+
+/// Removes diacritical marks from all Latin and Greek characters of the given text.
+///
+/// Every character is mapped to exactly one character, so the character count
+/// of the folded text is always the same as the one of the original text.
+/// Characters of other alphabets are left untouched because their combining
+/// marks (such as the vowel signs of Indic scripts) are not optional.
+pub(crate) fn fold_diacritics(text: &str) -> String {
+    text.chars().map(fold_diacritic).collect()
+}
+
+fn fold_diacritic(ch: char) -> char {
+    match ch {
+        'Đ' => 'D',
+        'đ' => 'd',
+        'Ħ' => 'H',
+        'ħ' => 'h',
+        'ı' => 'i',
+        'Ŀ' | 'Ł' => 'L',
+        'ŀ' | 'ł' => 'l',
+        'Ø' => 'O',
+        'ø' => 'o',
+        'Ŧ' => 'T',
+        'ŧ' => 't',
+        _ if Alphabet::Latin.matches_char(ch) || Alphabet::Greek.matches_char(ch) => {
+            ch.nfd().find(|it| !is_combining_mark(*it)).unwrap_or(ch)
+        }
+        _ => ch,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+
+    #[rstest(
+        text,
+        expected_text,
+        case("İSTANBUL", "istanbul"),
+        case("İstanbul", "istanbul"),
+        case("I\u{307}stanbul", "istanbul"),
+        case("i\u{307}stanbul", "istanbul"),
+        case("IŞIK ışık", "işik ışık"),
+        case("Əli İlham", "əli ilham"),
+        case("Größe", "größe"),
+        case("ΟΔΥΣΣΕΥΣ", "οδυσσευς")
+    )]
+    fn test_fold_case(text: &str, expected_text: &str) {
+        assert_eq!(fold_case(text), expected_text);
+    }
+
+    #[rstest(
+        text,
+        expected_text,
+        case("I", "ı"),
+        case("İ", "i"),
+        case("IŞIK", "ışık"),
+        case("ışık", "ışık"),
+        case("İSTANBUL", "istanbul"),
+        case("I\u{307}STANBUL", "istanbul"),
+        case("IŞIK ΟΔΥΣΣΕΥΣ", "ışık οδυσσευς")
+    )]
+    fn test_fold_turkic_case(text: &str, expected_text: &str) {
+        let mut folded_text = String::new();
+        fold_turkic_case_into(text, &mut folded_text);
+        assert_eq!(folded_text, expected_text);
+    }
+
+    #[cfg(all(feature = "english", feature = "turkish"))]
+    #[test]
+    fn assert_capital_i_is_folded_according_to_language() {
+        use crate::language::Language::{English, Turkish};
+
+        assert_eq!(
+            fold_case_for("IŞIK", &Turkish),
+            fold_case_for("ışık", &Turkish)
+        );
+        assert_eq!(fold_case_for("IŞIK", &Turkish), "ışık");
+        assert_eq!(fold_case_for("IŞIK", &English), "işik");
+        assert_eq!(fold_case_for("LIGHT", &English), "light");
+    }
+
+    #[test]
+    fn assert_folded_text_is_appended_to_buffer() {
+        let mut buffer = String::from("Ti");
+        fold_case_into("\u{307}İZMİR", &mut buffer);
+        assert_eq!(buffer, "Ti\u{307}izmir");
+    }
+
+    #[rstest(
+        text,
+        expected_text,
+        case("ça va bien", "ca va bien"),
+        case("não sei", "nao sei"),
+        case("łódź", "lodz"),
+        case("Ærøskøbing", "Æroskobing"),
+        case("mặn nguồn", "man nguon"),
+        case("ελληνικά", "ελληνικα"),
+        case("groß", "groß"),
+        case("йогурт", "йогурт"),
+        case("किताब", "किताब")
+    )]
+    fn test_fold_diacritics(text: &str, expected_text: &str) {
+        assert_eq!(fold_diacritics(text), expected_text);
+    }
+
+    #[test]
+    fn assert_folding_preserves_character_count() {
+        let text = "Příliš žluťoučký kůň úpěl ďábelské ódy";
+        assert_eq!(fold_diacritics(text).chars().count(), text.chars().count());
+    }
+}
@@ -15,9 +15,13 @@
  */
 
 use std::fmt::{Debug, Display, Formatter, Result};
+use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
-use strum_macros::{EnumIter, EnumString};
+use strum::{IntoEnumIterator, ParseError};
+use strum_macros::EnumIter;
+
+use crate::custom::CustomLanguage;
 
 /// This enum specifies the ISO 639-1 code representations for the supported languages.
 ///
@@ -29,7 +33,6 @@ use strum_macros::{EnumIter, EnumString};
     Serialize,
     Deserialize,
     EnumIter,
-    EnumString,
     Eq,
     PartialEq,
     Hash,
@@ -37,8 +40,7 @@ use strum_macros::{EnumIter, EnumString};
     PartialOrd,
 )]
 #[allow(clippy::upper_case_acronyms)]
-#[strum(ascii_case_insensitive)]
-#[cfg_attr(feature = "python", pyo3::prelude::pyclass(eq, eq_int, frozen, hash, ord))]
+#[non_exhaustive]
 pub enum IsoCode639_1 {
     #[cfg(feature = "afrikaans")]
     /// The ISO 639-1 code for [`Afrikaans`](crate::language::Language::Afrikaans)
@@ -339,6 +341,12 @@ pub enum IsoCode639_1 {
     #[cfg(feature = "zulu")]
     /// The ISO 639-1 code for [`Zulu`](crate::language::Language::Zulu)
     ZU,
+
+    /// The ISO 639-1 code of a language registered with
+    /// [`Language::register_custom`](crate::language::Language::register_custom)
+    #[strum(disabled)]
+    #[serde(rename = "CUSTOM")]
+    Custom(CustomLanguage),
 }
 
 /// This enum specifies the ISO 639-3 code representations for the supported languages.
@@ -351,7 +359,6 @@ pub enum IsoCode639_1 {
     Serialize,
     Deserialize,
     EnumIter,
-    EnumString,
     Eq,
     PartialEq,
     Hash,
@@ -359,8 +366,7 @@ pub enum IsoCode639_1 {
     PartialOrd,
 )]
 #[allow(clippy::upper_case_acronyms)]
-#[strum(ascii_case_insensitive)]
-#[cfg_attr(feature = "python", pyo3::prelude::pyclass(eq, eq_int, frozen, hash, ord))]
+#[non_exhaustive]
 pub enum IsoCode639_3 {
     #[cfg(feature = "afrikaans")]
     /// The ISO 639-3 code for [`Afrikaans`](crate::language::Language::Afrikaans)
@@ -661,19 +667,57 @@ pub enum IsoCode639_3 {
     #[cfg(feature = "zulu")]
     /// The ISO 639-3 code for [`Zulu`](crate::language::Language::Zulu)
     ZUL,
+
+    /// The ISO 639-3 code of a language registered with
+    /// [`Language::register_custom`](crate::language::Language::register_custom)
+    #[strum(disabled)]
+    #[serde(rename = "CUSTOM")]
+    Custom(CustomLanguage),
 }
 
 impl Display for IsoCode639_1 {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        let debug_repr = format!("{self:?}");
-        write!(f, "{}", debug_repr.to_lowercase())
+        match self {
+            IsoCode639_1::Custom(language) => write!(f, "{}", language.iso_code_639_1()),
+            _ => {
+                let debug_repr = format!("{self:?}");
+                write!(f, "{}", debug_repr.to_lowercase())
+            }
+        }
     }
 }
 
 impl Display for IsoCode639_3 {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        let debug_repr = format!("{self:?}");
-        write!(f, "{}", debug_repr.to_lowercase())
+        match self {
+            IsoCode639_3::Custom(language) => write!(f, "{}", language.iso_code_639_3()),
+            _ => {
+                let debug_repr = format!("{self:?}");
+                write!(f, "{}", debug_repr.to_lowercase())
+            }
+        }
+    }
+}
+
+impl FromStr for IsoCode639_1 {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        IsoCode639_1::iter()
+            .find(|iso_code| iso_code.to_string().eq_ignore_ascii_case(s))
+            .or_else(|| CustomLanguage::find_by_iso_code_639_1(s).map(IsoCode639_1::Custom))
+            .ok_or(ParseError::VariantNotFound)
+    }
+}
+
+impl FromStr for IsoCode639_3 {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        IsoCode639_3::iter()
+            .find(|iso_code| iso_code.to_string().eq_ignore_ascii_case(s))
+            .or_else(|| CustomLanguage::find_by_iso_code_639_3(s).map(IsoCode639_3::Custom))
+            .ok_or(ParseError::VariantNotFound)
     }
 }
 
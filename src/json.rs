@@ -14,177 +14,401 @@
  * limitations under the License.
  */
 
+#[cfg(feature = "external-models")]
+use std::collections::HashSet;
+use std::fs;
 use std::io::{Cursor, ErrorKind, Read};
+use std::path::Path;
+#[cfg(feature = "external-models")]
+use std::path::PathBuf;
+#[cfg(feature = "external-models")]
+use std::sync::RwLock;
 
 use brotli::Decompressor;
+#[cfg(not(feature = "external-models"))]
 use include_dir::Dir;
+#[cfg(feature = "external-models")]
+use itertools::Itertools;
 
-#[cfg(feature = "afrikaans")]
+#[cfg(all(feature = "afrikaans", not(feature = "external-models")))]
 use lingua_afrikaans_language_model::AFRIKAANS_MODELS_DIRECTORY;
-#[cfg(feature = "albanian")]
+#[cfg(all(feature = "albanian", not(feature = "external-models")))]
 use lingua_albanian_language_model::ALBANIAN_MODELS_DIRECTORY;
-#[cfg(feature = "arabic")]
+#[cfg(all(feature = "arabic", not(feature = "external-models")))]
 use lingua_arabic_language_model::ARABIC_MODELS_DIRECTORY;
-#[cfg(feature = "armenian")]
+#[cfg(all(feature = "armenian", not(feature = "external-models")))]
 use lingua_armenian_language_model::ARMENIAN_MODELS_DIRECTORY;
-#[cfg(feature = "azerbaijani")]
+#[cfg(all(feature = "azerbaijani", not(feature = "external-models")))]
 use lingua_azerbaijani_language_model::AZERBAIJANI_MODELS_DIRECTORY;
-#[cfg(feature = "basque")]
+#[cfg(all(feature = "basque", not(feature = "external-models")))]
 use lingua_basque_language_model::BASQUE_MODELS_DIRECTORY;
-#[cfg(feature = "belarusian")]
+#[cfg(all(feature = "belarusian", not(feature = "external-models")))]
 use lingua_belarusian_language_model::BELARUSIAN_MODELS_DIRECTORY;
-#[cfg(feature = "bengali")]
+#[cfg(all(feature = "bengali", not(feature = "external-models")))]
 use lingua_bengali_language_model::BENGALI_MODELS_DIRECTORY;
-#[cfg(feature = "bokmal")]
+#[cfg(all(feature = "bokmal", not(feature = "external-models")))]
 use lingua_bokmal_language_model::BOKMAL_MODELS_DIRECTORY;
-#[cfg(feature = "bosnian")]
+#[cfg(all(feature = "bosnian", not(feature = "external-models")))]
 use lingua_bosnian_language_model::BOSNIAN_MODELS_DIRECTORY;
-#[cfg(feature = "bulgarian")]
+#[cfg(all(feature = "bulgarian", not(feature = "external-models")))]
 use lingua_bulgarian_language_model::BULGARIAN_MODELS_DIRECTORY;
-#[cfg(feature = "catalan")]
+#[cfg(all(feature = "catalan", not(feature = "external-models")))]
 use lingua_catalan_language_model::CATALAN_MODELS_DIRECTORY;
-#[cfg(feature = "chinese")]
+#[cfg(all(feature = "chinese", not(feature = "external-models")))]
 use lingua_chinese_language_model::CHINESE_MODELS_DIRECTORY;
-#[cfg(feature = "croatian")]
+#[cfg(all(feature = "croatian", not(feature = "external-models")))]
 use lingua_croatian_language_model::CROATIAN_MODELS_DIRECTORY;
-#[cfg(feature = "czech")]
+#[cfg(all(feature = "czech", not(feature = "external-models")))]
 use lingua_czech_language_model::CZECH_MODELS_DIRECTORY;
-#[cfg(feature = "danish")]
+#[cfg(all(feature = "danish", not(feature = "external-models")))]
 use lingua_danish_language_model::DANISH_MODELS_DIRECTORY;
-#[cfg(feature = "dutch")]
+#[cfg(all(feature = "dutch", not(feature = "external-models")))]
 use lingua_dutch_language_model::DUTCH_MODELS_DIRECTORY;
-#[cfg(feature = "english")]
+#[cfg(all(feature = "english", not(feature = "external-models")))]
 use lingua_english_language_model::ENGLISH_MODELS_DIRECTORY;
-#[cfg(feature = "esperanto")]
+#[cfg(all(feature = "esperanto", not(feature = "external-models")))]
 use lingua_esperanto_language_model::ESPERANTO_MODELS_DIRECTORY;
-#[cfg(feature = "estonian")]
+#[cfg(all(feature = "estonian", not(feature = "external-models")))]
 use lingua_estonian_language_model::ESTONIAN_MODELS_DIRECTORY;
-#[cfg(feature = "finnish")]
+#[cfg(all(feature = "finnish", not(feature = "external-models")))]
 use lingua_finnish_language_model::FINNISH_MODELS_DIRECTORY;
-#[cfg(feature = "french")]
+#[cfg(all(feature = "french", not(feature = "external-models")))]
 use lingua_french_language_model::FRENCH_MODELS_DIRECTORY;
-#[cfg(feature = "ganda")]
+#[cfg(all(feature = "ganda", not(feature = "external-models")))]
 use lingua_ganda_language_model::GANDA_MODELS_DIRECTORY;
-#[cfg(feature = "georgian")]
+#[cfg(all(feature = "georgian", not(feature = "external-models")))]
 use lingua_georgian_language_model::GEORGIAN_MODELS_DIRECTORY;
-#[cfg(feature = "german")]
+#[cfg(all(feature = "german", not(feature = "external-models")))]
 use lingua_german_language_model::GERMAN_MODELS_DIRECTORY;
-#[cfg(feature = "greek")]
+#[cfg(all(feature = "greek", not(feature = "external-models")))]
 use lingua_greek_language_model::GREEK_MODELS_DIRECTORY;
-#[cfg(feature = "gujarati")]
+#[cfg(all(feature = "gujarati", not(feature = "external-models")))]
 use lingua_gujarati_language_model::GUJARATI_MODELS_DIRECTORY;
-#[cfg(feature = "hebrew")]
+#[cfg(all(feature = "hebrew", not(feature = "external-models")))]
 use lingua_hebrew_language_model::HEBREW_MODELS_DIRECTORY;
-#[cfg(feature = "hindi")]
+#[cfg(all(feature = "hindi", not(feature = "external-models")))]
 use lingua_hindi_language_model::HINDI_MODELS_DIRECTORY;
-#[cfg(feature = "hungarian")]
+#[cfg(all(feature = "hungarian", not(feature = "external-models")))]
 use lingua_hungarian_language_model::HUNGARIAN_MODELS_DIRECTORY;
-#[cfg(feature = "icelandic")]
+#[cfg(all(feature = "icelandic", not(feature = "external-models")))]
 use lingua_icelandic_language_model::ICELANDIC_MODELS_DIRECTORY;
-#[cfg(feature = "indonesian")]
+#[cfg(all(feature = "indonesian", not(feature = "external-models")))]
 use lingua_indonesian_language_model::INDONESIAN_MODELS_DIRECTORY;
-#[cfg(feature = "irish")]
+#[cfg(all(feature = "irish", not(feature = "external-models")))]
 use lingua_irish_language_model::IRISH_MODELS_DIRECTORY;
-#[cfg(feature = "italian")]
+#[cfg(all(feature = "italian", not(feature = "external-models")))]
 use lingua_italian_language_model::ITALIAN_MODELS_DIRECTORY;
-#[cfg(feature = "japanese")]
+#[cfg(all(feature = "japanese", not(feature = "external-models")))]
 use lingua_japanese_language_model::JAPANESE_MODELS_DIRECTORY;
-#[cfg(feature = "kazakh")]
+#[cfg(all(feature = "kazakh", not(feature = "external-models")))]
 use lingua_kazakh_language_model::KAZAKH_MODELS_DIRECTORY;
-#[cfg(feature = "korean")]
+#[cfg(all(feature = "korean", not(feature = "external-models")))]
 use lingua_korean_language_model::KOREAN_MODELS_DIRECTORY;
-#[cfg(feature = "latin")]
+#[cfg(all(feature = "latin", not(feature = "external-models")))]
 use lingua_latin_language_model::LATIN_MODELS_DIRECTORY;
-#[cfg(feature = "latvian")]
+#[cfg(all(feature = "latvian", not(feature = "external-models")))]
 use lingua_latvian_language_model::LATVIAN_MODELS_DIRECTORY;
-#[cfg(feature = "lithuanian")]
+#[cfg(all(feature = "lithuanian", not(feature = "external-models")))]
 use lingua_lithuanian_language_model::LITHUANIAN_MODELS_DIRECTORY;
-#[cfg(feature = "macedonian")]
+#[cfg(all(feature = "macedonian", not(feature = "external-models")))]
 use lingua_macedonian_language_model::MACEDONIAN_MODELS_DIRECTORY;
-#[cfg(feature = "malay")]
+#[cfg(all(feature = "malay", not(feature = "external-models")))]
 use lingua_malay_language_model::MALAY_MODELS_DIRECTORY;
-#[cfg(feature = "maori")]
+#[cfg(all(feature = "maori", not(feature = "external-models")))]
 use lingua_maori_language_model::MAORI_MODELS_DIRECTORY;
-#[cfg(feature = "marathi")]
+#[cfg(all(feature = "marathi", not(feature = "external-models")))]
 use lingua_marathi_language_model::MARATHI_MODELS_DIRECTORY;
-#[cfg(feature = "mongolian")]
+#[cfg(all(feature = "mongolian", not(feature = "external-models")))]
 use lingua_mongolian_language_model::MONGOLIAN_MODELS_DIRECTORY;
-#[cfg(feature = "nynorsk")]
+#[cfg(all(feature = "nynorsk", not(feature = "external-models")))]
 use lingua_nynorsk_language_model::NYNORSK_MODELS_DIRECTORY;
-#[cfg(feature = "persian")]
+#[cfg(all(feature = "persian", not(feature = "external-models")))]
 use lingua_persian_language_model::PERSIAN_MODELS_DIRECTORY;
-#[cfg(feature = "polish")]
+#[cfg(all(feature = "polish", not(feature = "external-models")))]
 use lingua_polish_language_model::POLISH_MODELS_DIRECTORY;
-#[cfg(feature = "portuguese")]
+#[cfg(all(feature = "portuguese", not(feature = "external-models")))]
 use lingua_portuguese_language_model::PORTUGUESE_MODELS_DIRECTORY;
-#[cfg(feature = "punjabi")]
+#[cfg(all(feature = "punjabi", not(feature = "external-models")))]
 use lingua_punjabi_language_model::PUNJABI_MODELS_DIRECTORY;
-#[cfg(feature = "romanian")]
+#[cfg(all(feature = "romanian", not(feature = "external-models")))]
 use lingua_romanian_language_model::ROMANIAN_MODELS_DIRECTORY;
-#[cfg(feature = "russian")]
+#[cfg(all(feature = "russian", not(feature = "external-models")))]
 use lingua_russian_language_model::RUSSIAN_MODELS_DIRECTORY;
-#[cfg(feature = "serbian")]
+#[cfg(all(feature = "serbian", not(feature = "external-models")))]
 use lingua_serbian_language_model::SERBIAN_MODELS_DIRECTORY;
-#[cfg(feature = "shona")]
+#[cfg(all(feature = "shona", not(feature = "external-models")))]
 use lingua_shona_language_model::SHONA_MODELS_DIRECTORY;
-#[cfg(feature = "slovak")]
+#[cfg(all(feature = "slovak", not(feature = "external-models")))]
 use lingua_slovak_language_model::SLOVAK_MODELS_DIRECTORY;
-#[cfg(feature = "slovene")]
+#[cfg(all(feature = "slovene", not(feature = "external-models")))]
 use lingua_slovene_language_model::SLOVENE_MODELS_DIRECTORY;
-#[cfg(feature = "somali")]
+#[cfg(all(feature = "somali", not(feature = "external-models")))]
 use lingua_somali_language_model::SOMALI_MODELS_DIRECTORY;
-#[cfg(feature = "sotho")]
+#[cfg(all(feature = "sotho", not(feature = "external-models")))]
 use lingua_sotho_language_model::SOTHO_MODELS_DIRECTORY;
-#[cfg(feature = "spanish")]
+#[cfg(all(feature = "spanish", not(feature = "external-models")))]
 use lingua_spanish_language_model::SPANISH_MODELS_DIRECTORY;
-#[cfg(feature = "swahili")]
+#[cfg(all(feature = "swahili", not(feature = "external-models")))]
 use lingua_swahili_language_model::SWAHILI_MODELS_DIRECTORY;
-#[cfg(feature = "swedish")]
+#[cfg(all(feature = "swedish", not(feature = "external-models")))]
 use lingua_swedish_language_model::SWEDISH_MODELS_DIRECTORY;
-#[cfg(feature = "tagalog")]
+#[cfg(all(feature = "tagalog", not(feature = "external-models")))]
 use lingua_tagalog_language_model::TAGALOG_MODELS_DIRECTORY;
-#[cfg(feature = "tamil")]
+#[cfg(all(feature = "tamil", not(feature = "external-models")))]
 use lingua_tamil_language_model::TAMIL_MODELS_DIRECTORY;
-#[cfg(feature = "telugu")]
+#[cfg(all(feature = "telugu", not(feature = "external-models")))]
 use lingua_telugu_language_model::TELUGU_MODELS_DIRECTORY;
-#[cfg(feature = "thai")]
+#[cfg(all(feature = "thai", not(feature = "external-models")))]
 use lingua_thai_language_model::THAI_MODELS_DIRECTORY;
-#[cfg(feature = "tsonga")]
+#[cfg(all(feature = "tsonga", not(feature = "external-models")))]
 use lingua_tsonga_language_model::TSONGA_MODELS_DIRECTORY;
-#[cfg(feature = "tswana")]
+#[cfg(all(feature = "tswana", not(feature = "external-models")))]
 use lingua_tswana_language_model::TSWANA_MODELS_DIRECTORY;
-#[cfg(feature = "turkish")]
+#[cfg(all(feature = "turkish", not(feature = "external-models")))]
 use lingua_turkish_language_model::TURKISH_MODELS_DIRECTORY;
-#[cfg(feature = "ukrainian")]
+#[cfg(all(feature = "ukrainian", not(feature = "external-models")))]
 use lingua_ukrainian_language_model::UKRAINIAN_MODELS_DIRECTORY;
-#[cfg(feature = "urdu")]
+#[cfg(all(feature = "urdu", not(feature = "external-models")))]
 use lingua_urdu_language_model::URDU_MODELS_DIRECTORY;
-#[cfg(feature = "vietnamese")]
+#[cfg(all(feature = "vietnamese", not(feature = "external-models")))]
 use lingua_vietnamese_language_model::VIETNAMESE_MODELS_DIRECTORY;
-#[cfg(feature = "welsh")]
+#[cfg(all(feature = "welsh", not(feature = "external-models")))]
 use lingua_welsh_language_model::WELSH_MODELS_DIRECTORY;
-#[cfg(feature = "xhosa")]
+#[cfg(all(feature = "xhosa", not(feature = "external-models")))]
 use lingua_xhosa_language_model::XHOSA_MODELS_DIRECTORY;
-#[cfg(feature = "yoruba")]
+#[cfg(all(feature = "yoruba", not(feature = "external-models")))]
 use lingua_yoruba_language_model::YORUBA_MODELS_DIRECTORY;
-#[cfg(feature = "zulu")]
+#[cfg(all(feature = "zulu", not(feature = "external-models")))]
 use lingua_zulu_language_model::ZULU_MODELS_DIRECTORY;
 
 use crate::ngram::Ngram;
 use crate::Language;
 
+/// The file name prefix of language models whose ngrams include word boundary markers.
+pub(crate) const WORD_BOUNDARY_MODEL_PREFIX: &str = "word-boundary-";
+
 pub(crate) fn load_json(language: Language, ngram_length: usize) -> std::io::Result<String> {
     let ngram_name = Ngram::find_ngram_name_by_length(ngram_length);
-    let file_path = format!("{ngram_name}s.json.br");
-    let directory = get_language_models_directory(language);
-    let compressed_file = directory.get_file(file_path).ok_or(ErrorKind::NotFound)?;
-    let compressed_file_reader = Cursor::new(compressed_file.contents());
+    load_compressed_file(language, &format!("{ngram_name}s.json.br"))
+}
+
+pub(crate) fn load_word_boundary_json(
+    language: Language,
+    ngram_length: usize,
+) -> std::io::Result<String> {
+    let ngram_name = Ngram::find_ngram_name_by_length(ngram_length);
+    load_compressed_file(
+        language,
+        &format!("{WORD_BOUNDARY_MODEL_PREFIX}{ngram_name}s.json.br"),
+    )
+}
+
+/// The file name of the models which contain the relative frequencies of whole words.
+pub(crate) const WORD_MODEL_FILE_NAME: &str = "words.json.br";
+
+pub(crate) fn load_word_json(language: Language) -> std::io::Result<String> {
+    load_compressed_file(language, WORD_MODEL_FILE_NAME)
+}
+
+pub(crate) fn has_word_model(language: Language) -> bool {
+    has_model_file(language, WORD_MODEL_FILE_NAME)
+}
+
+/// Loads the absolute ngram frequencies which the smoothing schemes are computed from.
+pub(crate) fn load_counts_json(
+    language: Language,
+    ngram_length: usize,
+    is_word_boundary_mode_enabled: bool,
+) -> std::io::Result<String> {
+    load_compressed_file(
+        language,
+        &counts_file_name(ngram_length, is_word_boundary_mode_enabled),
+    )
+}
+
+pub(crate) fn has_ngram_counts(
+    language: Language,
+    maximum_ngram_length: usize,
+    is_word_boundary_mode_enabled: bool,
+) -> bool {
+    (1..=maximum_ngram_length).all(|ngram_length| {
+        has_model_file(
+            language,
+            &counts_file_name(ngram_length, is_word_boundary_mode_enabled),
+        )
+    })
+}
+
+pub(crate) fn counts_file_name(ngram_length: usize, is_word_boundary_mode_enabled: bool) -> String {
+    let ngram_name = Ngram::find_ngram_name_by_length(ngram_length);
+    let prefix = if is_word_boundary_mode_enabled {
+        WORD_BOUNDARY_MODEL_PREFIX
+    } else {
+        ""
+    };
+    format!("{prefix}{ngram_name}-counts.json.br")
+}
+
+pub(crate) fn has_word_boundary_models(language: Language, maximum_ngram_length: usize) -> bool {
+    (1..=maximum_ngram_length).all(|ngram_length| {
+        let ngram_name = Ngram::find_ngram_name_by_length(ngram_length);
+        has_model_file(
+            language,
+            &format!("{WORD_BOUNDARY_MODEL_PREFIX}{ngram_name}s.json.br"),
+        )
+    })
+}
+
+/// The ISO 639-1 codes of the built-in languages whose model crates contain unigram models
+/// only, as their texts are identified by their characters.
+const UNIGRAM_ONLY_LANGUAGES: [&str; 3] = ["ja", "ko", "zh"];
+
+pub(crate) fn has_unigram_model_only(language: Language) -> bool {
+    !matches!(language, Language::Custom(_))
+        && UNIGRAM_ONLY_LANGUAGES.contains(&language.iso_code_639_1().to_string().as_str())
+}
+
+/// Custom languages are read from the file system, all other languages
+/// from the directories embedded by the language model crates.
+fn has_model_file(language: Language, file_path: &str) -> bool {
+    match language {
+        Language::Custom(language) => language.models_directory().join(file_path).is_file(),
+        _ => has_built_in_model_file(language, file_path),
+    }
+}
+
+#[cfg(not(feature = "external-models"))]
+fn has_built_in_model_file(language: Language, file_path: &str) -> bool {
+    get_language_models_directory(language)
+        .get_file(file_path)
+        .is_some()
+}
+
+#[cfg(feature = "external-models")]
+fn has_built_in_model_file(language: Language, file_path: &str) -> bool {
+    external_language_models_directory(language)
+        .is_some_and(|directory| directory.join(file_path).is_file())
+}
+
+/// Loads a compressed model file from a directory of the file system,
+/// such as the models of regional language variants.
+pub(crate) fn load_compressed_file_from_directory(
+    directory: &Path,
+    file_path: &str,
+) -> std::io::Result<String> {
+    decompress(&fs::read(directory.join(file_path))?)
+}
+
+fn load_compressed_file(language: Language, file_path: &str) -> std::io::Result<String> {
+    match language {
+        Language::Custom(language) => {
+            load_compressed_file_from_directory(language.models_directory(), file_path)
+        }
+        _ => load_built_in_compressed_file(language, file_path),
+    }
+}
+
+#[cfg(not(feature = "external-models"))]
+fn load_built_in_compressed_file(language: Language, file_path: &str) -> std::io::Result<String> {
+    decompress(
+        get_language_models_directory(language)
+            .get_file(file_path)
+            .ok_or(ErrorKind::NotFound)?
+            .contents(),
+    )
+}
+
+#[cfg(feature = "external-models")]
+fn load_built_in_compressed_file(language: Language, file_path: &str) -> std::io::Result<String> {
+    let directory = external_language_models_directory(language).ok_or_else(|| {
+        std::io::Error::new(ErrorKind::NotFound, MISSING_MODELS_DIRECTORY_MESSAGE)
+    })?;
+    let file = directory.join(file_path);
+    let compressed_file_content = fs::read(&file).map_err(|error| {
+        std::io::Error::new(
+            error.kind(),
+            format!(
+                "Language model file '{}' cannot be read: {error}",
+                file.display()
+            ),
+        )
+    })?;
+    decompress(&compressed_file_content)
+}
+
+/// The environment variable which points to the directory of the built-in language models
+/// if they are not embedded into the binary.
+#[cfg(feature = "external-models")]
+pub(crate) const MODELS_DIRECTORY_VARIABLE: &str = "LINGUA_MODELS_DIRECTORY";
+
+#[cfg(feature = "external-models")]
+pub(crate) const MISSING_MODELS_DIRECTORY_MESSAGE: &str = "No directory of language models has \
+     been specified, neither with LanguageDetectorBuilder::with_models_directory nor with \
+     the environment variable LINGUA_MODELS_DIRECTORY";
+
+/// The directory set by the builder, which takes precedence over the environment variable.
+/// It applies to all detectors, as they share the loaded language models.
+#[cfg(feature = "external-models")]
+static MODELS_DIRECTORY: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+#[cfg(feature = "external-models")]
+pub(crate) fn set_models_directory(directory: &Path) {
+    *MODELS_DIRECTORY.write().unwrap() = Some(directory.to_path_buf());
+}
+
+#[cfg(feature = "external-models")]
+pub(crate) fn models_directory() -> Option<PathBuf> {
+    MODELS_DIRECTORY
+        .read()
+        .unwrap()
+        .clone()
+        .or_else(|| std::env::var_os(MODELS_DIRECTORY_VARIABLE).map(PathBuf::from))
+}
+
+/// The models of a built-in language are located in a subdirectory of the models directory
+/// which is named after the language's ISO 639-1 code, such as `en` for English.
+#[cfg(feature = "external-models")]
+fn external_language_models_directory(language: Language) -> Option<PathBuf> {
+    Some(models_directory()?.join(language.iso_code_639_1().to_string()))
+}
+
+/// Returns the paths of the ngram model files which the given built-in languages require
+/// but which are missing in the given models directory.
+#[cfg(feature = "external-models")]
+pub(crate) fn missing_model_files(
+    models_directory: &Path,
+    languages: &HashSet<Language>,
+    maximum_ngram_length: usize,
+) -> Vec<PathBuf> {
+    languages
+        .iter()
+        .filter(|language| !matches!(language, Language::Custom(_)))
+        .flat_map(|language| {
+            let maximum_ngram_length = if has_unigram_model_only(*language) {
+                1
+            } else {
+                maximum_ngram_length
+            };
+            let directory = models_directory.join(language.iso_code_639_1().to_string());
+            (1..=maximum_ngram_length).map(move |ngram_length| {
+                let ngram_name = Ngram::find_ngram_name_by_length(ngram_length);
+                directory.join(format!("{ngram_name}s.json.br"))
+            })
+        })
+        .filter(|file| !file.is_file())
+        .sorted()
+        .collect()
+}
+
+fn decompress(compressed_file_content: &[u8]) -> std::io::Result<String> {
+    let compressed_file_reader = Cursor::new(compressed_file_content);
     let mut uncompressed_file = Decompressor::new(compressed_file_reader, 4096);
     let mut uncompressed_file_content = String::new();
     uncompressed_file.read_to_string(&mut uncompressed_file_content)?;
     Ok(uncompressed_file_content)
 }
 
+#[cfg(not(feature = "external-models"))]
 fn get_language_models_directory(language: Language) -> Dir<'static> {
     match language {
         #[cfg(feature = "afrikaans")]
@@ -411,6 +635,8 @@ fn get_language_models_directory(language: Language) -> Dir<'static> {
 
         #[cfg(feature = "zulu")]
         Language::Zulu => ZULU_MODELS_DIRECTORY,
+
+        Language::Custom(_) => unreachable!("custom languages are read from the file system"),
     }
 }
 
@@ -509,4 +735,69 @@ mod tests {
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), minify(EXPECTED_UNIGRAM_MODEL));
     }
+
+    #[test]
+    fn test_load_missing_word_boundary_json() {
+        assert!(!has_word_boundary_models(Language::English, 5));
+        assert_eq!(
+            load_word_boundary_json(Language::English, 1)
+                .unwrap_err()
+                .kind(),
+            ErrorKind::NotFound
+        );
+    }
+
+    #[test]
+    fn test_load_missing_counts_json() {
+        assert!(!has_ngram_counts(Language::English, 5, false));
+        assert_eq!(
+            load_counts_json(Language::English, 1, false)
+                .unwrap_err()
+                .kind(),
+            ErrorKind::NotFound
+        );
+    }
+
+    #[test]
+    fn test_load_missing_word_json() {
+        assert!(!has_word_model(Language::English));
+        assert_eq!(
+            load_word_json(Language::English).unwrap_err().kind(),
+            ErrorKind::NotFound
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "external-models")]
+    fn test_missing_model_files() {
+        let models_directory = tempfile::tempdir().unwrap();
+        let english_directory = models_directory.path().join("en");
+        fs::create_dir(&english_directory).unwrap();
+        fs::write(english_directory.join("unigrams.json.br"), []).unwrap();
+
+        let missing_files = missing_model_files(
+            models_directory.path(),
+            &HashSet::from([Language::English, Language::German, Language::Japanese]),
+            2,
+        );
+
+        assert_eq!(
+            missing_files,
+            vec![
+                models_directory.path().join("de/bigrams.json.br"),
+                models_directory.path().join("de/unigrams.json.br"),
+                english_directory.join("bigrams.json.br"),
+                models_directory.path().join("ja/unigrams.json.br"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_counts_file_name() {
+        assert_eq!(counts_file_name(3, false), "trigram-counts.json.br");
+        assert_eq!(
+            counts_file_name(1, true),
+            "word-boundary-unigram-counts.json.br"
+        );
+    }
 }
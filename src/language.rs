@@ -16,16 +16,22 @@
 
 use std::collections::HashSet;
 use std::fmt::{Debug, Display, Formatter, Result};
+use std::path::Path;
 use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
-use strum::IntoEnumIterator;
-use strum_macros::{EnumIter, EnumString};
+use strum::{IntoEnumIterator, ParseError};
+use strum_macros::EnumIter;
 
 use crate::alphabet::Alphabet;
+use crate::custom::CustomLanguage;
 use crate::isocode::{IsoCode639_1, IsoCode639_3};
 
 /// This enum specifies the so far 75 supported languages which can be detected by *Lingua*.
+///
+/// Further languages can be registered at runtime with [`Language::register_custom`].
+/// As more languages may be added in future versions, matches on this enum must
+/// include a wildcard arm.
 #[derive(
     Clone,
     Copy,
@@ -33,7 +39,6 @@ use crate::isocode::{IsoCode639_1, IsoCode639_3};
     Serialize,
     Deserialize,
     EnumIter,
-    EnumString,
     Eq,
     PartialEq,
     Hash,
@@ -41,8 +46,7 @@ use crate::isocode::{IsoCode639_1, IsoCode639_3};
     PartialOrd,
 )]
 #[serde(rename_all(serialize = "UPPERCASE", deserialize = "UPPERCASE"))]
-#[strum(ascii_case_insensitive)]
-#[cfg_attr(feature = "python", pyo3::prelude::pyclass(eq, eq_int, frozen, hash, ord, rename_all = "UPPERCASE"))]
+#[non_exhaustive]
 pub enum Language {
     #[cfg(feature = "afrikaans")]
     Afrikaans,
@@ -268,16 +272,67 @@ pub enum Language {
 
     #[cfg(feature = "zulu")]
     Zulu,
+
+    /// A language which has been registered at runtime with [`Language::register_custom`].
+    #[strum(disabled)]
+    Custom(CustomLanguage),
 }
 
 impl Display for Language {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        let debug_repr = format!("{self:?}");
-        write!(f, "{}", debug_repr)
+        match self {
+            Language::Custom(language) => write!(f, "{}", language.name()),
+            _ => {
+                let debug_repr = format!("{self:?}");
+                write!(f, "{}", debug_repr)
+            }
+        }
+    }
+}
+
+impl FromStr for Language {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Language::iter()
+            .find(|language| language.to_string().eq_ignore_ascii_case(s))
+            .or_else(|| CustomLanguage::find_by_name(s).map(Language::Custom))
+            .ok_or(ParseError::VariantNotFound)
     }
 }
 
 impl Language {
+    /// Registers a language which is not built into *Lingua* and returns it.
+    ///
+    /// The language models are loaded lazily from the given directory, which must
+    /// contain the files written by [`LanguageModelFilesWriter`](crate::LanguageModelFilesWriter)
+    /// for this language. The returned language can be passed to
+    /// [`LanguageDetectorBuilder`](crate::LanguageDetectorBuilder) together with built-in
+    /// languages and is found by its name and ISO codes just like them. Registering
+    /// exactly the same language again returns the existing one.
+    ///
+    /// ⚠ Panics if the name does not consist of ASCII letters only, if the ISO codes do
+    /// not consist of two and three ASCII letters, respectively, if no alphabet is given
+    /// or if the name or one of the ISO codes is already used by another language.
+    pub fn register_custom(
+        name: &str,
+        iso_code_639_1: &str,
+        iso_code_639_3: &str,
+        alphabets: &[Alphabet],
+        models_directory: impl AsRef<Path>,
+    ) -> Language {
+        match CustomLanguage::register(
+            name,
+            iso_code_639_1,
+            iso_code_639_3,
+            alphabets,
+            models_directory.as_ref(),
+        ) {
+            Ok(language) => Language::Custom(language),
+            Err(message) => panic!("{}", message),
+        }
+    }
+
     /// Returns a set of all supported languages.
     pub fn all() -> HashSet<Language> {
         Language::iter().collect()
@@ -327,6 +382,9 @@ impl Language {
     /// Returns the language associated with the ISO 639-1 code
     /// passed to this method.
     pub fn from_iso_code_639_1(iso_code: &IsoCode639_1) -> Language {
+        if let IsoCode639_1::Custom(language) = iso_code {
+            return Language::Custom(*language);
+        }
         Language::iter()
             .find(|it| &it.iso_code_639_1() == iso_code)
             .unwrap()
@@ -335,6 +393,9 @@ impl Language {
     /// Returns the language associated with the ISO 639-3 code
     /// passed to this method.
     pub fn from_iso_code_639_3(iso_code: &IsoCode639_3) -> Language {
+        if let IsoCode639_3::Custom(language) = iso_code {
+            return Language::Custom(*language);
+        }
         Language::iter()
             .find(|it| &it.iso_code_639_3() == iso_code)
             .unwrap()
@@ -567,6 +628,8 @@ impl Language {
 
             #[cfg(feature = "zulu")]
             Language::Zulu => IsoCode639_1::ZU,
+
+            Language::Custom(language) => IsoCode639_1::Custom(*language),
         }
     }
 
@@ -797,6 +860,8 @@ impl Language {
 
             #[cfg(feature = "zulu")]
             Language::Zulu => IsoCode639_3::ZUL,
+
+            Language::Custom(language) => IsoCode639_3::Custom(*language),
         }
     }
 
@@ -1026,6 +1091,8 @@ impl Language {
 
             #[cfg(feature = "thai")]
             Language::Thai => hashset!(Alphabet::Thai),
+
+            Language::Custom(language) => language.alphabets(),
         }
     }
 
@@ -1374,4 +1441,42 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn assert_custom_language_behaves_like_built_in_languages() {
+        let language = Language::register_custom(
+            "Sardinian",
+            "sc",
+            "srd",
+            &[Alphabet::Latin],
+            "/models/sardinian",
+        );
+
+        assert_eq!(language.to_string(), "Sardinian");
+        assert_eq!(
+            serde_json::to_string(&language).unwrap(),
+            r#"{"CUSTOM":"Sardinian"}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<Language>(r#"{"CUSTOM":"Sardinian"}"#).unwrap(),
+            language
+        );
+        assert_eq!(Language::from_str("sardinian").unwrap(), language);
+        assert_eq!(language.iso_code_639_1().to_string(), "sc");
+        assert_eq!(language.iso_code_639_3().to_string(), "srd");
+        assert_eq!(
+            Language::from_iso_code_639_3(&IsoCode639_3::from_str("srd").unwrap()),
+            language
+        );
+        assert_eq!(language.alphabets(), hashset!(Alphabet::Latin));
+        assert!(!Language::all().contains(&language));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "The name and ISO codes of a custom language must not be used by another language"
+    )]
+    fn assert_custom_language_cannot_replace_built_in_language() {
+        Language::register_custom("German", "xy", "xyz", &[Alphabet::Latin], "/models/german");
+    }
 }
@@ -36,9 +36,9 @@
 //! Unfortunately, most of them have two major drawbacks:
 //!
 //! 1. Detection only works with quite lengthy text fragments. For very short text snippets
-//! such as Twitter messages, it does not provide adequate results.
+//!    such as Twitter messages, it does not provide adequate results.
 //! 2. The more languages take part in the decision process, the less accurate are the
-//! detection results.
+//!    detection results.
 //!
 //! *Lingua* aims at eliminating these problems. She nearly does not need any configuration and
 //! yields pretty accurate results on both long and short text, even on single words and phrases.
@@ -121,6 +121,36 @@
 //! lingua = { version = "1.6.2", default-features = false, features = ["french", "italian", "spanish"] }
 //! ```
 //!
+//! The language models are decompressed and parsed from JSON when they are first needed. With the
+//! opt-in feature `compiled-models`, a build script converts the models of the selected languages
+//! into one sorted table per ngram length which is compiled into the binary instead. Looking up an
+//! ngram then requires neither loading nor allocation, so the first detection is as fast as any
+//! later one. Tables are generated for all ngram lengths of the models found in the language model
+//! crates, including word boundary models. The models used in accent-insensitive mode are still
+//! loaded at runtime, and the binary grows by roughly 5 MB per language:
+//!
+//! ```toml
+//! [dependencies]
+//! lingua = { version = "1.6.2", default-features = false, features = ["english", "german", "compiled-models"] }
+//! ```
+//!
+//! Conversely, the opt-in feature `external-models` keeps the language models out of the binary,
+//! which reduces the size of container images and serverless deployments by up to 70 MB. It is
+//! passed on to the language model crates, which then do not embed their models at compile time
+//! either. The models are shipped as a separate artifact instead: a directory containing a
+//! subdirectory for each language which is named after its ISO 639-1 code, such as `en`, and holds
+//! the files of the `models` directory of the respective language model crate. The directory is
+//! given by the environment variable `LINGUA_MODELS_DIRECTORY` or by the builder. Building a
+//! detector panics with a list of all missing model files if the directory is incomplete:
+//!
+//! ```ignore
+//! use lingua::LanguageDetectorBuilder;
+//!
+//! let detector = LanguageDetectorBuilder::from_all_languages()
+//!     .with_models_directory("/opt/lingua/models")
+//!     .build();
+//! ```
+//!
 //! ## 7. How to use?
 //!
 //! ### 7.1 Basic usage
@@ -231,6 +261,81 @@
 //! Multiple instances of `LanguageDetector` share the same language models in memory which are
 //! accessed asynchronously by the instances.
 //!
+//! With lazy loading, a detector built from all languages eventually holds every language model
+//! touched by its input. A memory budget in bytes limits this. The least recently used models
+//! are evicted once the budget is exceeded and loaded again when they are needed. The budget
+//! applies to each detector separately and covers only the models it has used:
+//!
+//! ```
+//! use lingua::LanguageDetectorBuilder;
+//!
+//! LanguageDetectorBuilder::from_all_languages().with_memory_budget(200_000_000).build();
+//! ```
+//!
+//! Preloading blocks building the detector until all models have been loaded, which delays the
+//! readiness of a service by several seconds. Alternatively, the models can be preloaded on a
+//! background thread while the detector loads the models it needs on demand in the meantime.
+//! The progress of the preloading can be polled:
+//!
+//! ```
+//! use lingua::LanguageDetectorBuilder;
+//!
+//! let detector = LanguageDetectorBuilder::from_all_languages()
+//!     .with_preloaded_language_models_in_background()
+//!     .build();
+//! let progress = detector.preloading_progress().unwrap();
+//!
+//! println!(
+//!     "{} of {} languages loaded",
+//!     progress.loaded_language_count(),
+//!     progress.total_language_count()
+//! );
+//! ```
+//!
+//! Short-lived worker processes can skip decompressing and parsing the language models altogether.
+//! A detector is written to a snapshot file once, together with its settings and all of its
+//! models, and restored from it in each process. The snapshot is rejected if it has been written
+//! by another version of *Lingua*, with other language models or with other built-in languages
+//! enabled by crate features:
+//!
+//! ```no_run
+//! use lingua::{LanguageDetector, LanguageDetectorBuilder};
+//!
+//! let detector = LanguageDetectorBuilder::from_all_languages().build();
+//! detector.write_snapshot("/path/to/detector.snapshot")?;
+//!
+//! let restored_detector = LanguageDetector::from_snapshot("/path/to/detector.snapshot")?;
+//! # Ok::<(), std::io::Error>(())
+//! ```
+//!
+//! A model which cannot be loaded, for instance because a model file of a custom language is
+//! missing or has been corrupted, does not stop the detection. Instead, the failure is recorded and
+//! the affected language simply receives no probabilities from that model. This applies to the
+//! ngram models as well as to the ngram counts used for smoothing and to the word models. The
+//! status of all models that have been needed so far can be inspected at any time. Model files
+//! written by `LanguageModelFilesWriter` carry a format version and a checksum of their ngrams, so
+//! that truncated or modified files are reported as well:
+//!
+//! ```
+//! use lingua::Language::{English, French};
+//! use lingua::LanguageDetectorBuilder;
+//!
+//! let detector = LanguageDetectorBuilder::from_languages(&[English, French]).build();
+//! detector.detect_language_of("languages are awesome");
+//!
+//! let status = detector.model_status();
+//!
+//! for failure in status.failures() {
+//!     eprintln!(
+//!         "{:?} model of length {} of {} failed to load: {}",
+//!         failure.kind(),
+//!         failure.ngram_length(),
+//!         failure.language(),
+//!         failure.error()
+//!     );
+//! }
+//! ```
+//!
 //! ### 7.5 Low accuracy mode versus high accuracy mode
 //!
 //! *Lingua's* high detection accuracy comes at the cost of being noticeably slower
@@ -261,7 +366,109 @@
 //! the texts you want to classify you can almost always rule out certain languages as impossible
 //! or unlikely to occur.
 //!
-//! ### 7.6 Detection of multiple languages in mixed-language texts
+//! Internally, texts of less than 120 characters are classified with unigrams up to fivegrams,
+//! whereas longer texts and all texts in low accuracy mode are classified with trigrams only.
+//! Both ranges of ngram lengths can be changed. Higher-order models up to eightgrams can be
+//! created with `LanguageModelFilesWriter::with_maximum_ngram_length`. Ngram lengths for
+//! which no model is available for a language are skipped.
+//!
+//! ```
+//! use lingua::LanguageDetectorBuilder;
+//!
+//! LanguageDetectorBuilder::from_all_languages()
+//!     .with_ngram_lengths(1..=5, 3..=6)
+//!     .build();
+//! ```
+//!
+//! Texts consisting of only one or two words provide little character evidence. For these,
+//! the ngram models can be combined with the relative frequencies of whole words. Word models
+//! are created with `LanguageModelFilesWriter::with_word_model`. The given weight determines
+//! how much the words contribute. Word models are not bundled with the built-in languages,
+//! so building the detector panics unless they are available for all of its languages,
+//! such as custom languages whose models have been written with a word model:
+//!
+//! ```no_run
+//! use lingua::{Alphabet, Language, LanguageDetectorBuilder};
+//!
+//! let galician = Language::register_custom(
+//!     "Galician",
+//!     "gl",
+//!     "glg",
+//!     &[Alphabet::Latin],
+//!     "/path/to/galician/models",
+//! );
+//! let occitan = Language::register_custom(
+//!     "Occitan",
+//!     "oc",
+//!     "oci",
+//!     &[Alphabet::Latin],
+//!     "/path/to/occitan/models",
+//! );
+//!
+//! LanguageDetectorBuilder::from_languages(&[galician, occitan])
+//!     .with_word_model(0.5)
+//!     .build();
+//! ```
+//!
+//! ### 7.6 Accent-insensitive mode
+//!
+//! Text from sources such as search queries, SMS or old ASCII-only systems often lacks the
+//! diacritics of the language it is written in. As the language models are built from correctly
+//! accented text, such input is frequently misclassified. The *accent-insensitive mode* removes
+//! diacritics from both the input text and the language models of all languages written in the
+//! Latin or Greek script before comparing them:
+//!
+//! ```
+//! use lingua::Language::{Czech, Polish, Slovak};
+//! use lingua::LanguageDetectorBuilder;
+//!
+//! let detector = LanguageDetectorBuilder::from_languages(&[Czech, Polish, Slovak])
+//!     .with_accent_insensitive_mode()
+//!     .build();
+//!
+//! assert_eq!(detector.detect_language_of("zazolc gesla jazn"), Some(Polish));
+//! ```
+//!
+//! The accent-insensitive language models are derived from the regular ones when they are loaded,
+//! so no additional model files are needed. Rules based on characters unique to a language still
+//! see the original input text, so text containing diacritics is classified as reliably as before.
+//!
+//! ### 7.7 Text normalization
+//!
+//! Text from social media or user input often contains characters which do not occur in the
+//! training data, such as invisible control characters, fullwidth letters or elongated words like
+//! *sooooo*. A chain of normalizers can be applied to the input text before it is split into words.
+//! The normalizers are applied in the given order:
+//!
+//! ```
+//! use lingua::Language::{English, German};
+//! use lingua::{LanguageDetectorBuilder, Normalizer};
+//!
+//! let detector = LanguageDetectorBuilder::from_languages(&[English, German])
+//!     .with_normalizers(&[
+//!         Normalizer::RemoveInvisibleCharacters,
+//!         Normalizer::Nfkc,
+//!         Normalizer::SquashElongation,
+//!     ])
+//!     .build();
+//!
+//! assert_eq!(detector.detect_language_of("Ｉｔ ｉｓ sooooo cooool"), Some(English));
+//! ```
+//!
+//! Input taken from web pages, e-mails or chat messages can be cleaned with `Normalizer::CleanMarkup`
+//! which masks HTML tags, Markdown code, URLs, e-mail addresses, @mentions and emoji, and splits
+//! camel-cased hashtags such as `#ThrowbackThursday` into separate words.
+//!
+//! The indices returned by the detection of multiple languages still refer to the original input
+//! text. For best results, create your own language models with the same normalizers by using
+//! `LanguageModelFilesWriter::with_normalizers`.
+//!
+//! After normalization, the text is split into words by the `DefaultTokenizer` which treats every
+//! Chinese, Japanese and Korean character as a separate word. A custom implementation of the
+//! `Tokenizer` trait, such as a dictionary-based segmenter, can be passed to
+//! `LanguageDetectorBuilder::with_tokenizer` and `LanguageModelFilesWriter::with_tokenizer` instead.
+//!
+//! ### 7.8 Detection of multiple languages in mixed-language texts
 //!
 //! In contrast to most other language detectors, *Lingua* is able to detect multiple languages
 //! in mixed-language texts. This feature can yield quite reasonable results, but it is still
@@ -310,7 +517,7 @@
 //! describes a contiguous single-language text section, providing start and end indices of the
 //! respective substring.
 //!
-//! ### 7.7 Single-threaded versus multi-threaded language detection
+//! ### 7.9 Single-threaded versus multi-threaded language detection
 //!
 //! The `LanguageDetector` methods explained above all operate in a single thread.
 //! If you want to classify a very large set of texts, you will probably want to
@@ -326,7 +533,7 @@
 //! | `compute_language_confidence_values` | `compute_language_confidence_values_in_parallel` |
 //! | `compute_language_confidence`        | `compute_language_confidence_in_parallel`        |
 //!
-//! ### 7.8 Methods to build the LanguageDetector
+//! ### 7.10 Methods to build the LanguageDetector
 //!
 //! There might be classification tasks where you know beforehand that your language data is
 //! definitely not written in Latin, for instance (what a surprise :-). The detection accuracy can
@@ -358,6 +565,96 @@
 //! LanguageDetectorBuilder::from_iso_codes_639_3(&[IsoCode639_3::ENG, IsoCode639_3::DEU]);
 //! ```
 //!
+//! Languages which are not built into *Lingua* can be registered at runtime. Their language
+//! models are created with `LanguageModelFilesWriter` and loaded lazily from the given directory.
+//! Afterwards, they can be used like any built-in language:
+//!
+//! ```
+//! use lingua::{Alphabet, Language, LanguageDetectorBuilder};
+//!
+//! let galician = Language::register_custom(
+//!     "Galician",
+//!     "gl",
+//!     "glg",
+//!     &[Alphabet::Latin],
+//!     "/path/to/galician/models",
+//! );
+//!
+//! LanguageDetectorBuilder::from_languages(&[Language::Portuguese, Language::Spanish, galician]);
+//! ```
+//!
+//! Regional variants of a language, such as Brazilian and European Portuguese, can be told apart
+//! once their language models have been created with
+//! `LanguageModelFilesWriter::write_language_variant_model_files`. Each variant is stored in a
+//! subdirectory named after its BCP 47 tag. The language is detected first, then only the
+//! variants of this language are compared with each other:
+//!
+//! ```no_run
+//! use lingua::{Language, LanguageDetectorBuilder, LanguageVariant};
+//!
+//! let detector = LanguageDetectorBuilder::from_languages(&[Language::English, Language::Portuguese])
+//!     .with_language_variants("/path/to/variant/models")
+//!     .build();
+//!
+//! let variant: Option<LanguageVariant> = detector.detect_language_variant_of("Peguei o ônibus.");
+//! ```
+//!
+//! Text detected as Chinese can further be classified as Simplified or Traditional Chinese.
+//! Characters which only occur in one of both scripts are counted, and text containing a
+//! substantial share of both is reported as mixed. The BCP 47 tag of the script is available
+//! as well:
+//!
+//! ```
+//! use lingua::{ChineseScript, Language, LanguageDetectorBuilder};
+//!
+//! let detector = LanguageDetectorBuilder::from_languages(&[Language::Chinese, Language::Japanese])
+//!     .build();
+//! let script = detector.detect_chinese_script_of("我們學習中文已經三年了");
+//!
+//! assert_eq!(script, Some(ChineseScript::Traditional));
+//! assert_eq!(script.unwrap().bcp47_tag(), "zh-Hant");
+//! ```
+//!
+//! Closely related languages, such as Bokmal, Nynorsk and Danish or Malay and Indonesian, are
+//! confused most often. A discriminator for such a pair can be trained from text of both
+//! languages with `LanguageModelFilesWriter::write_pair_discriminator_file`. No discriminators
+//! are bundled with this library, so this stage is opt-in and requires training them first.
+//! If the two most likely languages of a text form a pair for which a discriminator has been
+//! loaded, it decides how their combined confidence value is split between them:
+//!
+//! ```no_run
+//! use lingua::{Language, LanguageDetectorBuilder};
+//!
+//! let detector = LanguageDetectorBuilder::from_languages(&[
+//!     Language::Bokmal,
+//!     Language::Danish,
+//!     Language::Nynorsk,
+//! ])
+//! .with_pair_discriminators("/path/to/discriminators")
+//! .build();
+//! ```
+//!
+//! Some applications do not need to tell apart the individual languages of a macrolanguage,
+//! such as Bokmal and Nynorsk of Norwegian. Configured macrolanguages are reported with their
+//! summed confidence values instead, while the fine-grained distribution remains available
+//! through the other methods of the detector. Their ISO 639-3 codes are `msa`, `nor` and `hbs`:
+//!
+//! ```
+//! use lingua::{CollapsedLanguage, Language, LanguageDetectorBuilder, MacroLanguage};
+//!
+//! let detector = LanguageDetectorBuilder::from_languages(&[
+//!     Language::Bokmal,
+//!     Language::English,
+//!     Language::Nynorsk,
+//! ])
+//! .with_macro_languages(&[MacroLanguage::Norwegian])
+//! .build();
+//! let language = detector.detect_collapsed_language_of("Jeg vet ikke hva du mener");
+//!
+//! assert_eq!(language, Some(CollapsedLanguage::Macro(MacroLanguage::Norwegian)));
+//! assert_eq!(language.unwrap().iso_code_639_3(), "nor");
+//! ```
+//!
 //! ## 8. WebAssembly support
 //!
 //! This library can be compiled to [WebAssembly (WASM)](https://webassembly.org) which allows to
@@ -392,30 +689,59 @@ extern crate maplit;
 #[cfg(test)]
 use regex::Regex;
 
+pub use alphabet::Alphabet;
 pub use builder::LanguageDetectorBuilder;
+pub use chinese::ChineseScript;
+pub use custom::CustomLanguage;
 pub use detector::LanguageDetector;
 pub use isocode::{IsoCode639_1, IsoCode639_3};
 pub use language::Language;
+pub use macrolanguage::{CollapsedLanguage, MacroLanguage};
+pub use normalizer::Normalizer;
+pub use outcome::DetectionOutcome;
+pub use progress::PreloadingProgress;
 pub use result::DetectionResult;
+pub use smoothing::Smoothing;
+pub use status::{ModelKind, ModelLoadError, ModelLoadFailure, ModelStatus};
+pub use tokenizer::{DefaultTokenizer, Tokenizer};
+pub use variant::LanguageVariant;
 #[cfg(target_family = "wasm")]
 pub use wasm::{
-    ConfidenceValue, DetectionResult as WasmDetectionResult,
-    LanguageDetectorBuilder as WasmLanguageDetectorBuilder,
+    ConfidenceValue, DetectionOutcome as WasmDetectionOutcome,
+    DetectionResult as WasmDetectionResult, LanguageDetectorBuilder as WasmLanguageDetectorBuilder,
 };
-pub use writer::{LanguageModelFilesWriter, TestDataFilesWriter};
+pub use writer::{CharacterRulesWriter, LanguageModelFilesWriter, TestDataFilesWriter};
 
 mod alphabet;
 mod builder;
+mod cache;
+mod chinese;
+#[cfg(feature = "compiled-models")]
+mod compiled;
 mod constant;
+mod custom;
 mod detector;
+mod discriminator;
+mod folding;
 mod fraction;
 mod isocode;
 mod json;
 mod language;
+mod macrolanguage;
 mod model;
 mod ngram;
+mod normalizer;
+mod outcome;
+mod progress;
 mod result;
+mod rule;
 mod script;
+mod smoothing;
+mod snapshot;
+mod status;
+mod table;
+mod tokenizer;
+mod variant;
 mod writer;
 
 #[cfg(feature = "python")]
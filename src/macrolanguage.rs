@@ -0,0 +1,204 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter, Result};
+
+use strum::IntoEnumIterator;
+use strum_macros::{EnumIter, EnumString};
+
+use crate::language::Language;
+
+/// This enum specifies the macrolanguages which comprise several closely related
+/// languages supported by *Lingua*.
+///
+/// Detectors built with
+/// [`LanguageDetectorBuilder::with_macro_languages`](crate::LanguageDetectorBuilder::with_macro_languages)
+/// report the configured macrolanguages instead of their individual languages.
+#[derive(Clone, Copy, Debug, EnumIter, EnumString, Eq, PartialEq, Hash, Ord, PartialOrd)]
+#[strum(ascii_case_insensitive)]
+#[cfg_attr(
+    feature = "python",
+    pyo3::prelude::pyclass(eq, eq_int, frozen, hash, ord, rename_all = "SCREAMING_SNAKE_CASE")
+)]
+pub enum MacroLanguage {
+    /// Comprises *Malay* and *Indonesian*.
+    Malay,
+
+    /// Comprises *Bokmal* and *Nynorsk*.
+    Norwegian,
+
+    /// Comprises *Bosnian*, *Croatian* and *Serbian*.
+    SerboCroatian,
+}
+
+impl MacroLanguage {
+    /// Returns the ISO 639-3 code of this macrolanguage, such as `nor` for Norwegian.
+    pub fn iso_code_639_3(&self) -> &'static str {
+        match self {
+            MacroLanguage::Malay => "msa",
+            MacroLanguage::Norwegian => "nor",
+            MacroLanguage::SerboCroatian => "hbs",
+        }
+    }
+
+    /// Returns the individual languages which this macrolanguage comprises.
+    /// Languages whose crate features are disabled are not included.
+    pub fn languages(&self) -> HashSet<Language> {
+        let languages: Vec<Language> = match self {
+            MacroLanguage::Malay => vec![
+                #[cfg(feature = "indonesian")]
+                Language::Indonesian,
+                #[cfg(feature = "malay")]
+                Language::Malay,
+            ],
+            MacroLanguage::Norwegian => vec![
+                #[cfg(feature = "bokmal")]
+                Language::Bokmal,
+                #[cfg(feature = "nynorsk")]
+                Language::Nynorsk,
+            ],
+            MacroLanguage::SerboCroatian => vec![
+                #[cfg(feature = "bosnian")]
+                Language::Bosnian,
+                #[cfg(feature = "croatian")]
+                Language::Croatian,
+                #[cfg(feature = "serbian")]
+                Language::Serbian,
+            ],
+        };
+        languages.into_iter().collect()
+    }
+
+    /// Returns the macrolanguage which comprises the given language, if any.
+    pub fn from_language(language: Language) -> Option<Self> {
+        MacroLanguage::iter().find(|macro_language| macro_language.languages().contains(&language))
+    }
+}
+
+impl Display for MacroLanguage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let debug_repr = format!("{self:?}");
+        write!(f, "{}", debug_repr)
+    }
+}
+
+/// This enum describes the result of a detection in which the languages of the
+/// configured macrolanguages have been collapsed into their macrolanguage.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum CollapsedLanguage {
+    /// A language which is not part of any configured macrolanguage.
+    Individual(Language),
+
+    /// A configured macrolanguage.
+    Macro(MacroLanguage),
+}
+
+impl CollapsedLanguage {
+    /// Returns the lowercase ISO 639-3 code of the language or macrolanguage.
+    pub fn iso_code_639_3(&self) -> String {
+        match self {
+            CollapsedLanguage::Individual(language) => language.iso_code_639_3().to_string(),
+            CollapsedLanguage::Macro(macro_language) => macro_language.iso_code_639_3().to_string(),
+        }
+    }
+
+    pub(crate) fn collapse(language: Language, macro_languages: &HashSet<MacroLanguage>) -> Self {
+        match MacroLanguage::from_language(language) {
+            Some(macro_language) if macro_languages.contains(&macro_language) => {
+                CollapsedLanguage::Macro(macro_language)
+            }
+            _ => CollapsedLanguage::Individual(language),
+        }
+    }
+}
+
+impl Display for CollapsedLanguage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            CollapsedLanguage::Individual(language) => write!(f, "{language}"),
+            CollapsedLanguage::Macro(macro_language) => write!(f, "{macro_language}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::language::Language::{Bokmal, English, Indonesian, Serbian};
+
+    use super::*;
+
+    #[test]
+    fn assert_macro_language_of_language_is_correct() {
+        assert_eq!(
+            MacroLanguage::from_language(Bokmal),
+            Some(MacroLanguage::Norwegian)
+        );
+        assert_eq!(
+            MacroLanguage::from_language(Indonesian),
+            Some(MacroLanguage::Malay)
+        );
+        assert_eq!(
+            MacroLanguage::from_language(Serbian),
+            Some(MacroLanguage::SerboCroatian)
+        );
+        assert_eq!(MacroLanguage::from_language(English), None);
+    }
+
+    #[test]
+    fn assert_macro_languages_do_not_overlap() {
+        let languages = MacroLanguage::iter()
+            .flat_map(|macro_language| macro_language.languages())
+            .collect::<Vec<_>>();
+        let unique_languages = languages.iter().collect::<HashSet<_>>();
+
+        assert_eq!(languages.len(), unique_languages.len());
+    }
+
+    #[test]
+    fn assert_macro_language_string_representation_is_correct() {
+        assert_eq!(MacroLanguage::SerboCroatian.to_string(), "SerboCroatian");
+        assert_eq!(
+            MacroLanguage::from_str("serbocroatian"),
+            Ok(MacroLanguage::SerboCroatian)
+        );
+        assert_eq!(MacroLanguage::Norwegian.iso_code_639_3(), "nor");
+    }
+
+    #[test]
+    fn assert_language_is_collapsed_correctly() {
+        let macro_languages = hashset!(MacroLanguage::Norwegian);
+
+        assert_eq!(
+            CollapsedLanguage::collapse(Bokmal, &macro_languages),
+            CollapsedLanguage::Macro(MacroLanguage::Norwegian)
+        );
+        assert_eq!(
+            CollapsedLanguage::collapse(Indonesian, &macro_languages),
+            CollapsedLanguage::Individual(Indonesian)
+        );
+        assert_eq!(
+            CollapsedLanguage::Macro(MacroLanguage::Norwegian).iso_code_639_3(),
+            "nor"
+        );
+        assert_eq!(
+            CollapsedLanguage::Individual(Indonesian).iso_code_639_3(),
+            "ind"
+        );
+    }
+}
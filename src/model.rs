@@ -15,6 +15,10 @@
  */
 
 use std::collections::{BTreeMap, HashMap};
+use std::f64::consts::LN_2;
+use std::fmt::Display;
+use std::io;
+use std::io::{Read, Write};
 
 use ahash::AHashMap;
 use compact_str::CompactString;
@@ -22,16 +26,108 @@ use itertools::Itertools;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 
+use crate::constant::{WORD_END_MARKER, WORD_START_MARKER};
+use crate::folding::{fold_case_for, fold_diacritics};
 use crate::fraction::Fraction;
 use crate::language::Language;
-use crate::ngram::{Ngram, NgramRef};
+use crate::ngram::{Ngram, NgramRef, MAXIMUM_NGRAM_LENGTH};
+use crate::snapshot::{SnapshotReader, SnapshotWriter};
+use crate::status::ModelLoadError;
+
+/// The version of the format of the language model files, to be incremented whenever
+/// their layout changes. Files without a version have been written before it was introduced.
+const MODEL_FILE_FORMAT_VERSION: u32 = 1;
 
 #[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
 struct JsonLanguageModel {
     language: Language,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    version: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    checksum: Option<String>,
     ngrams: BTreeMap<Fraction, String>,
 }
 
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
+struct JsonNgramCounts {
+    language: Language,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    version: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    checksum: Option<String>,
+    ngrams: BTreeMap<u32, String>,
+}
+
+impl JsonLanguageModel {
+    fn new(language: Language, ngrams: BTreeMap<Fraction, String>) -> Self {
+        Self {
+            language,
+            version: Some(MODEL_FILE_FORMAT_VERSION),
+            checksum: Some(compute_checksum(&ngrams)),
+            ngrams,
+        }
+    }
+
+    fn parse(json: &str) -> Result<Self, ModelLoadError> {
+        let model = serde_json::from_str::<Self>(json)
+            .map_err(|error| ModelLoadError::Corrupt(error.to_string()))?;
+        verify_metadata(model.version, model.checksum.as_deref(), &model.ngrams)?;
+        Ok(model)
+    }
+}
+
+impl JsonNgramCounts {
+    fn new(language: Language, ngrams: BTreeMap<u32, String>) -> Self {
+        Self {
+            language,
+            version: Some(MODEL_FILE_FORMAT_VERSION),
+            checksum: Some(compute_checksum(&ngrams)),
+            ngrams,
+        }
+    }
+
+    fn parse(json: &str) -> Result<Self, ModelLoadError> {
+        let counts = serde_json::from_str::<Self>(json)
+            .map_err(|error| ModelLoadError::Corrupt(error.to_string()))?;
+        verify_metadata(counts.version, counts.checksum.as_deref(), &counts.ngrams)?;
+        Ok(counts)
+    }
+}
+
+/// Computes the 64-bit FNV-1a hash of the ngrams of a model file in their serialized order.
+fn compute_checksum<K: Display>(ngrams: &BTreeMap<K, String>) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for (key, joined_ngrams) in ngrams {
+        let entry = format!("{key}:{joined_ngrams}\n");
+        for byte in entry.bytes() {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    }
+    format!("{hash:016x}")
+}
+
+/// Files written before the metadata has been introduced carry none and are accepted as they are.
+fn verify_metadata<K: Display>(
+    version: Option<u32>,
+    checksum: Option<&str>,
+    ngrams: &BTreeMap<K, String>,
+) -> Result<(), ModelLoadError> {
+    if let Some(version) = version.filter(|version| *version > MODEL_FILE_FORMAT_VERSION) {
+        return Err(ModelLoadError::UnsupportedVersion(version));
+    }
+    if let Some(expected_checksum) = checksum {
+        let actual_checksum = compute_checksum(ngrams);
+        if actual_checksum != expected_checksum {
+            return Err(ModelLoadError::ChecksumMismatch {
+                expected: expected_checksum.to_string(),
+                actual: actual_checksum,
+            });
+        }
+    }
+    Ok(())
+}
+
 pub(crate) struct TrainingDataLanguageModel {
     language: Language,
     pub(crate) absolute_frequencies: Option<HashMap<Ngram, u32>>,
@@ -47,7 +143,7 @@ impl TrainingDataLanguageModel {
         lower_ngram_absolute_frequencies: &HashMap<Ngram, u32>,
     ) -> Self {
         let absolute_frequencies =
-            Self::compute_absolute_frequencies(text, ngram_length, char_class);
+            Self::compute_absolute_frequencies(text, language, ngram_length, char_class);
 
         let relative_frequencies = Self::compute_relative_frequencies(
             ngram_length,
@@ -62,8 +158,8 @@ impl TrainingDataLanguageModel {
         }
     }
 
-    pub(crate) fn from_json(json: &str) -> AHashMap<CompactString, f64> {
-        let json_language_model = serde_json::from_str::<JsonLanguageModel>(json).unwrap();
+    pub(crate) fn from_json(json: &str) -> Result<AHashMap<CompactString, f64>, ModelLoadError> {
+        let json_language_model = JsonLanguageModel::parse(json)?;
         let mut json_relative_frequencies = AHashMap::new();
 
         for (fraction, ngrams) in json_language_model.ngrams {
@@ -73,7 +169,73 @@ impl TrainingDataLanguageModel {
             }
         }
 
-        json_relative_frequencies
+        Ok(json_relative_frequencies)
+    }
+
+    pub(crate) fn counts_from_json(
+        json: &str,
+    ) -> Result<AHashMap<CompactString, u32>, ModelLoadError> {
+        let json_ngram_counts = JsonNgramCounts::parse(json)?;
+        let mut json_absolute_frequencies = AHashMap::new();
+
+        for (count, ngrams) in json_ngram_counts.ngrams {
+            for ngram in ngrams.split(' ') {
+                json_absolute_frequencies.insert(CompactString::new(ngram), count);
+            }
+        }
+
+        Ok(json_absolute_frequencies)
+    }
+
+    /// Merges all ngrams of the highest order model which are equal after
+    /// diacritics folding and recomputes their conditional probabilities.
+    ///
+    /// The given slice must contain the models of all ngram lengths from
+    /// 1 up to the length of the model to fold, in ascending order.
+    pub(crate) fn fold_diacritics(
+        language_models: &[AHashMap<CompactString, f64>],
+    ) -> AHashMap<CompactString, f64> {
+        let mut joint_probabilities: AHashMap<CompactString, f64> = AHashMap::new();
+
+        for ngram in language_models[language_models.len() - 1].keys() {
+            let mut joint_probability = 1.0;
+            let prefix_ends = ngram
+                .char_indices()
+                .skip(1)
+                .map(|(i, _)| i)
+                .chain(std::iter::once(ngram.len()));
+
+            for (model, end) in language_models.iter().zip(prefix_ends) {
+                match model.get(&ngram[..end]) {
+                    Some(probability) => joint_probability *= probability,
+                    None => {
+                        joint_probability = 0.0;
+                        break;
+                    }
+                }
+            }
+
+            if joint_probability > 0.0 {
+                *joint_probabilities
+                    .entry(CompactString::from(fold_diacritics(ngram)))
+                    .or_insert(0.0) += joint_probability;
+            }
+        }
+
+        let mut prefix_probabilities: AHashMap<&str, f64> = AHashMap::new();
+
+        for (ngram, probability) in joint_probabilities.iter() {
+            let prefix = &ngram[..ngram.char_indices().last().unwrap().0];
+            *prefix_probabilities.entry(prefix).or_insert(0.0) += probability;
+        }
+
+        joint_probabilities
+            .iter()
+            .map(|(ngram, probability)| {
+                let prefix = &ngram[..ngram.char_indices().last().unwrap().0];
+                (ngram.clone(), probability / prefix_probabilities[prefix])
+            })
+            .collect()
     }
 
     pub(crate) fn to_json(&self) -> String {
@@ -91,16 +253,32 @@ impl TrainingDataLanguageModel {
             );
         }
 
-        let model = JsonLanguageModel {
-            language: self.language,
-            ngrams: fractions_to_joined_ngrams,
-        };
+        let model = JsonLanguageModel::new(self.language, fractions_to_joined_ngrams);
 
         serde_json::to_string(&model).unwrap()
     }
 
+    pub(crate) fn to_counts_json(&self) -> String {
+        let mut counts_to_ngrams = hashmap!();
+        for (ngram, count) in self.absolute_frequencies.as_ref().unwrap() {
+            let ngrams = counts_to_ngrams.entry(*count).or_insert_with(Vec::new);
+            ngrams.push(ngram);
+        }
+
+        let mut counts_to_joined_ngrams = btreemap!();
+        for (count, ngrams) in counts_to_ngrams {
+            counts_to_joined_ngrams
+                .insert(count, ngrams.iter().map(|&it| &it.value).sorted().join(" "));
+        }
+
+        let counts = JsonNgramCounts::new(self.language, counts_to_joined_ngrams);
+
+        serde_json::to_string(&counts).unwrap()
+    }
+
     fn compute_absolute_frequencies(
         text: &[&str],
+        language: &Language,
         ngram_length: usize,
         char_class: &str,
     ) -> HashMap<Ngram, u32> {
@@ -112,7 +290,7 @@ impl TrainingDataLanguageModel {
         });
 
         for line in text.iter() {
-            let chars = line.to_lowercase().chars().collect_vec();
+            let chars = fold_case_for(line, language).chars().collect_vec();
 
             for i in 0..=chars.len() - ngram_length {
                 let slice = &chars[i..i + ngram_length].iter().collect::<String>();
@@ -153,17 +331,145 @@ impl TrainingDataLanguageModel {
     }
 }
 
+/// The relative frequencies of whole words which complement the ngram models
+/// for very short text.
+pub(crate) struct TrainingDataWordModel {
+    language: Language,
+    relative_frequencies: HashMap<String, Fraction>,
+}
+
+impl TrainingDataWordModel {
+    pub(crate) fn from_words<'a>(
+        words: impl Iterator<Item = &'a str>,
+        language: &Language,
+    ) -> Self {
+        let mut absolute_frequencies: HashMap<&str, u32> = hashmap!();
+        for word in words {
+            *absolute_frequencies.entry(word).or_insert(0) += 1;
+        }
+
+        let total_word_frequency = absolute_frequencies.values().sum::<u32>();
+        let relative_frequencies = absolute_frequencies
+            .into_iter()
+            .map(|(word, frequency)| {
+                (
+                    word.to_string(),
+                    Fraction::new(frequency, total_word_frequency),
+                )
+            })
+            .collect();
+
+        Self {
+            language: *language,
+            relative_frequencies,
+        }
+    }
+
+    pub(crate) fn to_json(&self) -> String {
+        let mut fractions_to_words = hashmap!();
+        for (word, fraction) in self.relative_frequencies.iter() {
+            let words = fractions_to_words.entry(fraction).or_insert_with(Vec::new);
+            words.push(word);
+        }
+
+        let mut fractions_to_joined_words = btreemap!();
+        for (fraction, words) in fractions_to_words {
+            fractions_to_joined_words.insert(*fraction, words.iter().sorted().join(" "));
+        }
+
+        let model = JsonLanguageModel::new(self.language, fractions_to_joined_words);
+
+        serde_json::to_string(&model).unwrap()
+    }
+}
+
+/// The log-probabilities of whole words of a single language as used for detection.
+pub(crate) struct WordModel {
+    log_probabilities: AHashMap<CompactString, f64>,
+    unseen_word_log_probability: f64,
+}
+
+impl WordModel {
+    pub(crate) fn new(relative_frequencies: AHashMap<CompactString, f64>) -> Self {
+        Self::from_log_probabilities(
+            relative_frequencies
+                .into_iter()
+                .map(|(word, relative_frequency)| (word, relative_frequency.ln()))
+                .collect(),
+        )
+    }
+
+    fn from_log_probabilities(log_probabilities: AHashMap<CompactString, f64>) -> Self {
+        // Words which do not occur in the training data are assumed to be
+        // half as likely as the rarest words which do.
+        let unseen_word_log_probability =
+            log_probabilities.values().copied().fold(0.0, f64::min) - LN_2;
+
+        Self {
+            log_probabilities,
+            unseen_word_log_probability,
+        }
+    }
+
+    pub(crate) fn from_json(
+        json: &str,
+        is_accent_insensitive_mode_enabled: bool,
+    ) -> Result<Self, ModelLoadError> {
+        let relative_frequencies = TrainingDataLanguageModel::from_json(json)?;
+
+        if !is_accent_insensitive_mode_enabled {
+            return Ok(Self::new(relative_frequencies));
+        }
+
+        let mut folded_frequencies = AHashMap::new();
+        for (word, probability) in relative_frequencies {
+            *folded_frequencies
+                .entry(CompactString::from(fold_diacritics(&word)))
+                .or_insert(0.0) += probability;
+        }
+        Ok(Self::new(folded_frequencies))
+    }
+
+    pub(crate) fn write_snapshot<W: Write>(
+        &self,
+        writer: &mut SnapshotWriter<W>,
+    ) -> io::Result<()> {
+        writer.write_log_probabilities(
+            &self
+                .log_probabilities
+                .iter()
+                .map(|(word, log_probability)| (word.as_str(), *log_probability))
+                .collect_vec(),
+        )
+    }
+
+    pub(crate) fn read_snapshot<R: Read>(reader: &mut SnapshotReader<R>) -> io::Result<Self> {
+        Ok(Self::from_log_probabilities(
+            reader.read_log_probabilities()?.into_iter().collect(),
+        ))
+    }
+
+    pub(crate) fn log_probability(&self, word: &str) -> f64 {
+        self.log_probabilities
+            .get(word)
+            .copied()
+            .unwrap_or(self.unseen_word_log_probability)
+    }
+}
+
 pub(crate) struct TestDataLanguageModel<'a> {
-    pub(crate) ngrams: Vec<Vec<NgramRef<'a>>>,
+    /// The distinct ngrams of the text in ascending order. Their lower-order ngrams
+    /// are derived from them while looking them up instead of being stored as well.
+    pub(crate) ngrams: Vec<NgramRef<'a>>,
 }
 
 impl<'a> TestDataLanguageModel<'a> {
-    pub(crate) fn from(words: &'a [String], ngram_length: usize) -> Self {
-        if !(1..6).contains(&ngram_length) {
-            panic!("ngram length {ngram_length} is not in range 1..6");
+    pub(crate) fn from(words: &[&'a str], ngram_length: usize) -> Self {
+        if !(1..=MAXIMUM_NGRAM_LENGTH).contains(&ngram_length) {
+            panic!("ngram length {ngram_length} is not in range 1..={MAXIMUM_NGRAM_LENGTH}");
         }
 
-        let mut ngrams = hashset!();
+        let mut ngrams = vec![];
 
         for word in words.iter() {
             let chars_count = word.chars().count();
@@ -171,23 +477,30 @@ impl<'a> TestDataLanguageModel<'a> {
             if chars_count >= ngram_length {
                 for i in 0..=chars_count - ngram_length {
                     let slice = get_utf8_slice(word, i, i + ngram_length);
-                    ngrams.insert(NgramRef::new(slice));
+                    if !is_word_boundary_marker(slice) {
+                        ngrams.push(NgramRef::new(slice));
+                    }
                 }
             }
         }
 
-        let mut lower_order_ngrams = Vec::with_capacity(ngrams.len());
-
-        for ngram in ngrams {
-            lower_order_ngrams.push(ngram.range_of_lower_order_ngrams().collect_vec());
-        }
+        ngrams.sort_unstable_by_key(|ngram| ngram.value);
+        ngrams.dedup();
 
-        Self {
-            ngrams: lower_order_ngrams,
-        }
+        Self { ngrams }
     }
 }
 
+/// Surrounds the word with markers so that ngrams at the start or the end of a word
+/// can be distinguished from the same ngrams inside of it.
+pub(crate) fn mark_word_boundaries(word: &str) -> String {
+    format!("{WORD_START_MARKER}{word}{WORD_END_MARKER}")
+}
+
+fn is_word_boundary_marker(ngram: &str) -> bool {
+    ngram.len() == 1 && ngram.starts_with([WORD_START_MARKER, WORD_END_MARKER])
+}
+
 fn get_utf8_slice(string: &str, start: usize, end: usize) -> &str {
     string
         .char_indices()
@@ -224,6 +537,8 @@ mod tests {
         fn test_json_model_serializer_and_deserializer() {
             let model = JsonLanguageModel {
                 language: Language::English,
+                version: None,
+                checksum: None,
                 ngrams: btreemap!(Fraction::new(3, 5) => "a b c d e".to_string()),
             };
 
@@ -236,6 +551,75 @@ mod tests {
             let deserialized = serde_json::from_str::<JsonLanguageModel>(&serialized).unwrap();
             assert_eq!(deserialized, model);
         }
+
+        #[test]
+        fn test_json_ngram_counts_serializer_and_deserializer() {
+            let counts = JsonNgramCounts {
+                language: Language::English,
+                version: None,
+                checksum: None,
+                ngrams: btreemap!(2 => "c d".to_string(), 12 => "a b".to_string()),
+            };
+
+            let serialized = serde_json::to_string(&counts).unwrap();
+            assert_eq!(
+                serialized,
+                r#"{"language":"ENGLISH","ngrams":{"2":"c d","12":"a b"}}"#
+            );
+
+            let deserialized = serde_json::from_str::<JsonNgramCounts>(&serialized).unwrap();
+            assert_eq!(deserialized, counts);
+        }
+
+        #[test]
+        fn assert_json_model_with_metadata_is_parsed() {
+            let model = JsonLanguageModel::new(
+                Language::English,
+                btreemap!(Fraction::new(3, 5) => "a b c d e".to_string()),
+            );
+            let serialized = serde_json::to_string(&model).unwrap();
+            assert_eq!(JsonLanguageModel::parse(&serialized), Ok(model));
+
+            let counts = JsonNgramCounts::new(
+                Language::English,
+                btreemap!(2 => "c d".to_string(), 12 => "a b".to_string()),
+            );
+            let serialized = serde_json::to_string(&counts).unwrap();
+            assert_eq!(JsonNgramCounts::parse(&serialized), Ok(counts));
+        }
+
+        #[test]
+        fn assert_json_model_without_metadata_is_parsed() {
+            let result = TrainingDataLanguageModel::from_json(
+                r#"{"language":"ENGLISH","ngrams":{"1/2":"a b"}}"#,
+            );
+            assert_eq!(result.map(|model| model.len()), Ok(2));
+        }
+
+        #[test]
+        fn assert_json_model_with_wrong_checksum_is_rejected() {
+            let result = TrainingDataLanguageModel::from_json(
+                r#"{"language":"ENGLISH","version":1,"checksum":"0000000000000000","ngrams":{"1/2":"a b"}}"#,
+            );
+            assert!(matches!(
+                result,
+                Err(ModelLoadError::ChecksumMismatch { expected, .. }) if expected == "0000000000000000"
+            ));
+        }
+
+        #[test]
+        fn assert_json_model_with_unsupported_version_is_rejected() {
+            let result = TrainingDataLanguageModel::counts_from_json(
+                r#"{"language":"ENGLISH","version":2,"ngrams":{"1":"a b"}}"#,
+            );
+            assert_eq!(result, Err(ModelLoadError::UnsupportedVersion(2)));
+        }
+
+        #[test]
+        fn assert_invalid_json_model_is_rejected() {
+            let result = TrainingDataLanguageModel::from_json(r#"{"language":"ENGLISH","ngr"#);
+            assert!(matches!(result, Err(ModelLoadError::Corrupt(_))));
+        }
     }
 
     mod training_data {
@@ -487,22 +871,101 @@ mod tests {
                 absolute_frequencies: None,
                 relative_frequencies: Some(expected_unigram_relative_frequencies()),
             };
-            let deserialized = TrainingDataLanguageModel::from_json(&model.to_json());
+            let deserialized = TrainingDataLanguageModel::from_json(&model.to_json()).unwrap();
             assert_eq!(deserialized, expected_unigram_json_relative_frequencies());
         }
+
+        fn map_keys_to_compact_strings(map: HashMap<&str, f64>) -> AHashMap<CompactString, f64> {
+            map.into_iter()
+                .map(|(key, value)| (CompactString::new(key), value))
+                .collect()
+        }
+
+        #[test]
+        fn test_diacritics_folding_of_unigram_model() {
+            let unigrams =
+                map_keys_to_compact_strings(hashmap!("a" => 0.5, "á" => 0.25, "b" => 0.25));
+            let folded = TrainingDataLanguageModel::fold_diacritics(&[unigrams]);
+            assert_eq!(
+                folded,
+                map_keys_to_compact_strings(hashmap!("a" => 0.75, "b" => 0.25))
+            );
+        }
+
+        #[test]
+        fn test_diacritics_folding_of_bigram_model() {
+            let unigrams = map_keys_to_compact_strings(hashmap!("a" => 0.5, "á" => 0.5));
+            let bigrams =
+                map_keys_to_compact_strings(hashmap!("ab" => 0.5, "ac" => 0.5, "áb" => 1.0));
+            let folded = TrainingDataLanguageModel::fold_diacritics(&[unigrams, bigrams]);
+            assert_eq!(
+                folded,
+                map_keys_to_compact_strings(hashmap!("ab" => 0.75, "ac" => 0.25))
+            );
+        }
+
+        #[test]
+        fn assert_capital_i_is_folded_in_turkish_training_data() {
+            let model = TrainingDataLanguageModel::from_text(
+                &["İzmir IŞIK"],
+                &Language::Turkish,
+                2,
+                "\\p{L}&&\\p{Latin}",
+                &hashmap!(),
+            );
+            assert_eq!(
+                model.absolute_frequencies,
+                Some(map_keys_to_ngrams(hashmap!(
+                    "iz" => 1, "zm" => 1, "mi" => 1, "ir" => 1, "ış" => 1, "şı" => 1, "ık" => 1
+                )))
+            );
+        }
+
+        #[test]
+        fn assert_unseen_words_are_less_likely_than_rare_words() {
+            let model = WordModel::from_json(
+                r#"{"language":"ENGLISH","ngrams":{"1/4":"the","1/8":"cät"}}"#,
+                true,
+            )
+            .unwrap();
+            assert_eq!(model.log_probability("the"), 0.25f64.ln());
+            assert_eq!(model.log_probability("cat"), 0.125f64.ln());
+            assert_eq!(model.log_probability("cät"), 0.0625f64.ln());
+        }
+
+        #[test]
+        fn assert_word_model_is_serialized_to_json() {
+            let model = TrainingDataWordModel::from_words(
+                "the cat saw the other cat and the dog".split(' '),
+                &Language::English,
+            );
+            assert_eq!(
+                model.to_json(),
+                r#"{"language":"ENGLISH","version":1,"checksum":"92bf7a75d4726344","ngrams":{"1/3":"the","1/9":"and dog other saw","2/9":"cat"}}"#
+            );
+        }
     }
 
     mod test_data {
         use crate::detector::split_text_into_words;
+        use crate::tokenizer::DefaultTokenizer;
 
         use super::*;
 
-        fn map_strs_to_ngrams(strs: Vec<Vec<&'static str>>) -> Vec<Vec<NgramRef>> {
+        fn map_strs_to_ngrams(strs: Vec<Vec<&'static str>>) -> Vec<Vec<NgramRef<'static>>> {
             strs.iter()
                 .map(|ngram_strs| ngram_strs.iter().map(|&it| NgramRef::new(it)).collect())
                 .collect()
         }
 
+        fn lower_order_ngrams_of<'a>(model: &TestDataLanguageModel<'a>) -> Vec<Vec<NgramRef<'a>>> {
+            model
+                .ngrams
+                .iter()
+                .map(|ngram| ngram.range_of_lower_order_ngrams().collect())
+                .collect()
+        }
+
         #[fixture]
         fn expected_unigrams() -> Vec<Vec<NgramRef<'static>>> {
             map_strs_to_ngrams(vec![
@@ -733,12 +1196,34 @@ mod tests {
             case::fivegram_model(5, expected_fivegrams())
         )]
         fn test_ngram_model_creation(ngram_length: usize, expected_ngrams: Vec<Vec<NgramRef>>) {
-            let words = split_text_into_words(TEXT);
-            let mut model = TestDataLanguageModel::from(&words, ngram_length);
-            model
-                .ngrams
-                .sort_by(|first, second| first[0].value.cmp(&second[0].value));
-            assert_eq!(model.ngrams, expected_ngrams);
+            let words = split_text_into_words(TEXT, &[], &DefaultTokenizer);
+            let words = words.iter().map(String::as_str).collect_vec();
+            let model = TestDataLanguageModel::from(&words, ngram_length);
+            assert_eq!(lower_order_ngrams_of(&model), expected_ngrams);
+        }
+
+        #[test]
+        fn test_ngram_model_creation_with_word_boundaries() {
+            let word = mark_word_boundaries("sing");
+            let words = vec![word.as_str()];
+
+            let unigram_model = TestDataLanguageModel::from(&words, 1);
+            assert_eq!(
+                lower_order_ngrams_of(&unigram_model),
+                map_strs_to_ngrams(vec![vec!["g"], vec!["i"], vec!["n"], vec!["s"]])
+            );
+
+            let bigram_model = TestDataLanguageModel::from(&words, 2);
+            assert_eq!(
+                lower_order_ngrams_of(&bigram_model),
+                map_strs_to_ngrams(vec![
+                    vec!["<s", "<"],
+                    vec!["g>", "g"],
+                    vec!["in", "i"],
+                    vec!["ng", "n"],
+                    vec!["si", "s"],
+                ])
+            );
         }
     }
 }
@@ -20,6 +20,9 @@ use std::fmt::Display;
 use serde::de::{Error, Visitor};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
+/// The highest ngram order for which language models can be created and used.
+pub(crate) const MAXIMUM_NGRAM_LENGTH: usize = 8;
+
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub(crate) struct Ngram {
     pub(crate) value: String,
@@ -28,8 +31,10 @@ pub(crate) struct Ngram {
 impl Ngram {
     pub(crate) fn new(value: &str) -> Self {
         let char_count = value.chars().count();
-        if !(0..6).contains(&char_count) {
-            panic!("length {char_count} of ngram '{value}' is not in range 0..6");
+        if char_count > MAXIMUM_NGRAM_LENGTH {
+            panic!(
+                "length {char_count} of ngram '{value}' is not in range 0..={MAXIMUM_NGRAM_LENGTH}"
+            );
         }
         Self {
             value: value.to_string(),
@@ -43,7 +48,10 @@ impl Ngram {
             3 => "trigram",
             4 => "quadrigram",
             5 => "fivegram",
-            _ => panic!("ngram length {ngram_length} is not in range 1..6"),
+            6 => "sixgram",
+            7 => "sevengram",
+            8 => "eightgram",
+            _ => panic!("ngram length {ngram_length} is not in range 1..={MAXIMUM_NGRAM_LENGTH}"),
         }
     }
 }
@@ -89,8 +97,10 @@ pub(crate) struct NgramRef<'a> {
 impl<'a> NgramRef<'a> {
     pub(crate) fn new(value: &'a str) -> Self {
         let char_count = value.chars().count();
-        if !(0..6).contains(&char_count) {
-            panic!("length {char_count} of ngram '{value}' is not in range 0..6");
+        if char_count > MAXIMUM_NGRAM_LENGTH {
+            panic!(
+                "length {char_count} of ngram '{value}' is not in range 0..={MAXIMUM_NGRAM_LENGTH}"
+            );
         }
         Self { value, char_count }
     }
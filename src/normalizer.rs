@@ -0,0 +1,403 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::borrow::Cow;
+
+use strum_macros::{EnumIter, EnumString};
+use unicode_normalization::char::canonical_combining_class;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::constant::MARKUP;
+
+/// This enum specifies the text normalization steps which can be applied
+/// to input text before it is split into words.
+///
+/// The normalizers are applied in the order in which they are passed to
+/// [`LanguageDetectorBuilder::with_normalizers`](crate::LanguageDetectorBuilder::with_normalizers)
+/// or [`LanguageModelFilesWriter::with_normalizers`](crate::LanguageModelFilesWriter::with_normalizers).
+#[derive(Clone, Copy, Debug, EnumIter, EnumString, Eq, PartialEq, Hash, Ord, PartialOrd)]
+#[strum(ascii_case_insensitive)]
+#[cfg_attr(
+    feature = "python",
+    pyo3::prelude::pyclass(eq, eq_int, frozen, hash, ord, rename_all = "SCREAMING_SNAKE_CASE")
+)]
+pub enum Normalizer {
+    /// Converts the text to Unicode Normalization Form C (canonical composition).
+    Nfc,
+
+    /// Converts the text to Unicode Normalization Form KC (compatibility composition).
+    Nfkc,
+
+    /// Removes zero-width characters, such as the zero-width space or the byte order mark,
+    /// and bidirectional control characters. Note that this includes the zero-width non-joiner
+    /// which is used within words in *Persian*.
+    RemoveInvisibleCharacters,
+
+    /// Converts fullwidth ASCII variants and the ideographic space to their halfwidth forms.
+    FoldFullwidthCharacters,
+
+    /// Reduces each run of three or more identical letters to two letters
+    /// and removes the Arabic tatweel, so that `sooooo` becomes `soo`.
+    SquashElongation,
+
+    /// Removes all numeric characters.
+    StripNumerals,
+
+    /// Masks HTML tags and entities, Markdown code, URLs, e-mail addresses, @mentions and emoji
+    /// with a single space each, and splits camel-cased #hashtags into separate words, so that
+    /// `#ThrowbackThursday` becomes `Throwback Thursday`.
+    CleanMarkup,
+}
+
+impl Normalizer {
+    fn apply(&self, chars: Vec<(char, usize)>) -> Vec<(char, usize)> {
+        match self {
+            Normalizer::Nfc => compose(chars, |segment| segment.nfc().collect()),
+            Normalizer::Nfkc => compose(chars, |segment| segment.nfkc().collect()),
+            Normalizer::RemoveInvisibleCharacters => chars
+                .into_iter()
+                .filter(|(ch, _)| !is_invisible_character(*ch))
+                .collect(),
+            Normalizer::FoldFullwidthCharacters => chars
+                .into_iter()
+                .map(|(ch, offset)| (fold_fullwidth_character(ch), offset))
+                .collect(),
+            Normalizer::SquashElongation => squash_elongation(chars),
+            Normalizer::StripNumerals => chars
+                .into_iter()
+                .filter(|(ch, _)| !ch.is_numeric())
+                .collect(),
+            Normalizer::CleanMarkup => clean_markup(chars),
+        }
+    }
+}
+
+/// A normalized text which remembers the byte offsets of its characters
+/// within the original text.
+pub(crate) struct NormalizedText {
+    pub(crate) text: String,
+    offsets: Vec<usize>,
+}
+
+impl NormalizedText {
+    pub(crate) fn new(text: &str, normalizers: &[Normalizer]) -> Self {
+        let mut chars = text
+            .char_indices()
+            .map(|(i, ch)| (ch, i))
+            .collect::<Vec<_>>();
+
+        for normalizer in normalizers {
+            chars = normalizer.apply(chars);
+        }
+
+        let mut normalized_text = String::with_capacity(text.len());
+        let mut offsets = Vec::with_capacity(text.len() + 1);
+
+        for (i, (ch, offset)) in chars.into_iter().enumerate() {
+            let offset = if i == 0 { 0 } else { offset };
+            normalized_text.push(ch);
+            offsets.resize(normalized_text.len(), offset);
+        }
+
+        offsets.push(text.len());
+
+        Self {
+            text: normalized_text,
+            offsets,
+        }
+    }
+
+    /// Converts a byte index of the normalized text to the respective
+    /// byte index of the original text.
+    pub(crate) fn original_index(&self, index: usize) -> usize {
+        self.offsets[index]
+    }
+}
+
+/// Applies the given normalizers to the text, borrowing the text if there are none.
+pub(crate) fn normalize<'a>(text: &'a str, normalizers: &[Normalizer]) -> Cow<'a, str> {
+    if normalizers.is_empty() {
+        Cow::Borrowed(text)
+    } else {
+        Cow::Owned(NormalizedText::new(text, normalizers).text)
+    }
+}
+
+/// Normalizes each starter character together with its subsequent combining
+/// characters, assigning the offset of the starter to all resulting characters.
+fn compose(
+    chars: Vec<(char, usize)>,
+    normalize_segment: impl Fn(&str) -> String,
+) -> Vec<(char, usize)> {
+    let mut result = Vec::with_capacity(chars.len());
+    let mut segment = String::new();
+    let mut segment_offset = 0;
+
+    for (ch, offset) in chars {
+        if canonical_combining_class(ch) == 0 && !segment.is_empty() {
+            result.extend(
+                normalize_segment(&segment)
+                    .chars()
+                    .map(|it| (it, segment_offset)),
+            );
+            segment.clear();
+        }
+        if segment.is_empty() {
+            segment_offset = offset;
+        }
+        segment.push(ch);
+    }
+
+    if !segment.is_empty() {
+        result.extend(
+            normalize_segment(&segment)
+                .chars()
+                .map(|it| (it, segment_offset)),
+        );
+    }
+
+    result
+}
+
+fn is_invisible_character(ch: char) -> bool {
+    matches!(
+        ch,
+        '\u{061C}'
+            | '\u{200B}'..='\u{200F}'
+            | '\u{202A}'..='\u{202E}'
+            | '\u{2060}'
+            | '\u{2066}'..='\u{2069}'
+            | '\u{FEFF}'
+    )
+}
+
+fn fold_fullwidth_character(ch: char) -> char {
+    match ch {
+        '\u{FF01}'..='\u{FF5E}' => char::from_u32(ch as u32 - 0xFEE0).unwrap(),
+        '\u{3000}' => ' ',
+        _ => ch,
+    }
+}
+
+fn squash_elongation(chars: Vec<(char, usize)>) -> Vec<(char, usize)> {
+    let mut result: Vec<(char, usize)> = Vec::with_capacity(chars.len());
+
+    for (ch, offset) in chars {
+        if ch == '\u{0640}' {
+            continue;
+        }
+        if ch.is_alphabetic() && result.len() >= 2 {
+            let (last, second_last) = (result[result.len() - 1].0, result[result.len() - 2].0);
+            if last == ch && second_last == ch {
+                continue;
+            }
+        }
+        result.push((ch, offset));
+    }
+
+    result
+}
+
+fn clean_markup(chars: Vec<(char, usize)>) -> Vec<(char, usize)> {
+    let text = chars.iter().map(|(ch, _)| ch).collect::<String>();
+    let byte_indices = text.char_indices().map(|(i, _)| i).collect::<Vec<_>>();
+    let char_index_of = |byte_index: usize| byte_indices.partition_point(|&i| i < byte_index);
+
+    let mut result = Vec::with_capacity(chars.len());
+    let mut last_char_index = 0;
+
+    for captures in MARKUP.captures_iter(&text) {
+        let span = captures.get(0).unwrap();
+        let start_char_index = char_index_of(span.start());
+        let end_char_index = char_index_of(span.end());
+
+        result.extend_from_slice(&chars[last_char_index..start_char_index]);
+
+        match captures.name("hashtag") {
+            Some(hashtag) => {
+                let hashtag_start = char_index_of(hashtag.start());
+                result.extend(split_camel_case(&chars[hashtag_start..end_char_index]));
+            }
+            None => result.push((' ', chars[start_char_index].1)),
+        }
+
+        last_char_index = end_char_index;
+    }
+
+    result.extend_from_slice(&chars[last_char_index..]);
+    result
+}
+
+/// Replaces underscores with spaces and inserts a space before each uppercase letter
+/// which starts a new word, such as in `throwbackThursday` or `HTMLParser`.
+fn split_camel_case(chars: &[(char, usize)]) -> Vec<(char, usize)> {
+    let mut result = Vec::with_capacity(chars.len() + 4);
+
+    for (i, &(ch, offset)) in chars.iter().enumerate() {
+        if ch == '_' {
+            result.push((' ', offset));
+            continue;
+        }
+        if i > 0 && ch.is_uppercase() {
+            let previous = chars[i - 1].0;
+            let next = chars.get(i + 1).map(|(it, _)| *it);
+            if previous.is_lowercase()
+                || (previous.is_uppercase() && next.is_some_and(|it| it.is_lowercase()))
+            {
+                result.push((' ', offset));
+            }
+        }
+        result.push((ch, offset));
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use rstest::*;
+
+    use super::*;
+
+    #[rstest(
+        text,
+        normalizer,
+        expected_text,
+        case("e\u{301}te\u{301}", Normalizer::Nfc, "été"),
+        case("ﬁnal ①", Normalizer::Nfkc, "final 1"),
+        case(
+            "hel\u{200B}lo\u{200F} wo\u{FEFF}rld",
+            Normalizer::RemoveInvisibleCharacters,
+            "hello world"
+        ),
+        case(
+            "Ｈｅｌｌｏ\u{3000}ｗｏｒｌｄ！",
+            Normalizer::FoldFullwidthCharacters,
+            "Hello world!"
+        ),
+        case("sooooo coool", Normalizer::SquashElongation, "soo cool"),
+        case("جميــــل", Normalizer::SquashElongation, "جميل"),
+        case("abc123 ٤٥٦def", Normalizer::StripNumerals, "abc def"),
+        case(
+            "<p>Hallo&nbsp;<b>Welt</b></p>",
+            Normalizer::CleanMarkup,
+            " Hallo  Welt  "
+        ),
+        case(
+            "see https://example.com/path?q=1 or www.example.org",
+            Normalizer::CleanMarkup,
+            "see   or  "
+        ),
+        case(
+            "mail john.doe+news@example.co.uk now",
+            Normalizer::CleanMarkup,
+            "mail   now"
+        ),
+        case("thanks @jane_doe!", Normalizer::CleanMarkup, "thanks  !"),
+        case(
+            "#ThrowbackThursday #HTMLParser #snake_case",
+            Normalizer::CleanMarkup,
+            "Throwback Thursday HTML Parser snake case"
+        ),
+        case("great 👍🏽 party 🇩🇪 ❤️", Normalizer::CleanMarkup, "great   party    "),
+        case(
+            "run `cargo build` or ```\nlet x = 1;\n``` now",
+            Normalizer::CleanMarkup,
+            "run   or   now"
+        ),
+        case(
+            "<script>var x = 'text';</script>Bonjour",
+            Normalizer::CleanMarkup,
+            " Bonjour"
+        ),
+        case(
+            "C# and issue#42 stay",
+            Normalizer::CleanMarkup,
+            "C# and issue#42 stay"
+        )
+    )]
+    fn test_normalizer(text: &str, normalizer: Normalizer, expected_text: &str) {
+        assert_eq!(normalize(text, &[normalizer]), expected_text);
+    }
+
+    #[test]
+    fn assert_text_is_borrowed_without_normalizers() {
+        assert!(matches!(normalize("text", &[]), Cow::Borrowed("text")));
+    }
+
+    #[test]
+    fn assert_normalizers_are_applied_in_order() {
+        let text = "ｓｏoooo";
+        assert_eq!(
+            normalize(text, &[Normalizer::SquashElongation, Normalizer::Nfkc]),
+            "sooo"
+        );
+        assert_eq!(
+            normalize(text, &[Normalizer::Nfkc, Normalizer::SquashElongation]),
+            "soo"
+        );
+    }
+
+    #[test]
+    fn assert_original_indices_are_preserved() {
+        let text = "\u{200B}wo\u{200B}rd sooooo e\u{301}t";
+        let normalized_text = NormalizedText::new(
+            text,
+            &[
+                Normalizer::RemoveInvisibleCharacters,
+                Normalizer::SquashElongation,
+                Normalizer::Nfc,
+            ],
+        );
+
+        assert_eq!(normalized_text.text, "word soo ét");
+        assert_eq!(normalized_text.original_index(0), 0);
+        assert_eq!(normalized_text.original_index(2), 8);
+        assert_eq!(normalized_text.original_index(4), 10);
+        assert_eq!(normalized_text.original_index(8), 17);
+        assert_eq!(normalized_text.original_index(9), 18);
+        assert_eq!(normalized_text.original_index(11), 21);
+        assert_eq!(normalized_text.original_index(12), text.len());
+    }
+
+    #[test]
+    fn assert_original_indices_are_preserved_for_cleaned_markup() {
+        let text = "<i>#CamelCase</i> 😀x";
+        let normalized_text = NormalizedText::new(text, &[Normalizer::CleanMarkup]);
+
+        assert_eq!(normalized_text.text, " Camel Case   x");
+        assert_eq!(normalized_text.original_index(1), 4);
+        assert_eq!(normalized_text.original_index(6), 9);
+        assert_eq!(normalized_text.original_index(7), 9);
+        assert_eq!(normalized_text.original_index(11), 13);
+        assert_eq!(normalized_text.original_index(12), 17);
+        assert_eq!(normalized_text.original_index(13), 18);
+        assert_eq!(normalized_text.original_index(14), 22);
+        assert_eq!(normalized_text.original_index(15), text.len());
+    }
+
+    #[test]
+    fn assert_normalizer_can_be_parsed_from_string() {
+        assert_eq!(Normalizer::from_str("nfkc"), Ok(Normalizer::Nfkc));
+        assert_eq!(
+            Normalizer::from_str("SquashElongation"),
+            Ok(Normalizer::SquashElongation)
+        );
+        assert!(Normalizer::from_str("unknown").is_err());
+    }
+}
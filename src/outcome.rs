@@ -0,0 +1,86 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::alphabet::Alphabet;
+use crate::language::Language;
+
+/// This enum describes the outcome of detecting the language of a text.
+///
+/// Unlike the [`Option`] returned by
+/// [`LanguageDetector::detect_language_of`](crate::LanguageDetector::detect_language_of),
+/// it tells text whose language cannot be reliably decided apart from text which is
+/// written in an alphabet that none of the languages of the detector uses.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum DetectionOutcome {
+    /// The language of the text has been detected.
+    Detected(Language),
+    /// The text is written in an alphabet of the detector's languages,
+    /// but its language cannot be reliably decided between them.
+    Undecided,
+    /// The text is written in an alphabet which none of the detector's languages uses.
+    UnsupportedAlphabet(Alphabet),
+}
+
+impl DetectionOutcome {
+    /// Returns the detected language, or [`None`] if no language has been detected.
+    pub fn language(&self) -> Option<Language> {
+        match self {
+            DetectionOutcome::Detected(language) => Some(*language),
+            _ => None,
+        }
+    }
+
+    /// Returns the alphabet of the text if none of the languages of the detector uses it,
+    /// otherwise [`None`].
+    pub fn unsupported_alphabet(&self) -> Option<Alphabet> {
+        match self {
+            DetectionOutcome::UnsupportedAlphabet(alphabet) => Some(*alphabet),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::Language::English;
+
+    #[test]
+    fn assert_language_is_only_returned_if_detected() {
+        assert_eq!(
+            DetectionOutcome::Detected(English).language(),
+            Some(English)
+        );
+        assert_eq!(DetectionOutcome::Undecided.language(), None);
+        assert_eq!(
+            DetectionOutcome::UnsupportedAlphabet(Alphabet::Kannada).language(),
+            None
+        );
+    }
+
+    #[test]
+    fn assert_alphabet_is_only_returned_if_unsupported() {
+        assert_eq!(
+            DetectionOutcome::UnsupportedAlphabet(Alphabet::Kannada).unsupported_alphabet(),
+            Some(Alphabet::Kannada)
+        );
+        assert_eq!(DetectionOutcome::Undecided.unsupported_alphabet(), None);
+        assert_eq!(
+            DetectionOutcome::Detected(English).unsupported_alphabet(),
+            None
+        );
+    }
+}
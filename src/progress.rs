@@ -0,0 +1,39 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+/// This struct describes how far the language models of a detector
+/// have been preloaded.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "python", pyo3::prelude::pyclass(eq, frozen))]
+pub struct PreloadingProgress {
+    pub(crate) loaded_language_count: usize,
+    pub(crate) total_language_count: usize,
+}
+
+impl PreloadingProgress {
+    /// Returns the number of languages whose models have been preloaded.
+    pub fn loaded_language_count(&self) -> usize {
+        self.loaded_language_count
+    }
+    /// Returns the number of languages whose models are preloaded in total.
+    pub fn total_language_count(&self) -> usize {
+        self.total_language_count
+    }
+    /// Returns `true` if the models of all languages have been preloaded.
+    pub fn is_complete(&self) -> bool {
+        self.loaded_language_count == self.total_language_count
+    }
+}
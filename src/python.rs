@@ -17,34 +17,64 @@
 use std::any::Any;
 use std::collections::HashSet;
 use std::io;
-use std::panic;
+use std::panic::{self, AssertUnwindSafe};
 use std::path::PathBuf;
+use std::str::FromStr;
 
 use pyo3::exceptions::{PyException, PyValueError};
 use pyo3::prelude::*;
 use pyo3::types::{PyTuple, PyType};
+use strum::IntoEnumIterator;
 
+use crate::alphabet::Alphabet;
 use crate::builder::{
-    LanguageDetectorBuilder, MINIMUM_RELATIVE_DISTANCE_MESSAGE, MISSING_LANGUAGE_MESSAGE,
+    is_valid_add_k_constant, is_valid_kneser_ney_discount, is_valid_models_directory,
+    is_valid_ngram_length_range, is_valid_word_model_weight, LanguageDetectorBuilder,
+    ADD_K_SMOOTHING_MESSAGE, KNESER_NEY_SMOOTHING_MESSAGE, LANGUAGE_VARIANTS_DIRECTORY_MESSAGE,
+    MACRO_LANGUAGES_MESSAGE, MEMORY_BUDGET_MESSAGE, MINIMUM_RELATIVE_DISTANCE_MESSAGE,
+    MISSING_LANGUAGE_MESSAGE, NGRAM_LENGTHS_MESSAGE, PAIR_DISCRIMINATORS_DIRECTORY_MESSAGE,
+    WORD_MODEL_WEIGHT_MESSAGE,
 };
+use crate::chinese::ChineseScript;
 use crate::convert_byte_indices_to_char_indices;
+use crate::custom::CustomLanguage;
 use crate::detector::LanguageDetector;
 use crate::isocode::{IsoCode639_1, IsoCode639_3};
 use crate::language::Language;
+use crate::macrolanguage::{CollapsedLanguage, MacroLanguage};
+use crate::ngram::MAXIMUM_NGRAM_LENGTH;
+use crate::normalizer::Normalizer;
+use crate::outcome::DetectionOutcome;
+use crate::progress::PreloadingProgress;
 use crate::result::DetectionResult;
-use crate::writer::{LanguageModelFilesWriter, TestDataFilesWriter};
+use crate::smoothing::Smoothing;
+use crate::status::{ModelKind, ModelLoadFailure, ModelStatus};
+use crate::variant::LanguageVariant;
+use crate::writer::{LanguageModelFilesWriter, TestDataFilesWriter, MAXIMUM_NGRAM_LENGTH_MESSAGE};
 
 #[pymodule]
 fn lingua(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Alphabet>()?;
+    m.add_class::<ChineseScript>()?;
     m.add_class::<ConfidenceValue>()?;
+    m.add_class::<PyDetectionOutcome>()?;
     m.add_class::<DetectionResult>()?;
-    m.add_class::<IsoCode639_1>()?;
-    m.add_class::<IsoCode639_3>()?;
-    m.add_class::<Language>()?;
+    m.add_class::<PyIsoCode639_1>()?;
+    m.add_class::<PyIsoCode639_3>()?;
+    m.add_class::<PyLanguage>()?;
     m.add_class::<LanguageDetectorBuilder>()?;
     m.add_class::<LanguageDetector>()?;
     m.add_class::<LanguageModelFilesWriter>()?;
+    m.add_class::<MacroLanguage>()?;
+    m.add_class::<ModelKind>()?;
+    m.add_class::<ModelLoadFailure>()?;
+    m.add_class::<ModelStatus>()?;
+    m.add_class::<Normalizer>()?;
+    m.add_class::<PreloadingProgress>()?;
     m.add_class::<TestDataFilesWriter>()?;
+    for language in Language::iter() {
+        add_class_attributes(m.py(), language)?;
+    }
     Ok(())
 }
 
@@ -101,6 +131,62 @@ impl ConfidenceValue {
     }
 }
 
+/// This class describes the outcome of detecting the language of a text.
+///
+/// Attributes:
+///
+///     language (Optional[Language]):
+///         The detected language or None if no language has been detected.
+///
+///     unsupported_alphabet (Optional[Alphabet]):
+///         The alphabet of the text if none of the languages of the detector
+///         uses it, otherwise None.
+///
+///     is_undecided (bool):
+///         Whether the text is written in an alphabet of the languages of the
+///         detector, but its language cannot be reliably detected.
+#[pyclass(name = "DetectionOutcome", frozen)]
+struct PyDetectionOutcome {
+    outcome: DetectionOutcome,
+}
+
+#[pymethods]
+impl PyDetectionOutcome {
+    fn __repr__(&self) -> String {
+        match self.outcome {
+            DetectionOutcome::Detected(language) => format!(
+                "DetectionOutcome(language=Language.{})",
+                language.to_string().to_uppercase()
+            ),
+            DetectionOutcome::Undecided => "DetectionOutcome(undecided)".to_string(),
+            DetectionOutcome::UnsupportedAlphabet(alphabet) => format!(
+                "DetectionOutcome(unsupported_alphabet=Alphabet.{})",
+                alphabet.to_string().to_uppercase()
+            ),
+        }
+    }
+
+    /// Return the detected language or None if no language has been detected.
+    #[getter]
+    fn language(&self) -> Option<Language> {
+        self.outcome.language()
+    }
+
+    /// Return the alphabet of the text if none of the languages of the
+    /// detector uses it, otherwise None.
+    #[getter]
+    fn unsupported_alphabet(&self) -> Option<Alphabet> {
+        self.outcome.unsupported_alphabet()
+    }
+
+    /// Return whether the language of the text cannot be reliably detected
+    /// although it is written in an alphabet of the languages of the detector.
+    #[getter]
+    fn is_undecided(&self) -> bool {
+        self.outcome == DetectionOutcome::Undecided
+    }
+}
+
 #[pymethods]
 impl DetectionResult {
     #[new]
@@ -164,63 +250,262 @@ impl DetectionResult {
 }
 
 #[pymethods]
-impl IsoCode639_1 {
+impl PreloadingProgress {
+    fn __repr__(&self) -> String {
+        format!(
+            "PreloadingProgress(loaded_language_count={}, total_language_count={})",
+            self.loaded_language_count, self.total_language_count
+        )
+    }
+
+    /// Return the number of languages whose models have been preloaded.
+    #[pyo3(name = "loaded_language_count")]
+    #[getter]
+    fn py_loaded_language_count(&self) -> usize {
+        self.loaded_language_count()
+    }
+
+    /// Return the number of languages whose models are preloaded in total.
+    #[pyo3(name = "total_language_count")]
+    #[getter]
+    fn py_total_language_count(&self) -> usize {
+        self.total_language_count()
+    }
+
+    /// Return `True` if the models of all languages have been preloaded.
+    #[pyo3(name = "is_complete")]
+    fn py_is_complete(&self) -> bool {
+        self.is_complete()
+    }
+}
+
+#[pymethods]
+impl ModelKind {
+    #[getter]
+    fn name(&self) -> String {
+        match self {
+            ModelKind::Ngrams => "NGRAMS",
+            ModelKind::NgramCounts => "NGRAM_COUNTS",
+            ModelKind::Words => "WORDS",
+        }
+        .to_string()
+    }
+}
+
+#[pymethods]
+impl ModelLoadFailure {
+    fn __repr__(&self) -> String {
+        format!(
+            "ModelLoadFailure(language={}, kind={}, ngram_length={}, error={:?})",
+            self.language.to_string().to_uppercase(),
+            self.kind.name(),
+            self.ngram_length,
+            self.error.to_string()
+        )
+    }
+
+    /// Return the language of the model.
+    #[pyo3(name = "language")]
+    #[getter]
+    fn py_language(&self) -> Language {
+        self.language()
+    }
+
+    /// Return the kind of the model.
+    #[pyo3(name = "kind")]
+    #[getter]
+    fn py_kind(&self) -> ModelKind {
+        self.kind()
+    }
+
+    /// Return the ngram length of the model, which is 0 for word models.
+    #[pyo3(name = "ngram_length")]
+    #[getter]
+    fn py_ngram_length(&self) -> usize {
+        self.ngram_length()
+    }
+
+    /// Return the reason why the model could not be loaded.
+    #[pyo3(name = "error")]
+    #[getter]
+    fn py_error(&self) -> String {
+        self.error().to_string()
+    }
+}
+
+#[pymethods]
+impl ModelStatus {
+    fn __repr__(&self) -> String {
+        format!(
+            "ModelStatus(loaded_model_count={}, failure_count={})",
+            self.loaded_models.len(),
+            self.failures.len()
+        )
+    }
+
+    /// Return the languages and ngram lengths of the models which have
+    /// been loaded, sorted by language and ngram length.
+    #[pyo3(name = "loaded_models")]
+    #[getter]
+    fn py_loaded_models(&self) -> Vec<(Language, usize)> {
+        self.loaded_models().to_vec()
+    }
+
+    /// Return the models which have failed to load,
+    /// sorted by language, kind of model and ngram length.
+    #[pyo3(name = "failures")]
+    #[getter]
+    fn py_failures(&self) -> Vec<ModelLoadFailure> {
+        self.failures().to_vec()
+    }
+
+    /// Return `True` if no model has failed to load.
+    #[pyo3(name = "is_healthy")]
+    fn py_is_healthy(&self) -> bool {
+        self.is_healthy()
+    }
+}
+
+#[pymethods]
+impl Alphabet {
+    #[getter]
+    fn name(&self) -> String {
+        self.to_string().to_uppercase()
+    }
+
+    /// Return `True` if this alphabet is used by at least one of the
+    /// languages that have been enabled by cargo features.
+    #[pyo3(name = "is_supported")]
+    fn py_is_supported(&self) -> bool {
+        self.is_supported()
+    }
+}
+
+#[pymethods]
+impl ChineseScript {
     #[getter]
     fn name(&self) -> String {
         self.to_string().to_uppercase()
     }
+
+    /// Return the BCP 47 tag of Chinese written in this script, which is
+    /// zh-Hans or zh-Hant. Text in mixed scripts is tagged as zh without
+    /// a script subtag.
+    #[pyo3(name = "bcp47_tag")]
+    fn py_bcp47_tag(&self) -> &'static str {
+        self.bcp47_tag()
+    }
 }
 
 #[pymethods]
-impl IsoCode639_3 {
+impl MacroLanguage {
     #[getter]
     fn name(&self) -> String {
         self.to_string().to_uppercase()
     }
+
+    /// Return the ISO 639-3 code of this macrolanguage, such as nor for
+    /// Norwegian.
+    #[pyo3(name = "iso_code_639_3")]
+    fn py_iso_code_639_3(&self) -> &'static str {
+        self.iso_code_639_3()
+    }
+
+    /// Return the individual languages which this macrolanguage comprises.
+    #[pyo3(name = "languages")]
+    fn py_languages(&self) -> HashSet<Language> {
+        self.languages()
+    }
+}
+
+/// This class specifies the ISO 639-1 code representations for the
+/// supported languages.
+///
+/// ISO 639 is a standardized nomenclature used to classify languages.
+#[pyclass(name = "IsoCode639_1", eq, frozen, hash, ord)]
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Ord, PartialOrd)]
+struct PyIsoCode639_1(IsoCode639_1);
+
+#[pymethods]
+impl PyIsoCode639_1 {
+    fn __repr__(&self) -> String {
+        format!("IsoCode639_1.{}", self.name())
+    }
+
+    #[getter]
+    fn name(&self) -> String {
+        self.0.to_string().to_uppercase()
+    }
 }
 
+/// This class specifies the ISO 639-3 code representations for the
+/// supported languages.
+///
+/// ISO 639 is a standardized nomenclature used to classify languages.
+#[pyclass(name = "IsoCode639_3", eq, frozen, hash, ord)]
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Ord, PartialOrd)]
+struct PyIsoCode639_3(IsoCode639_3);
+
 #[pymethods]
-impl Language {
+impl PyIsoCode639_3 {
+    fn __repr__(&self) -> String {
+        format!("IsoCode639_3.{}", self.name())
+    }
+
+    #[getter]
+    fn name(&self) -> String {
+        self.0.to_string().to_uppercase()
+    }
+}
+
+/// This class specifies the languages which can be detected by *Lingua*.
+///
+/// Besides the built-in languages, further languages can be registered
+/// at runtime with `Language.register_custom`.
+#[pyclass(name = "Language", eq, frozen, hash, ord)]
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Ord, PartialOrd)]
+struct PyLanguage(Language);
+
+#[pymethods]
+impl PyLanguage {
+    fn __repr__(&self) -> String {
+        format!("Language.{}", self.name())
+    }
+
     /// Return a set of all supported languages.
-    #[pyo3(name = "all")]
     #[classmethod]
-    fn py_all(_cls: &Bound<PyType>) -> HashSet<Self> {
-        Self::all()
+    fn all(_cls: &Bound<PyType>) -> HashSet<Language> {
+        Language::all()
     }
 
     /// Return a set of all supported spoken languages.
-    #[pyo3(name = "all_spoken_ones")]
     #[classmethod]
-    fn py_all_spoken_ones(_cls: &Bound<PyType>) -> HashSet<Self> {
-        Self::all_spoken_ones()
+    fn all_spoken_ones(_cls: &Bound<PyType>) -> HashSet<Language> {
+        Language::all_spoken_ones()
     }
 
     /// Return a set of all languages supporting the Arabic script.
-    #[pyo3(name = "all_with_arabic_script")]
     #[classmethod]
-    fn py_all_with_arabic_script(_cls: &Bound<PyType>) -> HashSet<Self> {
-        Self::all_with_arabic_script()
+    fn all_with_arabic_script(_cls: &Bound<PyType>) -> HashSet<Language> {
+        Language::all_with_arabic_script()
     }
 
     /// Return a set of all languages supporting the Cyrillic script.
-    #[pyo3(name = "all_with_cyrillic_script")]
     #[classmethod]
-    fn py_all_with_cyrillic_script(_cls: &Bound<PyType>) -> HashSet<Self> {
-        Self::all_with_cyrillic_script()
+    fn all_with_cyrillic_script(_cls: &Bound<PyType>) -> HashSet<Language> {
+        Language::all_with_cyrillic_script()
     }
 
     /// Return a set of all languages supporting the Devanagari script.
-    #[pyo3(name = "all_with_devanagari_script")]
     #[classmethod]
-    fn py_all_with_devanagari_script(_cls: &Bound<PyType>) -> HashSet<Self> {
-        Self::all_with_devanagari_script()
+    fn all_with_devanagari_script(_cls: &Bound<PyType>) -> HashSet<Language> {
+        Language::all_with_devanagari_script()
     }
 
     /// Return a set of all languages supporting the Latin script.
-    #[pyo3(name = "all_with_latin_script")]
     #[classmethod]
-    fn py_all_with_latin_script(_cls: &Bound<PyType>) -> HashSet<Self> {
-        Self::all_with_latin_script()
+    fn all_with_latin_script(_cls: &Bound<PyType>) -> HashSet<Language> {
+        Language::all_with_latin_script()
     }
 
     /// Return the language associated with the ISO 639-1 code
@@ -228,10 +513,9 @@ impl Language {
     ///
     /// Raises:
     ///     ValueError: if there is no language for the given ISO code
-    #[pyo3(name = "from_iso_code_639_1")]
     #[classmethod]
-    fn py_from_iso_code_639_1(_cls: &Bound<PyType>, iso_code: &IsoCode639_1) -> Self {
-        Self::from_iso_code_639_1(iso_code)
+    fn from_iso_code_639_1(_cls: &Bound<PyType>, iso_code: IsoCode639_1) -> Language {
+        Language::from_iso_code_639_1(&iso_code)
     }
 
     /// Return the language associated with the ISO 639-3 code
@@ -239,29 +523,154 @@ impl Language {
     ///
     /// Raises:
     ///     ValueError: if there is no language for the given ISO code
-    #[pyo3(name = "from_iso_code_639_3")]
     #[classmethod]
-    fn py_from_iso_code_639_3(_cls: &Bound<PyType>, iso_code: &IsoCode639_3) -> Self {
-        Self::from_iso_code_639_3(iso_code)
+    fn from_iso_code_639_3(_cls: &Bound<PyType>, iso_code: IsoCode639_3) -> Language {
+        Language::from_iso_code_639_3(&iso_code)
+    }
+
+    /// Register a language which is not built into *Lingua* and return it.
+    ///
+    /// The language models are loaded lazily from the given directory which
+    /// must contain the files written by `LanguageModelFilesWriter` for this
+    /// language. Afterwards, the language and its ISO codes are available as
+    /// attributes of the classes `Language`, `IsoCode639_1` and `IsoCode639_3`.
+    /// Registering exactly the same language again returns the existing one.
+    ///
+    /// Args:
+    ///     name: The name of the language consisting of ASCII letters only.
+    ///     iso_code_639_1: The two-letter ISO 639-1 code of the language.
+    ///     iso_code_639_3: The three-letter ISO 639-3 code of the language.
+    ///     alphabets: The alphabets the language is written in.
+    ///     models_directory: The directory containing the language models.
+    ///
+    /// Raises:
+    ///     ValueError: if the name or the ISO codes are malformed, if no
+    ///         alphabet is given or if the name or one of the ISO codes is
+    ///         already used by another language
+    #[classmethod]
+    fn register_custom(
+        cls: &Bound<PyType>,
+        name: &str,
+        iso_code_639_1: &str,
+        iso_code_639_3: &str,
+        alphabets: Vec<Alphabet>,
+        models_directory: PathBuf,
+    ) -> PyResult<Language> {
+        match CustomLanguage::register(
+            name,
+            iso_code_639_1,
+            iso_code_639_3,
+            &alphabets,
+            &models_directory,
+        ) {
+            Ok(language) => {
+                let language = Language::Custom(language);
+                add_class_attributes(cls.py(), language)?;
+                Ok(language)
+            }
+            Err(message) => Err(PyValueError::new_err(message)),
+        }
     }
 
     /// Return the ISO 639-1 code of this language.
-    #[pyo3(name = "iso_code_639_1")]
     #[getter]
-    fn py_iso_code_639_1(&self) -> IsoCode639_1 {
-        self.iso_code_639_1()
+    fn iso_code_639_1(&self) -> IsoCode639_1 {
+        self.0.iso_code_639_1()
     }
 
     /// Return the ISO 639-3 code of this language.
-    #[pyo3(name = "iso_code_639_3")]
     #[getter]
-    fn py_iso_code_639_3(&self) -> IsoCode639_3 {
-        self.iso_code_639_3()
+    fn iso_code_639_3(&self) -> IsoCode639_3 {
+        self.0.iso_code_639_3()
     }
 
     #[getter]
     fn name(&self) -> String {
-        self.to_string().to_uppercase()
+        self.0.to_string().to_uppercase()
+    }
+}
+
+impl IntoPy<PyObject> for IsoCode639_1 {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        PyIsoCode639_1(self).into_py(py)
+    }
+}
+
+impl FromPyObject<'_> for IsoCode639_1 {
+    fn extract_bound(ob: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Ok(ob.downcast::<PyIsoCode639_1>()?.get().0)
+    }
+}
+
+impl IntoPy<PyObject> for IsoCode639_3 {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        PyIsoCode639_3(self).into_py(py)
+    }
+}
+
+impl FromPyObject<'_> for IsoCode639_3 {
+    fn extract_bound(ob: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Ok(ob.downcast::<PyIsoCode639_3>()?.get().0)
+    }
+}
+
+impl IntoPy<PyObject> for Language {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        PyLanguage(self).into_py(py)
+    }
+}
+
+impl FromPyObject<'_> for Language {
+    fn extract_bound(ob: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Ok(ob.downcast::<PyLanguage>()?.get().0)
+    }
+}
+
+impl IntoPy<PyObject> for CollapsedLanguage {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        match self {
+            CollapsedLanguage::Individual(language) => language.into_py(py),
+            CollapsedLanguage::Macro(macro_language) => macro_language.into_py(py),
+        }
+    }
+}
+
+/// Makes the language and its ISO codes accessible by their uppercase names,
+/// such as `Language.ENGLISH` and `IsoCode639_1.EN`.
+fn add_class_attributes(py: Python<'_>, language: Language) -> PyResult<()> {
+    let iso_code_639_1 = language.iso_code_639_1();
+    let iso_code_639_3 = language.iso_code_639_3();
+
+    py.get_type_bound::<PyLanguage>().setattr(
+        language.to_string().to_uppercase().as_str(),
+        language.into_py(py),
+    )?;
+    py.get_type_bound::<PyIsoCode639_1>().setattr(
+        iso_code_639_1.to_string().to_uppercase().as_str(),
+        iso_code_639_1.into_py(py),
+    )?;
+    py.get_type_bound::<PyIsoCode639_3>().setattr(
+        iso_code_639_3.to_string().to_uppercase().as_str(),
+        iso_code_639_3.into_py(py),
+    )?;
+
+    Ok(())
+}
+
+#[pymethods]
+impl Normalizer {
+    #[getter]
+    fn name(&self) -> String {
+        match self {
+            Normalizer::Nfc => "NFC",
+            Normalizer::Nfkc => "NFKC",
+            Normalizer::RemoveInvisibleCharacters => "REMOVE_INVISIBLE_CHARACTERS",
+            Normalizer::FoldFullwidthCharacters => "FOLD_FULLWIDTH_CHARACTERS",
+            Normalizer::SquashElongation => "SQUASH_ELONGATION",
+            Normalizer::StripNumerals => "STRIP_NUMERALS",
+            Normalizer::CleanMarkup => "CLEAN_MARKUP",
+        }
+        .to_string()
     }
 }
 
@@ -319,7 +728,10 @@ impl LanguageDetectorBuilder {
     /// with all built-in languages except those passed to this method.
     #[pyo3(name = "from_all_languages_without", signature = (*languages))]
     #[classmethod]
-    fn py_from_all_languages_without(_cls: &Bound<PyType>, languages: &Bound<PyTuple>) -> PyResult<Self> {
+    fn py_from_all_languages_without(
+        _cls: &Bound<PyType>,
+        languages: &Bound<PyTuple>,
+    ) -> PyResult<Self> {
         match languages.extract::<Vec<Language>>() {
             Ok(vector) => match panic::catch_unwind(|| Self::from_all_languages_without(&vector)) {
                 Ok(builder) => Ok(builder),
@@ -428,6 +840,21 @@ impl LanguageDetectorBuilder {
         self_
     }
 
+    /// Preload all language models on a background thread after creating
+    /// the LanguageDetector instance.
+    ///
+    /// Unlike with_preloaded_language_models, this does not block build
+    /// until all models have been loaded. Until the preloading is complete,
+    /// the detector loads the models it needs on demand. The progress can
+    /// be polled with LanguageDetector.preloading_progress.
+    #[pyo3(name = "with_preloaded_language_models_in_background")]
+    fn py_with_preloaded_language_models_in_background(
+        mut self_: PyRefMut<Self>,
+    ) -> PyRefMut<Self> {
+        self_.with_preloaded_language_models_in_background();
+        self_
+    }
+
     /// Disable the high accuracy mode in order to save memory
     /// and increase performance.
     ///
@@ -447,10 +874,314 @@ impl LanguageDetectorBuilder {
         self_
     }
 
+    /// Enable the accent-insensitive mode in order to classify text
+    /// whose diacritics have been stripped.
+    ///
+    /// Text from sources such as search queries, SMS or old ASCII-only
+    /// systems often lacks the diacritics of the language it is written in.
+    /// As the language models are built from correctly accented text,
+    /// such input is frequently misclassified.
+    ///
+    /// This method removes diacritics from both the input text and the
+    /// language models of all languages written in the Latin or Greek
+    /// script before comparing them. Rules based on characters unique
+    /// to a language still see the original input text.
+    #[pyo3(name = "with_accent_insensitive_mode")]
+    fn py_with_accent_insensitive_mode(mut self_: PyRefMut<Self>) -> PyRefMut<Self> {
+        self_.with_accent_insensitive_mode();
+        self_
+    }
+
+    /// Derive the character rules of the rule-based engine from the
+    /// language models instead of using the hand-maintained tables.
+    ///
+    /// Before the language models are consulted, Lingua identifies or
+    /// filters languages by characters which occur in only one or a few
+    /// languages. By default, these characters are taken from hand-maintained
+    /// tables. This method derives them from the unigram models of the
+    /// languages of the detector instead, including custom languages,
+    /// so the rules are always consistent with the models. A character
+    /// is unique to a language if it occurs in none of the other languages
+    /// of the detector.
+    #[pyo3(name = "with_derived_character_rules")]
+    fn py_with_derived_character_rules(mut self_: PyRefMut<Self>) -> PyRefMut<Self> {
+        self_.with_derived_character_rules();
+        self_
+    }
+
+    /// Use language models whose ngrams include word boundary markers.
+    ///
+    /// By default, ngrams are extracted from inside of words only, so ngrams
+    /// at the start or the end of a word cannot be distinguished from the same
+    /// ngrams in the middle of a word. The word boundary models are not bundled
+    /// with the built-in languages, so this option is restricted to custom
+    /// languages. Building the detector raises a ValueError if the models
+    /// are missing for any of its languages.
+    #[pyo3(name = "with_word_boundary_ngrams")]
+    fn py_with_word_boundary_ngrams(mut self_: PyRefMut<Self>) -> PyRefMut<Self> {
+        self_.with_word_boundary_ngrams();
+        self_
+    }
+
+    /// Use the given ngram lengths for the classification of short and long text.
+    ///
+    /// By default, unigrams up to fivegrams are used for text consisting of
+    /// less than 120 characters, whereas only trigrams are used for longer
+    /// text. Higher-order models, such as sixgrams or sevengrams, can improve
+    /// the accuracy for long text. Ngram lengths for which no model is
+    /// available for a language are skipped.
+    ///
+    /// Args:
+    ///     short_text_ngram_lengths: The smallest and the largest ngram length
+    ///         used for short text.
+    ///     long_text_ngram_lengths: The smallest and the largest ngram length
+    ///         used for long text and in low accuracy mode.
+    ///
+    /// Raises:
+    ///     ValueError: if one of the ranges is empty or does not lie in
+    ///         between 1 and 8
+    #[pyo3(name = "with_ngram_lengths")]
+    fn py_with_ngram_lengths(
+        mut self_: PyRefMut<Self>,
+        short_text_ngram_lengths: (usize, usize),
+        long_text_ngram_lengths: (usize, usize),
+    ) -> PyResult<PyRefMut<Self>> {
+        let short_text_ngram_lengths = short_text_ngram_lengths.0..=short_text_ngram_lengths.1;
+        let long_text_ngram_lengths = long_text_ngram_lengths.0..=long_text_ngram_lengths.1;
+
+        if !is_valid_ngram_length_range(&short_text_ngram_lengths)
+            || !is_valid_ngram_length_range(&long_text_ngram_lengths)
+        {
+            Err(PyValueError::new_err(NGRAM_LENGTHS_MESSAGE))
+        } else {
+            self_.with_ngram_lengths(short_text_ngram_lengths, long_text_ngram_lengths);
+            Ok(self_)
+        }
+    }
+
+    /// Estimate the ngram probabilities with add-k smoothing.
+    ///
+    /// The constant k is added to the frequency of each ngram, including
+    /// ngrams which do not occur in the training data. Smoothing requires the
+    /// ngram count files of all languages of the detector. They are not bundled
+    /// with the built-in languages, so smoothing is restricted to custom
+    /// languages. Building the detector raises a ValueError if they are
+    /// missing for any of its languages.
+    ///
+    /// Args:
+    ///     k: The constant added to the frequency of each ngram.
+    ///
+    /// Raises:
+    ///     ValueError: if k is not greater than 0.0
+    #[pyo3(name = "with_add_k_smoothing")]
+    fn py_with_add_k_smoothing(mut self_: PyRefMut<Self>, k: f64) -> PyResult<PyRefMut<Self>> {
+        if !is_valid_add_k_constant(k) {
+            Err(PyValueError::new_err(ADD_K_SMOOTHING_MESSAGE))
+        } else {
+            self_.with_smoothing(Smoothing::AddK(k));
+            Ok(self_)
+        }
+    }
+
+    /// Estimate the ngram probabilities with Witten-Bell smoothing.
+    ///
+    /// Smoothing requires the ngram count files of all languages of the
+    /// detector. They are not bundled with the built-in languages, so smoothing
+    /// is restricted to custom languages. Building the detector raises a
+    /// ValueError if they are missing for any of its languages.
+    #[pyo3(name = "with_witten_bell_smoothing")]
+    fn py_with_witten_bell_smoothing(mut self_: PyRefMut<Self>) -> PyRefMut<Self> {
+        self_.with_smoothing(Smoothing::WittenBell);
+        self_
+    }
+
+    /// Estimate the ngram probabilities with interpolated Kneser-Ney smoothing.
+    ///
+    /// Smoothing requires the ngram count files of all languages of the
+    /// detector. They are not bundled with the built-in languages, so smoothing
+    /// is restricted to custom languages. Building the detector raises a
+    /// ValueError if they are missing for any of its languages.
+    ///
+    /// Args:
+    ///     discount: The absolute discount subtracted from the frequency
+    ///         of each ngram.
+    ///
+    /// Raises:
+    ///     ValueError: if discount does not lie in between 0.0 and 1.0
+    #[pyo3(name = "with_kneser_ney_smoothing")]
+    #[pyo3(signature = (discount=0.75))]
+    fn py_with_kneser_ney_smoothing(
+        mut self_: PyRefMut<Self>,
+        discount: f64,
+    ) -> PyResult<PyRefMut<Self>> {
+        if !is_valid_kneser_ney_discount(discount) {
+            Err(PyValueError::new_err(KNESER_NEY_SMOOTHING_MESSAGE))
+        } else {
+            self_.with_smoothing(Smoothing::KneserNey(discount));
+            Ok(self_)
+        }
+    }
+
+    /// Combine the ngram models with the relative frequencies of whole words
+    /// for text consisting of one or two words.
+    ///
+    /// The summed log-probabilities of the words are interpolated with those
+    /// of the ngrams, using the given weight for the words. The word models are
+    /// not bundled with the built-in languages, so this option is restricted to
+    /// custom languages. Building the detector raises a ValueError if they are
+    /// missing for any of its languages.
+    ///
+    /// Args:
+    ///     weight: The weight of the word models in between 0.0 and 1.0.
+    ///
+    /// Raises:
+    ///     ValueError: if weight is not greater than 0.0 or greater than 1.0
+    #[pyo3(name = "with_word_model")]
+    #[pyo3(signature = (weight=0.5))]
+    fn py_with_word_model(mut self_: PyRefMut<Self>, weight: f64) -> PyResult<PyRefMut<Self>> {
+        if !is_valid_word_model_weight(weight) {
+            Err(PyValueError::new_err(WORD_MODEL_WEIGHT_MESSAGE))
+        } else {
+            self_.with_word_model(weight);
+            Ok(self_)
+        }
+    }
+
+    /// Load the models of regional language variants, such as pt-BR and pt-PT,
+    /// from the subdirectories of the given directory.
+    ///
+    /// The subdirectories are named after the BCP 47 tags of the variants and
+    /// are created by LanguageModelFilesWriter.write_language_variant_model_files.
+    /// Variants of languages which this detector has not been built from are
+    /// ignored.
+    ///
+    /// Args:
+    ///     models_directory: The directory containing the variant models.
+    ///
+    /// Raises:
+    ///     ValueError: if models_directory is not an existing directory
+    #[pyo3(name = "with_language_variants")]
+    fn py_with_language_variants(
+        mut self_: PyRefMut<Self>,
+        models_directory: PathBuf,
+    ) -> PyResult<PyRefMut<Self>> {
+        if !is_valid_models_directory(&models_directory) {
+            Err(PyValueError::new_err(LANGUAGE_VARIANTS_DIRECTORY_MESSAGE))
+        } else {
+            self_.with_language_variants(models_directory);
+            Ok(self_)
+        }
+    }
+
+    /// Load discriminators for pairs of closely related languages, such as
+    /// Bokmal and Nynorsk or Malay and Indonesian, from the given directory.
+    ///
+    /// The discriminators are created by
+    /// LanguageModelFilesWriter.write_pair_discriminator_file. No
+    /// discriminators are bundled with this library, so they have to be
+    /// trained from your own text of both languages of each pair before
+    /// this stage has any effect.
+    /// Only the discriminators whose languages are both languages of this
+    /// detector are loaded. If the two most likely languages of a text form
+    /// such a pair, their discriminator decides how their combined confidence
+    /// value is split between them, which may change their ranking.
+    ///
+    /// Args:
+    ///     models_directory: The directory containing the discriminators.
+    ///
+    /// Raises:
+    ///     ValueError: if models_directory is not an existing directory
+    #[pyo3(name = "with_pair_discriminators")]
+    fn py_with_pair_discriminators(
+        mut self_: PyRefMut<Self>,
+        models_directory: PathBuf,
+    ) -> PyResult<PyRefMut<Self>> {
+        if !is_valid_models_directory(&models_directory) {
+            Err(PyValueError::new_err(PAIR_DISCRIMINATORS_DIRECTORY_MESSAGE))
+        } else {
+            self_.with_pair_discriminators(models_directory);
+            Ok(self_)
+        }
+    }
+
+    /// Collapse the languages of the given macrolanguages into their
+    /// macrolanguage, such as Bokmal and Nynorsk into Norwegian.
+    ///
+    /// The macrolanguages are reported by the methods
+    /// `detect_collapsed_language_of` and
+    /// `compute_collapsed_language_confidence_values` of the detector,
+    /// which sum up the confidence values of their individual languages.
+    /// The fine-grained distribution remains available through the other
+    /// methods of the detector.
+    ///
+    /// Raises:
+    ///     ValueError: if no macrolanguage is passed
+    #[pyo3(name = "with_macro_languages", signature = (*macro_languages))]
+    fn py_with_macro_languages<'py>(
+        mut self_: PyRefMut<'py, Self>,
+        macro_languages: &Bound<PyTuple>,
+    ) -> PyResult<PyRefMut<'py, Self>> {
+        let vector = macro_languages.extract::<Vec<MacroLanguage>>()?;
+        if vector.is_empty() {
+            Err(PyValueError::new_err(MACRO_LANGUAGES_MESSAGE))
+        } else {
+            self_.with_macro_languages(&vector);
+            Ok(self_)
+        }
+    }
+
+    /// Keep the memory occupied by the loaded language models below the
+    /// given number of bytes.
+    ///
+    /// By default, lazily loaded language models stay in memory until
+    /// unload_language_models is called. With a memory budget, the sizes of
+    /// the loaded models are estimated and the least recently used models of
+    /// a language and ngram length are evicted once the budget is exceeded.
+    /// Evicted models are loaded again when they are needed.
+    ///
+    /// The budget applies to each detector separately and covers only the
+    /// models it has used. As all detectors share the same language models,
+    /// a model evicted by one detector is loaded again by any other detector
+    /// which still needs it, even if that detector has preloaded its models.
+    ///
+    /// Raises:
+    ///     ValueError: if bytes is 0
+    #[pyo3(name = "with_memory_budget")]
+    fn py_with_memory_budget(mut self_: PyRefMut<Self>, bytes: usize) -> PyResult<PyRefMut<Self>> {
+        if bytes == 0 {
+            Err(PyValueError::new_err(MEMORY_BUDGET_MESSAGE))
+        } else {
+            self_.with_memory_budget(bytes);
+            Ok(self_)
+        }
+    }
+
+    /// Apply the given normalizers to the input text before it is split
+    /// into words.
+    ///
+    /// By default, the input text is only trimmed and lowercased. Text from
+    /// social media or user input often contains invisible control characters,
+    /// fullwidth letters or elongated words such as "sooooo" which do not occur
+    /// in the training data. The normalizers are applied in the given order.
+    #[pyo3(name = "with_normalizers")]
+    fn py_with_normalizers(
+        mut self_: PyRefMut<Self>,
+        normalizers: Vec<Normalizer>,
+    ) -> PyRefMut<Self> {
+        self_.with_normalizers(&normalizers);
+        self_
+    }
+
     /// Create and return the configured LanguageDetector instance.
+    ///
+    /// Raises ValueError if the models required by one of the configured
+    /// modes are missing for any of the languages.
     #[pyo3(name = "build")]
-    fn py_build(&mut self) -> LanguageDetector {
-        self.build()
+    fn py_build(&mut self) -> PyResult<LanguageDetector> {
+        match self.missing_models_message() {
+            Some(message) => Err(PyValueError::new_err(message)),
+            None => Ok(self.build()),
+        }
     }
 }
 
@@ -464,6 +1195,64 @@ impl LanguageDetector {
         self.unload_language_models()
     }
 
+    /// Write this detector to a snapshot file from which it can be restored
+    /// with from_snapshot without decompressing and parsing the language
+    /// models again.
+    ///
+    /// The snapshot contains the settings of this detector and all models
+    /// it uses. Models which have not been loaded yet are loaded before.
+    ///
+    /// Args:
+    ///     file_path: The path of the snapshot file to write.
+    ///
+    /// Raises:
+    ///     OSError: if the file cannot be written
+    #[pyo3(name = "write_snapshot")]
+    fn py_write_snapshot(&self, file_path: PathBuf) -> PyResult<()> {
+        Ok(self.write_snapshot(file_path)?)
+    }
+
+    /// Restore a detector from a snapshot file written by write_snapshot.
+    ///
+    /// Args:
+    ///     file_path: The path of the snapshot file to read.
+    ///
+    /// Raises:
+    ///     ValueError: if the file is not a snapshot, if it has been written
+    ///         by another version of this library, with other language models or
+    ///         other built-in languages enabled, or if one of its custom languages
+    ///         has not been registered with the same ISO codes
+    ///     OSError: if the file cannot be read
+    #[pyo3(name = "from_snapshot")]
+    #[classmethod]
+    fn py_from_snapshot(_cls: &Bound<PyType>, file_path: PathBuf) -> PyResult<Self> {
+        Self::from_snapshot(file_path).map_err(|error| {
+            if error.kind() == io::ErrorKind::InvalidData {
+                PyValueError::new_err(error.to_string())
+            } else {
+                PyErr::from(error)
+            }
+        })
+    }
+
+    /// Return how far the language models have been preloaded if this
+    /// detector has been built with preloaded language models, or `None`
+    /// otherwise.
+    #[pyo3(name = "preloading_progress")]
+    fn py_preloading_progress(&self) -> Option<PreloadingProgress> {
+        self.preloading_progress()
+    }
+
+    /// Return which language models of this detector have been loaded
+    /// and which have failed to load.
+    ///
+    /// Models are loaded on demand, so a model which has neither been
+    /// loaded nor failed to load is not needed yet.
+    #[pyo3(name = "model_status")]
+    fn py_model_status(&self) -> ModelStatus {
+        self.model_status()
+    }
+
     /// Detect the language of given input text.
     ///
     /// If the language cannot be reliably detected, `None` is returned.
@@ -476,6 +1265,58 @@ impl LanguageDetector {
         self.detect_language_of(text)
     }
 
+    /// Detect the alphabet of given input text if none of the languages
+    /// of this LanguageDetector instance is written in it.
+    ///
+    /// Text written in an alphabet that is not used by any of the configured
+    /// languages cannot be classified. This method allows to tell such text
+    /// apart from text whose language is merely ambiguous. If the predominant
+    /// alphabet of the text is used by at least one of the configured languages,
+    /// or if no alphabet can be determined, `None` is returned.
+    #[pyo3(name = "detect_unsupported_alphabet_of")]
+    fn py_detect_unsupported_alphabet_of(&self, text: String) -> Option<Alphabet> {
+        self.detect_unsupported_alphabet_of(text)
+    }
+
+    /// Detect the language of given input text and tell apart why no
+    /// language has been detected, if so.
+    ///
+    /// detect_language_of returns None both for text whose language cannot be
+    /// reliably detected and for text written in an alphabet which none of
+    /// the languages of this detector uses. The returned outcome tells both
+    /// cases apart.
+    #[pyo3(name = "detect_language_outcome_of")]
+    fn py_detect_language_outcome_of(&self, text: String) -> PyDetectionOutcome {
+        PyDetectionOutcome {
+            outcome: self.detect_language_outcome_of(text),
+        }
+    }
+
+    /// Detect whether the given input text is written in Simplified or
+    /// Traditional Chinese.
+    ///
+    /// The language is detected first as by `detect_language_of`. If it is
+    /// Chinese, the characters which only occur in one of both scripts are
+    /// counted. `ChineseScript.MIXED` is returned if both scripts make up a
+    /// substantial share of them. If the language is not Chinese or if the
+    /// text consists of characters shared by both scripts only, `None` is
+    /// returned.
+    #[pyo3(name = "detect_chinese_script_of")]
+    fn py_detect_chinese_script_of(&self, text: String) -> Option<ChineseScript> {
+        self.detect_chinese_script_of(text)
+    }
+
+    /// Detect the language of the given input text, collapsing the languages
+    /// of the macrolanguages configured with `with_macro_languages` into
+    /// their macrolanguage.
+    ///
+    /// Either a Language or a MacroLanguage is returned. If it cannot be
+    /// reliably detected, `None` is returned.
+    #[pyo3(name = "detect_collapsed_language_of")]
+    fn py_detect_collapsed_language_of(&self, text: String) -> Option<CollapsedLanguage> {
+        self.detect_collapsed_language_of(text)
+    }
+
     /// Detects the languages of all given input texts.
     ///
     /// If the language cannot be reliably detected for a text,
@@ -645,11 +1486,125 @@ impl LanguageDetector {
     ) -> Vec<f64> {
         self.compute_language_confidence_in_parallel(&texts, language)
     }
+
+    /// Detect the regional variant of the language of given input text
+    /// and return its BCP 47 tag, such as pt-BR or pt-PT.
+    ///
+    /// The language is detected first as by `detect_language_of`. If the
+    /// language cannot be reliably detected, if no variants of it have been
+    /// loaded or if its most likely variants are equally likely, `None` is
+    /// returned.
+    #[pyo3(name = "detect_language_variant_of")]
+    fn py_detect_language_variant_of(&self, text: String) -> Option<String> {
+        self.detect_language_variant_of(text)
+            .map(|variant| variant.to_string())
+    }
+
+    /// Compute confidence values for the regional variants of the language
+    /// of the given text.
+    ///
+    /// A list of tuples is returned, each consisting of the BCP 47 tag of a
+    /// variant and its confidence value. The entries are sorted by their
+    /// confidence value in descending order and sum to 1.0. If the language
+    /// cannot be reliably detected or if no variants of it have been loaded,
+    /// the list is empty.
+    #[pyo3(name = "compute_language_variant_confidence_values")]
+    fn py_compute_language_variant_confidence_values(&self, text: String) -> Vec<(String, f64)> {
+        self.compute_language_variant_confidence_values(text)
+            .into_iter()
+            .map(|(variant, value)| (variant.to_string(), value))
+            .collect()
+    }
+
+    /// Compute confidence values for each language supported by this
+    /// detector for the given input text, collapsing the languages of the
+    /// macrolanguages configured with `with_macro_languages` into their
+    /// macrolanguage.
+    ///
+    /// A list of tuples is returned, each consisting of a Language or
+    /// MacroLanguage and its confidence value. The confidence value of a
+    /// macrolanguage is the sum of the values of its individual languages.
+    /// The entries are sorted by their confidence value in descending order.
+    #[pyo3(name = "compute_collapsed_language_confidence_values")]
+    fn py_compute_collapsed_language_confidence_values(
+        &self,
+        text: String,
+    ) -> Vec<(CollapsedLanguage, f64)> {
+        self.compute_collapsed_language_confidence_values(text)
+    }
 }
 
 #[pymethods]
 impl LanguageModelFilesWriter {
-    /// Create language model files and write them to a directory.
+    /// Create a new writer which builds the language models from the
+    /// unmodified input text.
+    #[new]
+    fn py_new() -> Self {
+        Self::new()
+    }
+
+    /// Apply the given normalizers to each line of the input text before
+    /// the language models are built from it.
+    ///
+    /// The normalizers should be the same as the ones passed to
+    /// LanguageDetectorBuilder.with_normalizers so that the models match
+    /// the text they are compared with.
+    #[pyo3(name = "with_normalizers")]
+    fn py_with_normalizers(
+        mut self_: PyRefMut<Self>,
+        normalizers: Vec<Normalizer>,
+    ) -> PyRefMut<Self> {
+        self_.with_normalizers(&normalizers);
+        self_
+    }
+
+    /// Surround each word with boundary markers, so that ngrams at the start
+    /// or the end of a word are distinguished from the same ngrams inside of
+    /// it. The language model files are named word-boundary-unigrams.json.br
+    /// and so on.
+    #[pyo3(name = "with_word_boundary_ngrams")]
+    fn py_with_word_boundary_ngrams(mut self_: PyRefMut<Self>) -> PyRefMut<Self> {
+        self_.with_word_boundary_ngrams();
+        self_
+    }
+
+    /// Additionally write the absolute frequencies of all ngrams to files
+    /// named unigram-counts.json.br and so on, which smoothing is computed from.
+    #[pyo3(name = "with_ngram_counts")]
+    fn py_with_ngram_counts(mut self_: PyRefMut<Self>) -> PyRefMut<Self> {
+        self_.with_ngram_counts();
+        self_
+    }
+
+    /// Additionally write the relative frequencies of whole words
+    /// to a file named words.json.br.
+    #[pyo3(name = "with_word_model")]
+    fn py_with_word_model(mut self_: PyRefMut<Self>) -> PyRefMut<Self> {
+        self_.with_word_model();
+        self_
+    }
+
+    /// Create language models for all ngram lengths from 1 up to and
+    /// including the given maximum ngram length. By default, unigram up to
+    /// fivegram models are created.
+    ///
+    /// Raises:
+    ///     ValueError: if the maximum ngram length does not lie in between 1 and 8
+    #[pyo3(name = "with_maximum_ngram_length")]
+    fn py_with_maximum_ngram_length(
+        mut self_: PyRefMut<Self>,
+        maximum_ngram_length: usize,
+    ) -> PyResult<PyRefMut<Self>> {
+        if !(1..=MAXIMUM_NGRAM_LENGTH).contains(&maximum_ngram_length) {
+            Err(PyValueError::new_err(MAXIMUM_NGRAM_LENGTH_MESSAGE))
+        } else {
+            self_.with_maximum_ngram_length(maximum_ngram_length);
+            Ok(self_)
+        }
+    }
+
+    /// Create language model files with the default configuration and write
+    /// them to a directory.
     ///
     /// Args:
     ///     input_file_path: The path to a txt file used for language
@@ -672,17 +1627,139 @@ impl LanguageModelFilesWriter {
         _cls: &Bound<PyType>,
         input_file_path: PathBuf,
         output_directory_path: PathBuf,
-        language: &Language,
+        language: Language,
         char_class: &str,
     ) -> PyResult<()> {
-        convert_io_result_to_py_result(panic::catch_unwind(|| {
-            Self::create_and_write_language_model_files(
+        Self::new().py_write_language_model_files(
+            input_file_path,
+            output_directory_path,
+            language,
+            char_class,
+        )
+    }
+
+    /// Create language model files with the configuration of this writer
+    /// and write them to a directory.
+    ///
+    /// Args:
+    ///     input_file_path: The path to a txt file used for language
+    ///         model creation. The assumed encoding of the txt file is UTF-8.
+    ///     output_directory_path: The path to an existing directory where the
+    ///         language model files are to be written.
+    ///     language: The language for which to create language models.
+    ///     char_class: A regex character class such as \\p{L} to restrict the
+    ///         set of characters that the language models are built from.
+    ///
+    /// Raises:
+    ///     Exception: under the same conditions as
+    ///         create_and_write_language_model_files
+    #[pyo3(name = "write_language_model_files")]
+    fn py_write_language_model_files(
+        &self,
+        input_file_path: PathBuf,
+        output_directory_path: PathBuf,
+        language: Language,
+        char_class: &str,
+    ) -> PyResult<()> {
+        // The writer is only read, so a panic cannot leave it in an inconsistent state.
+        convert_io_result_to_py_result(panic::catch_unwind(AssertUnwindSafe(|| {
+            self.write_language_model_files(
                 input_file_path.as_path(),
                 output_directory_path.as_path(),
-                language,
+                &language,
                 char_class,
             )
-        }))
+        })))
+    }
+
+    /// Create the language model files of a regional language variant with
+    /// the configuration of this writer and write them to a subdirectory of
+    /// the given output directory which is named after the variant's
+    /// BCP 47 tag.
+    ///
+    /// A word model is always written in addition to the ngram models.
+    ///
+    /// Args:
+    ///     input_file_path: The path to a txt file used for language
+    ///         model creation. The assumed encoding of the txt file is UTF-8.
+    ///     output_directory_path: The path to an existing directory in which
+    ///         the subdirectory of the variant is created.
+    ///     variant: The BCP 47 tag of the variant, such as pt-BR.
+    ///     char_class: A regex character class such as \\p{L} to restrict the
+    ///         set of characters that the language models are built from.
+    ///
+    /// Raises:
+    ///     ValueError: if the variant is not a valid BCP 47 tag consisting of
+    ///         a supported ISO 639-1 code and a region subtag
+    ///     Exception: under the same conditions as
+    ///         create_and_write_language_model_files
+    #[pyo3(name = "write_language_variant_model_files")]
+    fn py_write_language_variant_model_files(
+        &self,
+        input_file_path: PathBuf,
+        output_directory_path: PathBuf,
+        variant: &str,
+        char_class: &str,
+    ) -> PyResult<()> {
+        let variant = LanguageVariant::from_str(variant).map_err(|_| {
+            PyValueError::new_err(format!(
+                "'{variant}' is not a valid language variant tag such as pt-BR"
+            ))
+        })?;
+
+        convert_io_result_to_py_result(panic::catch_unwind(AssertUnwindSafe(|| {
+            self.write_language_variant_model_files(
+                input_file_path.as_path(),
+                output_directory_path.as_path(),
+                &variant,
+                char_class,
+            )
+        })))
+    }
+
+    /// Train a discriminator for a pair of closely related languages with
+    /// the configuration of this writer and write it to a file named after
+    /// the ISO 639-1 codes of both languages, such as nb-nn.json.br.
+    ///
+    /// The normalizers of this writer are applied to each line of the input
+    /// texts before the discriminator is trained on them.
+    ///
+    /// Args:
+    ///     first_input_file_path: The path to a txt file containing text
+    ///         written in the first language, encoded in UTF-8.
+    ///     second_input_file_path: The path to a txt file containing text
+    ///         written in the second language, encoded in UTF-8.
+    ///     output_directory_path: The path to an existing directory where
+    ///         the discriminator file is to be written.
+    ///     first_language: The language of the first input file.
+    ///     second_language: The language of the second input file.
+    ///     char_class: A regex character class such as \\p{L} to restrict the
+    ///         set of characters that the discriminator is built from.
+    ///
+    /// Raises:
+    ///     Exception: if both languages are the same; under the same
+    ///         conditions as create_and_write_language_model_files for
+    ///         any of the input files and the output directory
+    #[pyo3(name = "write_pair_discriminator_file")]
+    fn py_write_pair_discriminator_file(
+        &self,
+        first_input_file_path: PathBuf,
+        second_input_file_path: PathBuf,
+        output_directory_path: PathBuf,
+        first_language: Language,
+        second_language: Language,
+        char_class: &str,
+    ) -> PyResult<()> {
+        convert_io_result_to_py_result(panic::catch_unwind(AssertUnwindSafe(|| {
+            self.write_pair_discriminator_file(
+                first_input_file_path.as_path(),
+                second_input_file_path.as_path(),
+                output_directory_path.as_path(),
+                &first_language,
+                &second_language,
+                char_class,
+            )
+        })))
     }
 }
 
@@ -0,0 +1,263 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+use strum::IntoEnumIterator;
+
+use crate::alphabet::Alphabet;
+use crate::constant::CHARS_TO_LANGUAGES_MAPPING;
+use crate::json::load_json;
+use crate::language::Language;
+use crate::model::TrainingDataLanguageModel;
+
+/// The minimum relative frequency of a character in a unigram model
+/// for the character to be considered part of the respective language.
+const MINIMUM_CHARACTER_FREQUENCY: f64 = 0.0001;
+
+/// The maximum number of languages that a character may be shared by
+/// in order to be added to the character-to-languages mapping.
+const MAXIMUM_LANGUAGE_COUNT: usize = 3;
+
+/// The character tables used by the rule-based engine to identify
+/// or filter languages before the language models are consulted.
+#[derive(Clone)]
+pub(crate) struct CharacterRules {
+    pub(crate) unique_characters: HashMap<Language, String>,
+    pub(crate) chars_to_languages_mapping: HashMap<String, HashSet<Language>>,
+}
+
+impl CharacterRules {
+    /// Creates the rules from the hand-maintained tables.
+    pub(crate) fn built_in(languages: &HashSet<Language>) -> Self {
+        let unique_characters = languages
+            .iter()
+            .filter_map(|language| {
+                language
+                    .unique_characters()
+                    .map(|characters| (*language, characters.to_string()))
+            })
+            .collect();
+
+        let chars_to_languages_mapping = CHARS_TO_LANGUAGES_MAPPING
+            .iter()
+            .map(|(characters, languages)| (characters.to_string(), languages.clone()))
+            .collect();
+
+        Self {
+            unique_characters,
+            chars_to_languages_mapping,
+        }
+    }
+
+    /// Derives the rules from the unigram models of the given languages, which are
+    /// loaded from wherever their models reside, including custom languages and
+    /// external models directories.
+    ///
+    /// A character is attributed to a language if its relative frequency in the
+    /// language's unigram model is at least [`MINIMUM_CHARACTER_FREQUENCY`].
+    /// Characters attributed to exactly one of the given languages become unique
+    /// characters of that language. Characters attributed to a few languages are
+    /// added to the character-to-languages mapping, unless these languages are all
+    /// the given languages written in the character's alphabet. Characters of
+    /// alphabets handled by dedicated rules are skipped.
+    pub(crate) fn derived_from_unigram_models(languages: &HashSet<Language>) -> Self {
+        let mut character_languages = BTreeMap::<char, BTreeSet<Language>>::new();
+
+        for &language in languages.iter() {
+            let Ok(json) = load_json(language, 1) else {
+                continue;
+            };
+            let Ok(unigram_model) = TrainingDataLanguageModel::from_json(&json) else {
+                continue;
+            };
+            for (unigram, probability) in unigram_model {
+                if probability >= MINIMUM_CHARACTER_FREQUENCY {
+                    if let Some(character) = unigram.chars().next() {
+                        character_languages
+                            .entry(character)
+                            .or_default()
+                            .insert(language);
+                    }
+                }
+            }
+        }
+
+        let alphabet_language_counts = Alphabet::iter()
+            .filter(|alphabet| {
+                !matches!(
+                    alphabet,
+                    Alphabet::Han | Alphabet::Hiragana | Alphabet::Katakana
+                )
+            })
+            .map(|alphabet| {
+                let language_count = languages
+                    .iter()
+                    .filter(|language| language.alphabets().contains(&alphabet))
+                    .count();
+                (alphabet, language_count)
+            })
+            .filter(|(_, language_count)| *language_count > 1)
+            .collect::<Vec<_>>();
+
+        let mut unique_characters = HashMap::<Language, String>::new();
+        let mut grouped_characters = BTreeMap::<Vec<Language>, String>::new();
+
+        for (character, character_languages) in character_languages {
+            let alphabet_language_count = match alphabet_language_counts
+                .iter()
+                .find(|(alphabet, _)| alphabet.matches_char(character))
+            {
+                Some((_, language_count)) => *language_count,
+                None => continue,
+            };
+
+            if character_languages.len() == 1 {
+                let language = character_languages.into_iter().next().unwrap();
+                unique_characters
+                    .entry(language)
+                    .or_default()
+                    .push(character);
+            } else if character_languages.len() <= MAXIMUM_LANGUAGE_COUNT
+                && character_languages.len() < alphabet_language_count
+            {
+                grouped_characters
+                    .entry(character_languages.into_iter().collect())
+                    .or_default()
+                    .push(character);
+            }
+        }
+
+        let chars_to_languages_mapping = grouped_characters
+            .into_iter()
+            .map(|(languages, characters)| (characters, languages.into_iter().collect()))
+            .collect();
+
+        Self {
+            unique_characters,
+            chars_to_languages_mapping,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use tempfile::{tempdir, NamedTempFile};
+
+    use crate::language::Language::*;
+    use crate::writer::LanguageModelFilesWriter;
+
+    use super::*;
+
+    #[test]
+    fn assert_built_in_rules_are_restricted_to_given_languages() {
+        let rules = CharacterRules::built_in(&hashset!(English, German, Polish));
+        assert_eq!(
+            rules.unique_characters,
+            hashmap!(German => "ß".to_string(), Polish => "ŁłŃńŚśŹź".to_string())
+        );
+        assert_eq!(
+            rules.chars_to_languages_mapping.len(),
+            CHARS_TO_LANGUAGES_MAPPING.len()
+        );
+    }
+
+    #[test]
+    fn assert_derived_unique_characters_are_consistent_with_built_in_ones() {
+        let rules = CharacterRules::derived_from_unigram_models(&Language::all());
+
+        for (language, characters) in [
+            (Azerbaijani, "ə"),
+            (Czech, "řů"),
+            (German, "ß"),
+            (Hungarian, "őű"),
+            (Latvian, "ģķļņ"),
+            (Polish, "łśź"),
+            (Ukrainian, "ї"),
+        ] {
+            for character in characters.chars() {
+                assert!(
+                    rules.unique_characters[&language].contains(character),
+                    "expected '{character}' to be unique to {language:?}"
+                );
+            }
+        }
+
+        assert!(!rules.unique_characters.contains_key(&English));
+    }
+
+    #[test]
+    fn assert_derived_mapping_contains_characters_shared_by_few_languages() {
+        let rules = CharacterRules::derived_from_unigram_models(&Language::all());
+
+        let languages_of = |character: char| {
+            rules
+                .chars_to_languages_mapping
+                .iter()
+                .find(|(characters, _)| characters.contains(character))
+                .map(|(_, languages)| languages.clone())
+        };
+
+        assert_eq!(languages_of('ą'), Some(hashset!(Lithuanian, Polish)));
+        assert_eq!(languages_of('ğ'), Some(hashset!(Azerbaijani, Turkish)));
+        assert_eq!(languages_of('e'), None);
+        assert_eq!(languages_of('क'), None);
+    }
+
+    #[test]
+    fn assert_derived_unique_characters_are_restricted_to_given_languages() {
+        let rules = CharacterRules::derived_from_unigram_models(&hashset!(English, German));
+        assert_eq!(
+            rules.unique_characters.keys().collect::<Vec<_>>(),
+            vec![&German]
+        );
+        assert!(rules.unique_characters[&German].contains('ä'));
+
+        let rules = CharacterRules::derived_from_unigram_models(&Language::all());
+        assert!(!rules.unique_characters[&German].contains('ä'));
+    }
+
+    #[test]
+    fn assert_derived_rules_include_custom_languages() {
+        let models_directory = tempdir().unwrap();
+        let sami = Language::register_custom(
+            "NorthernSami",
+            "se",
+            "sme",
+            &[Alphabet::Latin],
+            models_directory.path(),
+        );
+
+        let mut input_file = NamedTempFile::new().unwrap();
+        input_file
+            .write_all("Buorre beaivi. Ođđa jahki. Ŋuolggat ja ŧuoŧŧa.".as_bytes())
+            .unwrap();
+        LanguageModelFilesWriter::create_and_write_language_model_files(
+            input_file.path(),
+            models_directory.path(),
+            &sami,
+            "\\p{L}",
+        )
+        .unwrap();
+
+        let rules = CharacterRules::derived_from_unigram_models(&hashset!(English, German, sami));
+        for character in "đŋŧ".chars() {
+            assert!(rules.unique_characters[&sami].contains(character));
+        }
+    }
+}
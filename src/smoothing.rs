@@ -0,0 +1,390 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::io;
+use std::io::{Read, Write};
+
+use ahash::AHashMap;
+use compact_str::CompactString;
+
+use crate::snapshot::{SnapshotReader, SnapshotWriter};
+
+/// This enum specifies how the probabilities of ngrams are estimated from the
+/// absolute ngram frequencies of the training data.
+///
+/// All schemes except for [`Smoothing::None`] assign a non-zero probability to every
+/// ngram, including ngrams which do not occur in the training data, so that rare
+/// ngrams do not dominate the classification of short text. They require the ngram
+/// count files created by
+/// [`LanguageModelFilesWriter::with_ngram_counts`](crate::LanguageModelFilesWriter::with_ngram_counts).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Smoothing {
+    /// Uses the relative frequency of the longest ngram found in the language model
+    /// without any discount. Ngrams which are not found at all are ignored.
+    /// This is the default.
+    None,
+
+    /// Adds the given constant `k` to the frequency of each ngram, including ngrams
+    /// which do not occur in the training data.
+    AddK(f64),
+
+    /// Interpolates the relative frequency of each ngram with the probability of the
+    /// next shorter ngram, weighted by the number of distinct characters observed
+    /// after the respective context.
+    WittenBell,
+
+    /// Subtracts the given absolute discount from the frequency of each ngram and
+    /// interpolates with shorter ngrams whose probabilities are estimated from the
+    /// number of distinct contexts they occur in.
+    KneserNey(f64),
+}
+
+/// The absolute frequencies of the ngrams of a single language and the statistics
+/// derived from them which the smoothing schemes rely on.
+pub(crate) struct NgramCounts {
+    counts: Vec<AHashMap<CompactString, u32>>,
+    contexts: Vec<AHashMap<CompactString, ContextStatistics>>,
+    continuation_counts: Vec<AHashMap<CompactString, u32>>,
+    continuation_contexts: Vec<AHashMap<CompactString, ContextStatistics>>,
+    vocabulary_size: usize,
+}
+
+#[derive(Clone, Copy, Default)]
+struct ContextStatistics {
+    total: u32,
+    types: u32,
+}
+
+impl NgramCounts {
+    /// Creates the statistics from the given ngram frequencies, which must cover
+    /// all ngram lengths from 1 up to the highest one in ascending order.
+    pub(crate) fn new(counts: Vec<AHashMap<CompactString, u32>>) -> Self {
+        let contexts = counts.iter().map(collect_context_statistics).collect();
+
+        // The continuation count of an ngram is the number of distinct
+        // characters which precede it, taken from the next higher order.
+        let continuation_counts = (0..counts.len())
+            .map(|i| {
+                let mut continuation_counts = AHashMap::new();
+                if let Some(higher_order_counts) = counts.get(i + 1) {
+                    for ngram in higher_order_counts.keys() {
+                        let suffix = CompactString::from(split_ngram(ngram).1);
+                        *continuation_counts.entry(suffix).or_insert(0) += 1;
+                    }
+                }
+                continuation_counts
+            })
+            .collect::<Vec<_>>();
+
+        let continuation_contexts = continuation_counts
+            .iter()
+            .map(collect_context_statistics)
+            .collect();
+
+        let vocabulary_size = counts
+            .first()
+            .map_or(0, |unigram_counts| unigram_counts.len());
+
+        Self {
+            counts,
+            contexts,
+            continuation_counts,
+            continuation_contexts,
+            vocabulary_size,
+        }
+    }
+
+    pub(crate) fn maximum_ngram_length(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// Writes the absolute frequencies only, as all other statistics are derived from them.
+    pub(crate) fn write_snapshot<W: Write>(
+        &self,
+        writer: &mut SnapshotWriter<W>,
+    ) -> io::Result<()> {
+        writer.write_usize(self.counts.len())?;
+        for counts in self.counts.iter() {
+            writer.write_counts(counts)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn read_snapshot<R: Read>(reader: &mut SnapshotReader<R>) -> io::Result<Self> {
+        let length = reader.read_usize()?;
+        let counts = (0..length)
+            .map(|_| reader.read_counts())
+            .collect::<io::Result<Vec<_>>>()?;
+        Ok(Self::new(counts))
+    }
+
+    /// Returns the probability of the last character of the given ngram
+    /// conditioned on the characters preceding it.
+    pub(crate) fn probability(&self, smoothing: Smoothing, ngram: &str) -> f64 {
+        match smoothing {
+            Smoothing::None => self.relative_frequency(ngram),
+            Smoothing::AddK(k) => self.add_k_probability(ngram, k),
+            Smoothing::WittenBell => self.witten_bell_probability(ngram),
+            Smoothing::KneserNey(discount) => self.kneser_ney_probability(ngram, discount, true),
+        }
+    }
+
+    fn relative_frequency(&self, ngram: &str) -> f64 {
+        let (count, context) = self.count_and_context(ngram);
+        if context.total == 0 {
+            0.0
+        } else {
+            count as f64 / context.total as f64
+        }
+    }
+
+    fn add_k_probability(&self, ngram: &str, k: f64) -> f64 {
+        let ngram_length = ngram.chars().count();
+        if ngram_length > self.counts.len() {
+            return self.add_k_probability(split_ngram(ngram).1, k);
+        }
+        let (count, context) = self.count_and_context(ngram);
+        (count as f64 + k) / (context.total as f64 + k * self.extended_vocabulary_size())
+    }
+
+    fn witten_bell_probability(&self, ngram: &str) -> f64 {
+        if ngram.is_empty() {
+            return self.uniform_probability();
+        }
+        let (context, suffix) = split_ngram(ngram);
+        let lower_order_probability = self.witten_bell_probability(suffix);
+        let ngram_length = ngram.chars().count();
+
+        if ngram_length > self.counts.len() {
+            return lower_order_probability;
+        }
+
+        let count = self.counts[ngram_length - 1]
+            .get(ngram)
+            .copied()
+            .unwrap_or(0);
+        let statistics = self.contexts[ngram_length - 1]
+            .get(context)
+            .copied()
+            .unwrap_or_default();
+
+        if statistics.total == 0 {
+            return lower_order_probability;
+        }
+
+        (count as f64 + statistics.types as f64 * lower_order_probability)
+            / (statistics.total + statistics.types) as f64
+    }
+
+    fn kneser_ney_probability(&self, ngram: &str, discount: f64, is_highest_order: bool) -> f64 {
+        if ngram.is_empty() {
+            return self.uniform_probability();
+        }
+        let (context, suffix) = split_ngram(ngram);
+        let ngram_length = ngram.chars().count();
+
+        if ngram_length > self.counts.len() {
+            return self.kneser_ney_probability(suffix, discount, is_highest_order);
+        }
+
+        let lower_order_probability = self.kneser_ney_probability(suffix, discount, false);
+
+        let (counts, contexts) = if is_highest_order {
+            (&self.counts, &self.contexts)
+        } else {
+            (&self.continuation_counts, &self.continuation_contexts)
+        };
+
+        let count = counts[ngram_length - 1].get(ngram).copied().unwrap_or(0);
+        let statistics = contexts[ngram_length - 1]
+            .get(context)
+            .copied()
+            .unwrap_or_default();
+
+        if statistics.total == 0 {
+            return lower_order_probability;
+        }
+
+        let total = statistics.total as f64;
+
+        (count as f64 - discount).max(0.0) / total
+            + discount * statistics.types as f64 / total * lower_order_probability
+    }
+
+    fn count_and_context(&self, ngram: &str) -> (u32, ContextStatistics) {
+        let ngram_length = ngram.chars().count();
+        if ngram_length == 0 || ngram_length > self.counts.len() {
+            return (0, ContextStatistics::default());
+        }
+        let count = self.counts[ngram_length - 1]
+            .get(ngram)
+            .copied()
+            .unwrap_or(0);
+        let context = self.contexts[ngram_length - 1]
+            .get(split_ngram(ngram).0)
+            .copied()
+            .unwrap_or_default();
+        (count, context)
+    }
+
+    /// The number of distinct characters in the training data plus one
+    /// for all characters which do not occur in it.
+    fn extended_vocabulary_size(&self) -> f64 {
+        (self.vocabulary_size + 1) as f64
+    }
+
+    fn uniform_probability(&self) -> f64 {
+        1.0 / self.extended_vocabulary_size()
+    }
+}
+
+/// Sums up the frequencies and counts the distinct ngrams for each context,
+/// which is the ngram without its last character.
+fn collect_context_statistics(
+    counts: &AHashMap<CompactString, u32>,
+) -> AHashMap<CompactString, ContextStatistics> {
+    let mut contexts: AHashMap<CompactString, ContextStatistics> = AHashMap::new();
+    for (ngram, count) in counts.iter() {
+        let statistics = contexts
+            .entry(CompactString::from(split_ngram(ngram).0))
+            .or_default();
+        statistics.total += count;
+        statistics.types += 1;
+    }
+    contexts
+}
+
+/// Splits the ngram into the context without its last character
+/// and the suffix without its first character.
+fn split_ngram(ngram: &str) -> (&str, &str) {
+    let context_end = ngram.char_indices().last().map_or(0, |(i, _)| i);
+    let suffix_start = ngram.chars().next().map_or(0, |ch| ch.len_utf8());
+    (&ngram[..context_end], &ngram[suffix_start..])
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::*;
+
+    use super::*;
+
+    #[fixture]
+    fn ngram_counts() -> NgramCounts {
+        // The unigrams, bigrams and trigrams of "abracadabra".
+        let counts = [
+            vec![("a", 5), ("b", 2), ("r", 2), ("c", 1), ("d", 1)],
+            vec![
+                ("ab", 2),
+                ("br", 2),
+                ("ra", 2),
+                ("ac", 1),
+                ("ca", 1),
+                ("ad", 1),
+                ("da", 1),
+            ],
+            vec![
+                ("abr", 2),
+                ("bra", 2),
+                ("rac", 1),
+                ("aca", 1),
+                ("cad", 1),
+                ("ada", 1),
+                ("dab", 1),
+            ],
+        ];
+
+        NgramCounts::new(
+            counts
+                .into_iter()
+                .map(|order| {
+                    order
+                        .into_iter()
+                        .map(|(ngram, count)| (CompactString::new(ngram), count))
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+
+    #[rstest(
+        smoothing,
+        case(Smoothing::AddK(0.5)),
+        case(Smoothing::WittenBell),
+        case(Smoothing::KneserNey(0.75))
+    )]
+    fn assert_smoothed_probabilities_sum_up_to_one(
+        ngram_counts: NgramCounts,
+        smoothing: Smoothing,
+    ) {
+        // The character x stands for all characters which do not occur in the training data.
+        for context in ["", "a", "r", "x", "ab", "ra", "xa", "xx"] {
+            let sum: f64 = ["a", "b", "r", "c", "d", "x"]
+                .iter()
+                .map(|ch| ngram_counts.probability(smoothing, &format!("{context}{ch}")))
+                .sum();
+
+            assert!(
+                (sum - 1.0).abs() < 1e-9,
+                "sum for context '{context}' is {sum}"
+            );
+        }
+    }
+
+    #[rstest(
+        smoothing,
+        case(Smoothing::AddK(0.5)),
+        case(Smoothing::WittenBell),
+        case(Smoothing::KneserNey(0.75))
+    )]
+    fn assert_unseen_ngrams_have_non_zero_probability(
+        ngram_counts: NgramCounts,
+        smoothing: Smoothing,
+    ) {
+        assert!(ngram_counts.probability(smoothing, "x") > 0.0);
+        assert!(ngram_counts.probability(smoothing, "abx") > 0.0);
+        assert!(ngram_counts.probability(smoothing, "xyz") > 0.0);
+        assert!(
+            ngram_counts.probability(smoothing, "abr") > ngram_counts.probability(smoothing, "abx")
+        );
+    }
+
+    #[rstest]
+    fn assert_relative_frequencies_are_not_smoothed(ngram_counts: NgramCounts) {
+        assert_eq!(ngram_counts.probability(Smoothing::None, "a"), 5.0 / 11.0);
+        assert_eq!(ngram_counts.probability(Smoothing::None, "ra"), 1.0);
+        assert_eq!(ngram_counts.probability(Smoothing::None, "abx"), 0.0);
+    }
+
+    #[rstest]
+    fn assert_kneser_ney_uses_continuation_counts_for_lower_orders(ngram_counts: NgramCounts) {
+        // The context "xb" does not occur in the training data, so the probability
+        // is taken from the bigram model which is built from continuation counts.
+        let probability = ngram_counts.probability(Smoothing::KneserNey(0.75), "xbr");
+        let expected_probability = ngram_counts.kneser_ney_probability("br", 0.75, false);
+        assert_eq!(probability, expected_probability);
+        assert_ne!(
+            probability,
+            ngram_counts.kneser_ney_probability("br", 0.75, true)
+        );
+    }
+
+    #[test]
+    fn assert_ngram_is_split_into_context_and_suffix() {
+        assert_eq!(split_ngram("äbc"), ("äb", "bc"));
+        assert_eq!(split_ngram("ä"), ("", ""));
+        assert_eq!(split_ngram(""), ("", ""));
+    }
+}
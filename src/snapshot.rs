@@ -0,0 +1,472 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::HashSet;
+use std::io;
+use std::io::{ErrorKind, Read, Write};
+use std::str::FromStr;
+
+use ahash::AHashMap;
+use compact_str::CompactString;
+use itertools::Itertools;
+use strum::IntoEnumIterator;
+
+use crate::language::Language;
+
+/// The bytes every snapshot file starts with.
+const SNAPSHOT_MAGIC: &[u8; 8] = b"LINGUASN";
+
+/// The version of the snapshot format, to be incremented whenever the
+/// layout of the encoded detector changes.
+const SNAPSHOT_FORMAT_VERSION: u32 = 4;
+
+/// The largest length of a string or collection which is accepted when reading
+/// a snapshot, so that corrupted lengths cannot cause huge allocations.
+const MAXIMUM_LENGTH: usize = u32::MAX as usize;
+
+/// The number of elements to reserve at most before reading a collection,
+/// so that the reserved capacity does not depend on an untrusted length alone.
+const MAXIMUM_INITIAL_CAPACITY: usize = 4096;
+
+/// The version of the language model crates the built-in models are taken from,
+/// to be updated together with the dependencies in `Cargo.toml`. As this version
+/// alone does not tell which models are compiled in, the header also contains the
+/// built-in languages enabled by crate features and the custom languages in use.
+pub(crate) const LANGUAGE_MODELS_VERSION: &str = "1.1.0";
+
+/// Encodes the values of a snapshot in little-endian byte order, prefixing
+/// strings and collections with their lengths.
+pub(crate) struct SnapshotWriter<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> SnapshotWriter<W> {
+    pub(crate) fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Writes the header which identifies the snapshot format, the crate and model
+    /// versions as well as the enabled built-in languages the snapshot has been created
+    /// with, followed by the ISO codes of the given custom languages.
+    pub(crate) fn write_header(&mut self, languages: &HashSet<Language>) -> io::Result<()> {
+        self.writer.write_all(SNAPSHOT_MAGIC)?;
+        self.write_u32(SNAPSHOT_FORMAT_VERSION)?;
+        self.write_str(env!("CARGO_PKG_VERSION"))?;
+        self.write_str(LANGUAGE_MODELS_VERSION)?;
+
+        let built_in_languages = Language::iter().collect_vec();
+        self.write_usize(built_in_languages.len())?;
+        for language in built_in_languages.iter() {
+            self.write_language(language)?;
+        }
+
+        let custom_languages = custom_languages(languages);
+        self.write_usize(custom_languages.len())?;
+        for language in custom_languages {
+            self.write_language(&language)?;
+            self.write_str(&language.iso_code_639_1().to_string())?;
+            self.write_str(&language.iso_code_639_3().to_string())?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn write_bool(&mut self, value: bool) -> io::Result<()> {
+        self.writer.write_all(&[u8::from(value)])
+    }
+
+    pub(crate) fn write_u8(&mut self, value: u8) -> io::Result<()> {
+        self.writer.write_all(&[value])
+    }
+
+    pub(crate) fn write_u32(&mut self, value: u32) -> io::Result<()> {
+        self.writer.write_all(&value.to_le_bytes())
+    }
+
+    pub(crate) fn write_usize(&mut self, value: usize) -> io::Result<()> {
+        self.writer.write_all(&(value as u64).to_le_bytes())
+    }
+
+    pub(crate) fn write_f64(&mut self, value: f64) -> io::Result<()> {
+        self.writer.write_all(&value.to_le_bytes())
+    }
+
+    pub(crate) fn write_str(&mut self, value: &str) -> io::Result<()> {
+        self.write_usize(value.len())?;
+        self.writer.write_all(value.as_bytes())
+    }
+
+    pub(crate) fn write_language(&mut self, language: &Language) -> io::Result<()> {
+        self.write_str(&language.to_string())
+    }
+
+    pub(crate) fn write_log_probabilities(
+        &mut self,
+        log_probabilities: &[(&str, f64)],
+    ) -> io::Result<()> {
+        self.write_usize(log_probabilities.len())?;
+        for (ngram, log_probability) in log_probabilities {
+            self.write_str(ngram)?;
+            self.write_f64(*log_probability)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn write_counts(&mut self, counts: &AHashMap<CompactString, u32>) -> io::Result<()> {
+        self.write_usize(counts.len())?;
+        for (ngram, count) in counts {
+            self.write_str(ngram)?;
+            self.write_u32(*count)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Decodes the values written by [`SnapshotWriter`].
+pub(crate) struct SnapshotReader<R: Read> {
+    reader: R,
+}
+
+impl<R: Read> SnapshotReader<R> {
+    pub(crate) fn new(reader: R) -> Self {
+        Self { reader }
+    }
+
+    /// Reads the header and fails if the snapshot has not been created with the
+    /// same snapshot format, crate version, model version and enabled built-in
+    /// languages as this library, or if one of its custom languages has not been
+    /// registered with the same ISO codes.
+    pub(crate) fn read_header(&mut self) -> io::Result<()> {
+        let mut magic = [0; SNAPSHOT_MAGIC.len()];
+        self.reader.read_exact(&mut magic)?;
+        if &magic != SNAPSHOT_MAGIC {
+            return Err(invalid_data("file is not a Lingua snapshot".to_string()));
+        }
+
+        let format_version = self.read_u32()?;
+        if format_version != SNAPSHOT_FORMAT_VERSION {
+            return Err(invalid_data(format!(
+                "snapshot format version {format_version} is not supported, \
+                 expected version {SNAPSHOT_FORMAT_VERSION}"
+            )));
+        }
+
+        let crate_version = self.read_string()?;
+        let models_version = self.read_string()?;
+        if crate_version != env!("CARGO_PKG_VERSION") || models_version != LANGUAGE_MODELS_VERSION {
+            return Err(invalid_data(format!(
+                "snapshot has been created by Lingua {crate_version} with language models \
+                 {models_version}, but this is Lingua {} with language models {}",
+                env!("CARGO_PKG_VERSION"),
+                LANGUAGE_MODELS_VERSION
+            )));
+        }
+
+        let built_in_language_count = self.read_length()?;
+        let built_in_names = (0..built_in_language_count)
+            .map(|_| self.read_string())
+            .collect::<io::Result<HashSet<_>>>()?;
+        let enabled_names = Language::iter()
+            .map(|language| language.to_string())
+            .collect::<HashSet<_>>();
+        if built_in_names != enabled_names {
+            let differing_names = built_in_names
+                .symmetric_difference(&enabled_names)
+                .sorted()
+                .join(", ");
+            return Err(invalid_data(format!(
+                "snapshot has been created with other built-in languages enabled, \
+                 differing in {differing_names}"
+            )));
+        }
+
+        for _ in 0..self.read_length()? {
+            let name = self.read_string()?;
+            let iso_code_639_1 = self.read_string()?;
+            let iso_code_639_3 = self.read_string()?;
+            let is_registered = Language::from_str(&name).is_ok_and(|language| {
+                matches!(language, Language::Custom(_))
+                    && language.iso_code_639_1().to_string() == iso_code_639_1
+                    && language.iso_code_639_3().to_string() == iso_code_639_3
+            });
+            if !is_registered {
+                return Err(invalid_data(format!(
+                    "custom language '{name}' with ISO codes '{iso_code_639_1}' and \
+                     '{iso_code_639_3}' has not been registered"
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn read_bool(&mut self) -> io::Result<bool> {
+        match self.read_u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            value => Err(invalid_data(format!("invalid boolean value {value}"))),
+        }
+    }
+
+    pub(crate) fn read_u8(&mut self) -> io::Result<u8> {
+        let mut bytes = [0; 1];
+        self.reader.read_exact(&mut bytes)?;
+        Ok(bytes[0])
+    }
+
+    pub(crate) fn read_u32(&mut self) -> io::Result<u32> {
+        let mut bytes = [0; 4];
+        self.reader.read_exact(&mut bytes)?;
+        Ok(u32::from_le_bytes(bytes))
+    }
+
+    pub(crate) fn read_usize(&mut self) -> io::Result<usize> {
+        let mut bytes = [0; 8];
+        self.reader.read_exact(&mut bytes)?;
+        usize::try_from(u64::from_le_bytes(bytes))
+            .map_err(|_| invalid_data("length exceeds the address space".to_string()))
+    }
+
+    /// Reads the length of a string or collection and fails if it is implausibly large.
+    pub(crate) fn read_length(&mut self) -> io::Result<usize> {
+        let length = self.read_usize()?;
+        if length > MAXIMUM_LENGTH {
+            return Err(invalid_data(format!(
+                "length {length} exceeds the maximum length {MAXIMUM_LENGTH}"
+            )));
+        }
+        Ok(length)
+    }
+
+    pub(crate) fn read_f64(&mut self) -> io::Result<f64> {
+        let mut bytes = [0; 8];
+        self.reader.read_exact(&mut bytes)?;
+        Ok(f64::from_le_bytes(bytes))
+    }
+
+    pub(crate) fn read_string(&mut self) -> io::Result<String> {
+        let length = self.read_length()?;
+        let mut bytes = vec![];
+        (&mut self.reader)
+            .take(length as u64)
+            .read_to_end(&mut bytes)?;
+        if bytes.len() != length {
+            return Err(ErrorKind::UnexpectedEof.into());
+        }
+        String::from_utf8(bytes).map_err(|error| invalid_data(error.to_string()))
+    }
+
+    /// Reads a language by its name. Custom languages must have been registered
+    /// before with the same name as when the snapshot has been created.
+    pub(crate) fn read_language(&mut self) -> io::Result<Language> {
+        let name = self.read_string()?;
+        Language::from_str(&name)
+            .map_err(|_| invalid_data(format!("language '{name}' is not supported")))
+    }
+
+    pub(crate) fn read_log_probabilities(&mut self) -> io::Result<Vec<(CompactString, f64)>> {
+        let length = self.read_length()?;
+        let mut log_probabilities = Vec::with_capacity(length.min(MAXIMUM_INITIAL_CAPACITY));
+        for _ in 0..length {
+            let ngram = CompactString::from(self.read_string()?);
+            log_probabilities.push((ngram, self.read_f64()?));
+        }
+        Ok(log_probabilities)
+    }
+
+    pub(crate) fn read_counts(&mut self) -> io::Result<AHashMap<CompactString, u32>> {
+        let length = self.read_length()?;
+        let mut counts = AHashMap::new();
+        for _ in 0..length {
+            let ngram = CompactString::from(self.read_string()?);
+            counts.insert(ngram, self.read_u32()?);
+        }
+        Ok(counts)
+    }
+}
+
+/// Returns the custom languages among the given ones, sorted by their names.
+fn custom_languages(languages: &HashSet<Language>) -> Vec<Language> {
+    languages
+        .iter()
+        .filter(|language| matches!(language, Language::Custom(_)))
+        .copied()
+        .sorted_by_key(|language| language.to_string())
+        .collect()
+}
+
+pub(crate) fn invalid_data(message: String) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alphabet::Alphabet;
+    use crate::language::Language::English;
+    use tempfile::tempdir;
+
+    fn write_header_without_languages(writer: &mut SnapshotWriter<&mut Vec<u8>>) {
+        writer.writer.write_all(SNAPSHOT_MAGIC).unwrap();
+        writer.write_u32(SNAPSHOT_FORMAT_VERSION).unwrap();
+        writer.write_str(env!("CARGO_PKG_VERSION")).unwrap();
+        writer.write_str(LANGUAGE_MODELS_VERSION).unwrap();
+    }
+
+    #[test]
+    fn assert_values_are_read_as_written() {
+        let counts = AHashMap::from([(CompactString::from("äöü"), 42)]);
+        let log_probabilities = [("cd", -1.5)];
+        let mut bytes = vec![];
+
+        let mut writer = SnapshotWriter::new(&mut bytes);
+        writer.write_header(&HashSet::from([English])).unwrap();
+        writer.write_bool(true).unwrap();
+        writer.write_u8(7).unwrap();
+        writer.write_usize(123_456).unwrap();
+        writer.write_f64(0.5).unwrap();
+        writer.write_str("lingua").unwrap();
+        writer.write_language(&English).unwrap();
+        writer.write_log_probabilities(&log_probabilities).unwrap();
+        writer.write_counts(&counts).unwrap();
+
+        let mut reader = SnapshotReader::new(bytes.as_slice());
+        reader.read_header().unwrap();
+        assert!(reader.read_bool().unwrap());
+        assert_eq!(reader.read_u8().unwrap(), 7);
+        assert_eq!(reader.read_usize().unwrap(), 123_456);
+        assert_eq!(reader.read_f64().unwrap(), 0.5);
+        assert_eq!(reader.read_string().unwrap(), "lingua");
+        assert_eq!(reader.read_language().unwrap(), English);
+        assert_eq!(
+            reader.read_log_probabilities().unwrap(),
+            vec![(CompactString::from("cd"), -1.5)]
+        );
+        assert_eq!(reader.read_counts().unwrap(), counts);
+        assert_eq!(
+            reader.read_u8().unwrap_err().kind(),
+            ErrorKind::UnexpectedEof
+        );
+    }
+
+    #[test]
+    fn assert_snapshot_of_other_version_is_rejected() {
+        let mut bytes = vec![];
+        let mut writer = SnapshotWriter::new(&mut bytes);
+        writer.writer.write_all(SNAPSHOT_MAGIC).unwrap();
+        writer.write_u32(SNAPSHOT_FORMAT_VERSION).unwrap();
+        writer.write_str("0.1.0").unwrap();
+        writer.write_str(LANGUAGE_MODELS_VERSION).unwrap();
+
+        let error = SnapshotReader::new(bytes.as_slice())
+            .read_header()
+            .unwrap_err();
+
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        assert!(error.to_string().contains("created by Lingua 0.1.0"));
+    }
+
+    #[test]
+    fn assert_file_without_magic_bytes_is_rejected() {
+        let error = SnapshotReader::new("not a snapshot".as_bytes())
+            .read_header()
+            .unwrap_err();
+
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        assert_eq!(error.to_string(), "file is not a Lingua snapshot");
+    }
+
+    #[test]
+    fn assert_huge_length_is_rejected() {
+        let mut bytes = vec![];
+        let mut writer = SnapshotWriter::new(&mut bytes);
+        writer.write_header(&HashSet::new()).unwrap();
+        writer.writer.write_all(&u64::MAX.to_le_bytes()).unwrap();
+
+        let mut reader = SnapshotReader::new(bytes.as_slice());
+        reader.read_header().unwrap();
+        let error = reader.read_log_probabilities().unwrap_err();
+
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        assert!(error.to_string().contains("exceeds the maximum length"));
+    }
+
+    #[test]
+    fn assert_large_length_without_data_fails_without_reserving_memory() {
+        let mut bytes = vec![];
+        let mut writer = SnapshotWriter::new(&mut bytes);
+        writer.write_usize(MAXIMUM_LENGTH).unwrap();
+
+        let error = SnapshotReader::new(bytes.as_slice())
+            .read_log_probabilities()
+            .unwrap_err();
+
+        assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn assert_snapshot_with_other_built_in_languages_is_rejected() {
+        let mut bytes = vec![];
+        let mut writer = SnapshotWriter::new(&mut bytes);
+        write_header_without_languages(&mut writer);
+        writer.write_usize(1).unwrap();
+        writer.write_str("Klingon").unwrap();
+        writer.write_usize(0).unwrap();
+
+        let error = SnapshotReader::new(bytes.as_slice())
+            .read_header()
+            .unwrap_err();
+
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        assert!(error
+            .to_string()
+            .contains("other built-in languages enabled"));
+        assert!(error.to_string().contains("Klingon"));
+    }
+
+    #[test]
+    fn assert_snapshot_with_custom_language_is_read_if_registered_with_same_iso_codes() {
+        let models_directory = tempdir().unwrap();
+        let khuzdul = Language::register_custom(
+            "Khuzdul",
+            "kz",
+            "khz",
+            &[Alphabet::Latin],
+            models_directory.path(),
+        );
+        let mut bytes = vec![];
+        SnapshotWriter::new(&mut bytes)
+            .write_header(&HashSet::from([English, khuzdul]))
+            .unwrap();
+
+        assert!(SnapshotReader::new(bytes.as_slice()).read_header().is_ok());
+
+        let offset = bytes.len() - "khz".len();
+        bytes[offset..].copy_from_slice(b"kzd");
+        let error = SnapshotReader::new(bytes.as_slice())
+            .read_header()
+            .unwrap_err();
+
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        assert_eq!(
+            error.to_string(),
+            "custom language 'Khuzdul' with ISO codes 'kz' and 'kzd' has not been registered"
+        );
+    }
+}
@@ -0,0 +1,144 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result};
+use std::io;
+use std::io::ErrorKind;
+
+use crate::language::Language;
+
+/// This enum describes why a language model could not be loaded.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ModelLoadError {
+    /// The model file does not exist.
+    Missing,
+
+    /// The model file could not be read or decompressed.
+    Unreadable(String),
+
+    /// The model file does not contain a valid language model.
+    Corrupt(String),
+
+    /// The model file has been written in a format version which is not supported.
+    UnsupportedVersion(u32),
+
+    /// The ngrams of the model file do not match the checksum it has been written with.
+    ChecksumMismatch { expected: String, actual: String },
+}
+
+impl Display for ModelLoadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            ModelLoadError::Missing => write!(f, "model file does not exist"),
+            ModelLoadError::Unreadable(message) => {
+                write!(f, "model file cannot be read: {message}")
+            }
+            ModelLoadError::Corrupt(message) => write!(f, "model file is corrupt: {message}"),
+            ModelLoadError::UnsupportedVersion(version) => {
+                write!(f, "model file format version {version} is not supported")
+            }
+            ModelLoadError::ChecksumMismatch { expected, actual } => write!(
+                f,
+                "model file checksum {actual} does not match expected checksum {expected}"
+            ),
+        }
+    }
+}
+
+impl Error for ModelLoadError {}
+
+impl From<io::Error> for ModelLoadError {
+    fn from(error: io::Error) -> Self {
+        match error.kind() {
+            ErrorKind::NotFound => ModelLoadError::Missing,
+            _ => ModelLoadError::Unreadable(error.to_string()),
+        }
+    }
+}
+
+/// This enum specifies the kinds of models which a language detector loads.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+#[cfg_attr(
+    feature = "python",
+    pyo3::prelude::pyclass(eq, eq_int, frozen, hash, ord, rename_all = "SCREAMING_SNAKE_CASE")
+)]
+pub enum ModelKind {
+    /// The relative frequencies of the ngrams, which are always loaded.
+    Ngrams,
+
+    /// The absolute frequencies of the ngrams, which are loaded for smoothing.
+    NgramCounts,
+
+    /// The relative frequencies of whole words, which are loaded for short text.
+    Words,
+}
+
+/// This struct describes a language model of a single ngram length
+/// which could not be loaded.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "python", pyo3::prelude::pyclass(frozen))]
+pub struct ModelLoadFailure {
+    pub(crate) language: Language,
+    pub(crate) kind: ModelKind,
+    pub(crate) ngram_length: usize,
+    pub(crate) error: ModelLoadError,
+}
+
+impl ModelLoadFailure {
+    /// Returns the language of the model.
+    pub fn language(&self) -> Language {
+        self.language
+    }
+    /// Returns the kind of the model.
+    pub fn kind(&self) -> ModelKind {
+        self.kind
+    }
+    /// Returns the ngram length of the model, which is 0 for word models.
+    pub fn ngram_length(&self) -> usize {
+        self.ngram_length
+    }
+    /// Returns the reason why the model could not be loaded.
+    pub fn error(&self) -> &ModelLoadError {
+        &self.error
+    }
+}
+
+/// This struct reports which ngram models of the languages of a detector have been loaded
+/// and which models of any kind have failed to load.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "python", pyo3::prelude::pyclass(frozen))]
+pub struct ModelStatus {
+    pub(crate) loaded_models: Vec<(Language, usize)>,
+    pub(crate) failures: Vec<ModelLoadFailure>,
+}
+
+impl ModelStatus {
+    /// Returns the languages and ngram lengths of the models which have been loaded,
+    /// sorted by language and ngram length.
+    pub fn loaded_models(&self) -> &[(Language, usize)] {
+        &self.loaded_models
+    }
+    /// Returns the models which have failed to load, sorted by language, kind of model
+    /// and ngram length.
+    pub fn failures(&self) -> &[ModelLoadFailure] {
+        &self.failures
+    }
+    /// Returns `true` if no model has failed to load.
+    pub fn is_healthy(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
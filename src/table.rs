@@ -0,0 +1,471 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::mem::size_of;
+
+use ahash::AHashMap;
+use compact_str::CompactString;
+
+#[cfg(feature = "compiled-models")]
+use crate::compiled::CompiledTable;
+use crate::language::Language;
+use crate::ngram::{NgramRef, MAXIMUM_NGRAM_LENGTH};
+
+/// The log-probability of an ngram in the language stored at a slot of an [`NgramTable`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct NgramEntry {
+    slot: u16,
+    log_probability: f64,
+}
+
+/// The ngram models of several languages for a single ngram length, merged into one table
+/// which maps each ngram to the log-probabilities of all languages containing it.
+/// Scoring a text therefore costs a single lookup per ngram, regardless of how many
+/// languages are compared.
+///
+/// Each language occupies a slot which is released again when the language is removed.
+#[derive(Debug, Default)]
+pub(crate) struct NgramTable {
+    rows: AHashMap<CompactString, Box<[NgramEntry]>>,
+    slots: Vec<Option<LanguageSlot>>,
+}
+
+#[derive(Debug)]
+struct LanguageSlot {
+    language: Language,
+    ngram_count: usize,
+}
+
+impl NgramTable {
+    pub(crate) fn contains(&self, language: &Language) -> bool {
+        self.slots
+            .iter()
+            .flatten()
+            .any(|slot| slot.language == *language)
+    }
+
+    /// Adds the model of the given language, given as the relative frequencies of its
+    /// ngrams. Ngrams without a positive relative frequency are left out.
+    /// Nothing is added if the table already contains the language.
+    pub(crate) fn insert(
+        &mut self,
+        language: Language,
+        relative_frequencies: AHashMap<CompactString, f64>,
+    ) {
+        if self.contains(&language) {
+            return;
+        }
+
+        // Filtering loses the exact length of the model, so capacity is reserved up front.
+        self.rows.reserve(relative_frequencies.len());
+        self.insert_log_probabilities(
+            language,
+            relative_frequencies
+                .into_iter()
+                .filter(|(_, relative_frequency)| *relative_frequency > 0.0)
+                .map(|(ngram, relative_frequency)| (ngram, relative_frequency.ln())),
+        );
+    }
+
+    /// Adds the model of the given language, given as the log-probabilities of its ngrams.
+    /// Nothing is added if the table already contains the language.
+    pub(crate) fn insert_log_probabilities(
+        &mut self,
+        language: Language,
+        log_probabilities: impl IntoIterator<Item = (CompactString, f64)>,
+    ) {
+        if self.contains(&language) {
+            return;
+        }
+
+        let slot = self.free_slot();
+        let mut ngram_count = 0;
+        let log_probabilities = log_probabilities.into_iter();
+        self.rows.reserve(log_probabilities.size_hint().0);
+
+        for (ngram, log_probability) in log_probabilities {
+            let entry = NgramEntry {
+                slot,
+                log_probability,
+            };
+            match self.rows.get_mut(&ngram) {
+                Some(row) => {
+                    let mut entries = Vec::with_capacity(row.len() + 1);
+                    entries.extend_from_slice(row);
+                    entries.push(entry);
+                    *row = entries.into_boxed_slice();
+                }
+                None => {
+                    self.rows.insert(ngram, Box::new([entry]));
+                }
+            }
+            ngram_count += 1;
+        }
+
+        self.slots[usize::from(slot)] = Some(LanguageSlot {
+            language,
+            ngram_count,
+        });
+    }
+
+    /// Removes the models of the given languages in a single pass over the table
+    /// and releases the memory they have occupied.
+    pub(crate) fn remove(&mut self, languages: &HashSet<Language>) {
+        let mut is_removed = vec![false; self.slots.len()];
+
+        for (index, slot) in self.slots.iter_mut().enumerate() {
+            if slot
+                .as_ref()
+                .is_some_and(|slot| languages.contains(&slot.language))
+            {
+                *slot = None;
+                is_removed[index] = true;
+            }
+        }
+
+        if !is_removed.contains(&true) {
+            return;
+        }
+
+        self.rows.retain(|_, row| {
+            if row.iter().any(|entry| is_removed[usize::from(entry.slot)]) {
+                *row = row
+                    .iter()
+                    .filter(|entry| !is_removed[usize::from(entry.slot)])
+                    .copied()
+                    .collect();
+            }
+            !row.is_empty()
+        });
+        self.rows.shrink_to_fit();
+    }
+
+    /// Estimates the number of bytes occupied by the model of the given language.
+    /// Rows are counted as if they were not shared with other languages,
+    /// so the estimate is an upper bound of the memory freed by removing the model.
+    pub(crate) fn estimate_size(&self, language: &Language) -> usize {
+        let row_size = size_of::<CompactString>() + size_of::<Box<[NgramEntry]>>() + 1;
+        let entry_size = size_of::<NgramEntry>();
+
+        self.slots
+            .iter()
+            .flatten()
+            .find(|slot| slot.language == *language)
+            .map_or(0, |slot| slot.ngram_count * (row_size + entry_size))
+    }
+
+    /// Returns the log-probabilities of the ngrams of the given languages,
+    /// collected in a single pass over the table.
+    pub(crate) fn log_probabilities_of(
+        &self,
+        languages: &HashSet<Language>,
+    ) -> BTreeMap<Language, Vec<(&str, f64)>> {
+        let mut log_probabilities = BTreeMap::new();
+
+        for slot in self.slots.iter().flatten() {
+            if languages.contains(&slot.language) {
+                log_probabilities.insert(slot.language, Vec::with_capacity(slot.ngram_count));
+            }
+        }
+
+        for (ngram, row) in self.rows.iter() {
+            for entry in row.iter() {
+                if let Some(ngrams) = self
+                    .language_at(usize::from(entry.slot))
+                    .and_then(|language| log_probabilities.get_mut(&language))
+                {
+                    ngrams.push((ngram.as_str(), entry.log_probability));
+                }
+            }
+        }
+
+        log_probabilities
+    }
+
+    #[cfg(test)]
+    pub(crate) fn log_probability(&self, language: &Language, ngram: &str) -> Option<f64> {
+        self.rows.get(ngram)?.iter().find_map(|entry| {
+            (self.language_at(usize::from(entry.slot)) == Some(*language))
+                .then_some(entry.log_probability)
+        })
+    }
+
+    fn language_at(&self, slot: usize) -> Option<Language> {
+        self.slots[slot].as_ref().map(|slot| slot.language)
+    }
+
+    fn free_slot(&mut self) -> u16 {
+        let index = match self.slots.iter().position(Option::is_none) {
+            Some(index) => index,
+            None => {
+                self.slots.push(None);
+                self.slots.len() - 1
+            }
+        };
+        u16::try_from(index).expect("number of languages in ngram table exceeds 65536")
+    }
+}
+
+/// A table of ngram log-probabilities, either loaded at runtime or compiled into the binary.
+#[derive(Clone, Copy)]
+pub(crate) enum NgramTableRef<'a> {
+    Loaded(&'a NgramTable),
+    #[cfg(feature = "compiled-models")]
+    Compiled(CompiledTable),
+}
+
+impl NgramTableRef<'_> {
+    fn slot_count(&self) -> usize {
+        match self {
+            NgramTableRef::Loaded(table) => table.slots.len(),
+            #[cfg(feature = "compiled-models")]
+            NgramTableRef::Compiled(_) => CompiledTable::slot_count(),
+        }
+    }
+
+    fn language_at(&self, slot: usize) -> Option<Language> {
+        match self {
+            NgramTableRef::Loaded(table) => table.language_at(slot),
+            #[cfg(feature = "compiled-models")]
+            NgramTableRef::Compiled(_) => Some(CompiledTable::language_at(slot)),
+        }
+    }
+
+    fn for_each_entry(&self, ngram: &str, mut callback: impl FnMut(usize, f64)) {
+        match self {
+            NgramTableRef::Loaded(table) => {
+                if let Some(row) = table.rows.get(ngram) {
+                    for entry in row.iter() {
+                        callback(usize::from(entry.slot), entry.log_probability);
+                    }
+                }
+            }
+            #[cfg(feature = "compiled-models")]
+            NgramTableRef::Compiled(table) => table.for_each_entry(ngram, callback),
+        }
+    }
+}
+
+/// Scores ngrams for a fixed set of languages against the tables of all ngram lengths.
+/// The slots of each table are mapped to the positions of their languages beforehand,
+/// so that the entries found for an ngram are accumulated without any further lookup.
+pub(crate) struct NgramScorer<'a> {
+    languages: Vec<Language>,
+    tables: [Vec<(NgramTableRef<'a>, Vec<Option<usize>>)>; MAXIMUM_NGRAM_LENGTH],
+}
+
+impl<'a> NgramScorer<'a> {
+    pub(crate) fn new(languages: &HashSet<Language>) -> Self {
+        Self {
+            languages: languages.iter().copied().collect(),
+            tables: Default::default(),
+        }
+    }
+
+    /// Adds a table of the given ngram length unless it contains none of the languages.
+    pub(crate) fn add(&mut self, ngram_length: usize, table: NgramTableRef<'a>) {
+        let positions: HashMap<Language, usize> = self
+            .languages
+            .iter()
+            .enumerate()
+            .map(|(position, language)| (*language, position))
+            .collect();
+
+        let slot_positions: Vec<Option<usize>> = (0..table.slot_count())
+            .map(|slot| {
+                table
+                    .language_at(slot)
+                    .and_then(|language| positions.get(&language).copied())
+            })
+            .collect();
+
+        if slot_positions.iter().any(Option::is_some) {
+            self.tables[ngram_length - 1].push((table, slot_positions));
+        }
+    }
+
+    /// Sums up the log-probabilities of the given ngrams for each language.
+    /// For each ngram and language, the log-probability of the longest of the ngram
+    /// and its prefixes which is known to the language is added.
+    pub(crate) fn sum_up_log_probabilities(&self, ngrams: &[NgramRef]) -> Vec<(Language, f64)> {
+        let mut sums = vec![0.0; self.languages.len()];
+        let mut last_scored_ngrams = vec![usize::MAX; self.languages.len()];
+
+        for (index, ngram) in ngrams.iter().enumerate() {
+            let mut scored_language_count = 0;
+
+            for ngram in ngram.range_of_lower_order_ngrams() {
+                for (table, slot_positions) in self.tables[ngram.char_count - 1].iter() {
+                    table.for_each_entry(ngram.value, |slot, log_probability| {
+                        if let Some(position) = slot_positions[slot] {
+                            if last_scored_ngrams[position] != index {
+                                last_scored_ngrams[position] = index;
+                                sums[position] += log_probability;
+                                scored_language_count += 1;
+                            }
+                        }
+                    });
+                }
+                if scored_language_count == self.languages.len() {
+                    break;
+                }
+            }
+        }
+
+        self.languages.iter().copied().zip(sums).collect()
+    }
+
+    /// Counts for each language how many of the given ngrams it knows.
+    pub(crate) fn count_known_ngrams(&self, ngrams: &[NgramRef]) -> Vec<(Language, u32)> {
+        let mut counts = vec![0; self.languages.len()];
+
+        for ngram in ngrams {
+            for (table, slot_positions) in self.tables[ngram.char_count - 1].iter() {
+                table.for_each_entry(ngram.value, |slot, _| {
+                    if let Some(position) = slot_positions[slot] {
+                        counts[position] += 1;
+                    }
+                });
+            }
+        }
+
+        self.languages.iter().copied().zip(counts).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::Language::{English, French, German};
+
+    fn relative_frequencies(frequencies: &[(&str, f64)]) -> AHashMap<CompactString, f64> {
+        frequencies
+            .iter()
+            .map(|(ngram, frequency)| (CompactString::new(ngram), *frequency))
+            .collect()
+    }
+
+    fn table_for_english_and_german() -> NgramTable {
+        let mut table = NgramTable::default();
+        table.insert(
+            English,
+            relative_frequencies(&[("a", 0.5), ("b", 0.25), ("c", 0.0)]),
+        );
+        table.insert(German, relative_frequencies(&[("a", 0.125), ("d", 0.5)]));
+        table
+    }
+
+    fn ngrams(ngrams: &[&'static str]) -> Vec<NgramRef<'static>> {
+        ngrams.iter().map(|ngram| NgramRef::new(ngram)).collect()
+    }
+
+    #[test]
+    fn assert_models_are_merged_into_shared_rows() {
+        let table = table_for_english_and_german();
+
+        assert_eq!(table.rows.len(), 3);
+        assert_eq!(table.rows["a"].len(), 2);
+        assert_eq!(table.log_probability(&English, "a"), Some(0.5f64.ln()));
+        assert_eq!(table.log_probability(&German, "a"), Some(0.125f64.ln()));
+        assert_eq!(table.log_probability(&English, "c"), None);
+        assert_eq!(table.log_probability(&German, "b"), None);
+    }
+
+    #[test]
+    fn assert_removed_language_releases_its_slot_and_rows() {
+        let mut table = table_for_english_and_german();
+        table.remove(&HashSet::from([English]));
+
+        assert!(!table.contains(&English));
+        assert!(table.contains(&German));
+        assert_eq!(table.rows.len(), 2);
+        assert_eq!(table.log_probability(&English, "a"), None);
+        assert_eq!(table.estimate_size(&English), 0);
+
+        table.insert(French, relative_frequencies(&[("e", 1.0)]));
+        assert_eq!(table.slots.len(), 2);
+        assert_eq!(table.log_probability(&French, "e"), Some(0.0));
+    }
+
+    #[test]
+    fn assert_language_is_inserted_only_once() {
+        let mut table = table_for_english_and_german();
+        table.insert(English, relative_frequencies(&[("z", 1.0)]));
+
+        assert_eq!(table.log_probability(&English, "z"), None);
+        assert_eq!(table.estimate_size(&English), table.estimate_size(&German));
+    }
+
+    #[test]
+    fn assert_model_size_is_estimated_correctly() {
+        let table = table_for_english_and_german();
+        let size = table.estimate_size(&English);
+
+        assert_eq!(NgramTable::default().estimate_size(&English), 0);
+        assert!(size >= 2 * (size_of::<CompactString>() + size_of::<NgramEntry>()));
+    }
+
+    #[test]
+    fn assert_log_probabilities_are_collected_per_language() {
+        let table = table_for_english_and_german();
+        let mut log_probabilities = table.log_probabilities_of(&HashSet::from([German]));
+
+        assert_eq!(log_probabilities.len(), 1);
+        let ngrams = log_probabilities.get_mut(&German).unwrap();
+        ngrams.sort_by(|first, second| first.0.cmp(second.0));
+        assert_eq!(ngrams, &vec![("a", 0.125f64.ln()), ("d", 0.5f64.ln())]);
+    }
+
+    #[test]
+    fn assert_scorer_backs_off_to_shorter_ngrams_per_language() {
+        let unigrams = table_for_english_and_german();
+        let mut bigrams = NgramTable::default();
+        bigrams.insert(German, relative_frequencies(&[("ab", 0.25)]));
+
+        let mut scorer = NgramScorer::new(&HashSet::from([English, German, French]));
+        scorer.add(1, NgramTableRef::Loaded(&unigrams));
+        scorer.add(2, NgramTableRef::Loaded(&bigrams));
+
+        let sums: HashMap<_, _> = scorer
+            .sum_up_log_probabilities(&ngrams(&["ab", "bd"]))
+            .into_iter()
+            .collect();
+
+        assert_eq!(sums[&English], 0.5f64.ln() + 0.25f64.ln());
+        assert_eq!(sums[&German], 0.25f64.ln());
+        assert_eq!(sums[&French], 0.0);
+
+        let counts: HashMap<_, _> = scorer
+            .count_known_ngrams(&ngrams(&["a", "b", "d"]))
+            .into_iter()
+            .collect();
+
+        assert_eq!(
+            counts,
+            HashMap::from([(English, 2), (German, 2), (French, 0)])
+        );
+    }
+
+    #[test]
+    fn assert_scorer_ignores_tables_without_its_languages() {
+        let table = table_for_english_and_german();
+        let mut scorer = NgramScorer::new(&HashSet::from([French]));
+        scorer.add(1, NgramTableRef::Loaded(&table));
+
+        assert!(scorer.tables[0].is_empty());
+    }
+}
@@ -0,0 +1,85 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::ops::Range;
+
+use crate::constant::{LETTERS, TOKENS_WITH_OPTIONAL_WHITESPACE};
+
+/// This trait splits text into the words and tokens that the language detection operates on.
+///
+/// A custom implementation, such as a dictionary-based segmenter for Chinese or Japanese,
+/// can be passed to
+/// [`LanguageDetectorBuilder::with_tokenizer`](crate::LanguageDetectorBuilder::with_tokenizer)
+/// and [`LanguageModelFilesWriter::with_tokenizer`](crate::LanguageModelFilesWriter::with_tokenizer).
+/// The text passed to the tokenizer has already been normalized.
+pub trait Tokenizer: Send + Sync {
+    /// Returns the words of the given text from which ngrams are extracted.
+    ///
+    /// Characters which are not part of any word, such as whitespace, punctuation
+    /// and numbers, must be omitted.
+    fn words<'a>(&self, text: &'a str) -> Vec<&'a str>;
+
+    /// Returns the byte ranges of the tokens of the given text which are used to
+    /// find the boundaries between languages in mixed-language text.
+    ///
+    /// The ranges must be in ascending order and must not overlap. Each range should
+    /// include the whitespace and punctuation which surround the respective word.
+    fn token_ranges(&self, text: &str) -> Vec<Range<usize>>;
+}
+
+/// The tokenizer used by default. It treats every Chinese, Japanese and Korean
+/// character as a separate word and splits any other text at each character
+/// which is not a letter.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefaultTokenizer;
+
+impl Tokenizer for DefaultTokenizer {
+    fn words<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        LETTERS.find_iter(text).map(|mat| mat.as_str()).collect()
+    }
+
+    fn token_ranges(&self, text: &str) -> Vec<Range<usize>> {
+        TOKENS_WITH_OPTIONAL_WHITESPACE
+            .find_iter(text)
+            .map(|mat| mat.range())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn assert_default_tokenizer_splits_text_into_words() {
+        assert_eq!(
+            DefaultTokenizer.words("Weltweit    gibt es ungefähr 6.000 Sprachen, 北京."),
+            vec!["Weltweit", "gibt", "es", "ungefähr", "Sprachen", "北", "京"]
+        );
+    }
+
+    #[test]
+    fn assert_default_tokenizer_returns_token_ranges() {
+        let text = " I don't know, 北京 ";
+        let tokens = DefaultTokenizer
+            .token_ranges(text)
+            .into_iter()
+            .map(|range| &text[range])
+            .collect::<Vec<_>>();
+
+        assert_eq!(tokens, vec![" I ", "don't ", "know, ", "北", "京 "]);
+    }
+}
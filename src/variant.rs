@@ -0,0 +1,263 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter, Result};
+use std::fs::read_dir;
+use std::io;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+use compact_str::CompactString;
+use strum::ParseError;
+
+use crate::isocode::IsoCode639_1;
+use crate::json::{load_compressed_file_from_directory, WORD_MODEL_FILE_NAME};
+use crate::language::Language;
+use crate::model::{TestDataLanguageModel, WordModel};
+use crate::ngram::{Ngram, MAXIMUM_NGRAM_LENGTH};
+use crate::snapshot::{invalid_data, SnapshotReader, SnapshotWriter};
+
+pub(crate) const LANGUAGE_VARIANT_REGION_MESSAGE: &str =
+    "The region of a language variant must consist of two ASCII letters or three ASCII digits";
+
+/// This struct describes a regional variant of a language, such as Brazilian Portuguese.
+///
+/// It is expressed as a BCP 47 tag consisting of the ISO 639-1 code of the language and a
+/// region subtag, which is either an ISO 3166-1 country code such as `BR` or a UN M.49
+/// area code such as `419` for Latin America.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct LanguageVariant {
+    language: Language,
+    region: CompactString,
+}
+
+impl LanguageVariant {
+    /// Creates a regional variant of the given language.
+    ///
+    /// ⚠ Panics if the region consists neither of two ASCII letters nor of three ASCII digits.
+    pub fn new(language: Language, region: &str) -> Self {
+        if !is_valid_region(region) {
+            panic!("{}", LANGUAGE_VARIANT_REGION_MESSAGE);
+        }
+        Self {
+            language,
+            region: CompactString::from(region.to_ascii_uppercase()),
+        }
+    }
+
+    /// Returns the language of this variant.
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    /// Returns the region subtag of this variant in uppercase.
+    pub fn region(&self) -> &str {
+        &self.region
+    }
+}
+
+impl Display for LanguageVariant {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}-{}", self.language.iso_code_639_1(), self.region)
+    }
+}
+
+impl FromStr for LanguageVariant {
+    type Err = ParseError;
+
+    /// Parses a BCP 47 tag such as `pt-BR` or `es-419`. Underscores are accepted
+    /// as separators as well.
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let (language_subtag, region) = s
+            .split_once(['-', '_'])
+            .ok_or(ParseError::VariantNotFound)?;
+        let iso_code = IsoCode639_1::from_str(language_subtag)?;
+
+        if !is_valid_region(region) {
+            return Err(ParseError::VariantNotFound);
+        }
+
+        Ok(Self::new(Language::from_iso_code_639_1(&iso_code), region))
+    }
+}
+
+pub(crate) fn is_valid_region(region: &str) -> bool {
+    (region.len() == 2 && region.chars().all(|ch| ch.is_ascii_alphabetic()))
+        || (region.len() == 3 && region.chars().all(|ch| ch.is_ascii_digit()))
+}
+
+/// The language models of a single regional variant, which are compared with the
+/// models of the other variants of the same language only.
+pub(crate) struct LanguageVariantModel {
+    pub(crate) variant: LanguageVariant,
+    ngram_models: Vec<WordModel>,
+    word_model: Option<WordModel>,
+}
+
+impl LanguageVariantModel {
+    /// Loads the models of all variants of the given languages from the subdirectories
+    /// of the given directory, which are named after the BCP 47 tags of the variants.
+    /// Subdirectories which do not contain a unigram model are skipped.
+    pub(crate) fn load_all(directory: &Path, languages: &HashSet<Language>) -> Vec<Self> {
+        let Ok(entries) = read_dir(directory) else {
+            return vec![];
+        };
+
+        let mut models = entries
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                let variant = LanguageVariant::from_str(path.file_name()?.to_str()?).ok()?;
+                if languages.contains(&variant.language) {
+                    Self::load(variant, &path)
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+
+        models.sort_by(|first, second| first.variant.cmp(&second.variant));
+        models
+    }
+
+    fn load(variant: LanguageVariant, directory: &Path) -> Option<Self> {
+        // Unseen ngrams are treated like unseen words, so the word model
+        // serves for the ngram models as well.
+        let ngram_models = (1..=MAXIMUM_NGRAM_LENGTH)
+            .map_while(|ngram_length| {
+                let ngram_name = Ngram::find_ngram_name_by_length(ngram_length);
+                let json = load_compressed_file_from_directory(
+                    directory,
+                    &format!("{ngram_name}s.json.br"),
+                )
+                .ok()?;
+                WordModel::from_json(&json, false).ok()
+            })
+            .collect::<Vec<_>>();
+
+        if ngram_models.is_empty() {
+            return None;
+        }
+
+        let word_model = load_compressed_file_from_directory(directory, WORD_MODEL_FILE_NAME)
+            .ok()
+            .and_then(|json| WordModel::from_json(&json, false).ok());
+
+        Some(Self {
+            variant,
+            ngram_models,
+            word_model,
+        })
+    }
+
+    pub(crate) fn write_snapshot<W: Write>(
+        &self,
+        writer: &mut SnapshotWriter<W>,
+    ) -> io::Result<()> {
+        writer.write_str(&self.variant.to_string())?;
+        writer.write_usize(self.ngram_models.len())?;
+        for ngram_model in self.ngram_models.iter() {
+            ngram_model.write_snapshot(writer)?;
+        }
+        writer.write_bool(self.word_model.is_some())?;
+        if let Some(word_model) = &self.word_model {
+            word_model.write_snapshot(writer)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn read_snapshot<R: Read>(reader: &mut SnapshotReader<R>) -> io::Result<Self> {
+        let tag = reader.read_string()?;
+        let variant = LanguageVariant::from_str(&tag)
+            .map_err(|_| invalid_data(format!("language variant '{tag}' is not supported")))?;
+        let ngram_model_count = reader.read_usize()?;
+        let ngram_models = (0..ngram_model_count)
+            .map(|_| WordModel::read_snapshot(reader))
+            .collect::<io::Result<Vec<_>>>()?;
+        let word_model = if reader.read_bool()? {
+            Some(WordModel::read_snapshot(reader)?)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            variant,
+            ngram_models,
+            word_model,
+        })
+    }
+
+    /// Sums up the log-probabilities of all ngrams and words of the given text.
+    pub(crate) fn log_probability(&self, words: &[&str]) -> f64 {
+        let mut sum = 0.0;
+
+        for (i, ngram_model) in self.ngram_models.iter().enumerate() {
+            let test_data_model = TestDataLanguageModel::from(words, i + 1);
+            sum += test_data_model
+                .ngrams
+                .iter()
+                .map(|ngram| ngram_model.log_probability(ngram.value))
+                .sum::<f64>();
+        }
+
+        if let Some(word_model) = &self.word_model {
+            sum += words
+                .iter()
+                .map(|word| word_model.log_probability(word))
+                .sum::<f64>();
+        }
+
+        sum
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::language::Language::{English, Portuguese, Spanish};
+
+    use super::*;
+
+    #[test]
+    fn assert_language_variant_string_representation_is_correct() {
+        assert_eq!(LanguageVariant::new(Portuguese, "br").to_string(), "pt-BR");
+        assert_eq!(LanguageVariant::new(Spanish, "419").to_string(), "es-419");
+    }
+
+    #[test]
+    fn assert_string_to_language_variant_is_correct() {
+        assert_eq!(
+            LanguageVariant::from_str("en-GB"),
+            Ok(LanguageVariant::new(English, "GB"))
+        );
+        assert_eq!(
+            LanguageVariant::from_str("es_419"),
+            Ok(LanguageVariant::new(Spanish, "419"))
+        );
+        assert!(LanguageVariant::from_str("en").is_err());
+        assert!(LanguageVariant::from_str("xx-GB").is_err());
+        assert!(LanguageVariant::from_str("en-G1").is_err());
+        assert!(LanguageVariant::from_str("en-GBR").is_err());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "The region of a language variant must consist of two ASCII letters or three ASCII digits"
+    )]
+    fn assert_language_variant_with_invalid_region_cannot_be_created() {
+        LanguageVariant::new(English, "1B");
+    }
+}
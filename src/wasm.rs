@@ -23,10 +23,16 @@ use serde::{Deserialize, Serialize};
 
 use wasm_bindgen::prelude::*;
 
-use crate::builder::{MINIMUM_RELATIVE_DISTANCE_MESSAGE, MISSING_LANGUAGE_MESSAGE};
+use crate::builder::{
+    is_valid_add_k_constant, is_valid_kneser_ney_discount, is_valid_ngram_length_range,
+    is_valid_word_model_weight, ADD_K_SMOOTHING_MESSAGE, KNESER_NEY_SMOOTHING_MESSAGE,
+    MACRO_LANGUAGES_MESSAGE, MEMORY_BUDGET_MESSAGE, MINIMUM_RELATIVE_DISTANCE_MESSAGE,
+    MISSING_LANGUAGE_MESSAGE, NGRAM_LENGTHS_MESSAGE, WORD_MODEL_WEIGHT_MESSAGE,
+};
 use crate::{
     convert_byte_indices_to_char_indices, IsoCode639_1, IsoCode639_3, Language,
-    LanguageDetector as Detector, LanguageDetectorBuilder as Builder,
+    LanguageDetector as Detector, LanguageDetectorBuilder as Builder, MacroLanguage, Normalizer,
+    Smoothing,
 };
 
 /// This class configures and creates an instance of `LanguageDetector`.
@@ -48,6 +54,17 @@ pub struct ConfidenceValue {
     pub value: f64,
 }
 
+/// This class describes the outcome of detecting the language of a text.
+/// At most one of both fields is set. If neither is set, the language
+/// cannot be reliably detected.
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
+pub struct DetectionOutcome {
+    /// Returns the detected language.
+    pub language: Option<String>,
+    /// Returns the alphabet of the text if none of the languages of the detector uses it.
+    pub unsupportedAlphabet: Option<String>,
+}
+
 /// This class describes a contiguous single-language
 /// text section within a possibly mixed-language text.
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
@@ -263,11 +280,200 @@ impl LanguageDetectorBuilder {
         self.clone()
     }
 
+    /// Enables the accent-insensitive mode in order to classify text whose diacritics
+    /// have been stripped.
+    ///
+    /// This method removes diacritics from both the input text and the language models
+    /// of all languages written in the Latin or Greek script before comparing them.
+    /// Rules based on characters unique to a language still see the original input text.
+    pub fn withAccentInsensitiveMode(&mut self) -> Self {
+        self.builder.with_accent_insensitive_mode();
+        self.clone()
+    }
+
+    /// Configures `LanguageDetectorBuilder` to derive the character rules of the rule-based
+    /// engine from the unigram models of the languages of the detector instead of using the
+    /// hand-maintained tables.
+    pub fn withDerivedCharacterRules(&mut self) -> Self {
+        self.builder.with_derived_character_rules();
+        self.clone()
+    }
+
+    /// Configures `LanguageDetectorBuilder` to use language models whose ngrams include
+    /// word boundary markers. They are not bundled with the built-in languages, so building
+    /// the detector fails unless they are available for custom languages.
+    pub fn withWordBoundaryNgrams(&mut self) -> Self {
+        self.builder.with_word_boundary_ngrams();
+        self.clone()
+    }
+
+    /// Configures `LanguageDetectorBuilder` to use the ngram lengths from the given minimum
+    /// up to and including the given maximum length for short and long text, respectively.
+    /// By default, unigrams up to fivegrams are used for short text and only trigrams are
+    /// used for text of 120 characters or more.
+    ///
+    /// ⚠ Throws an error if one of the ranges is empty or does not lie in between 1 and 8.
+    pub fn withNgramLengths(
+        &mut self,
+        shortTextMinimumLength: usize,
+        shortTextMaximumLength: usize,
+        longTextMinimumLength: usize,
+        longTextMaximumLength: usize,
+    ) -> Result<LanguageDetectorBuilder, JsValue> {
+        let short_text_ngram_lengths = shortTextMinimumLength..=shortTextMaximumLength;
+        let long_text_ngram_lengths = longTextMinimumLength..=longTextMaximumLength;
+
+        if !is_valid_ngram_length_range(&short_text_ngram_lengths)
+            || !is_valid_ngram_length_range(&long_text_ngram_lengths)
+        {
+            return Err(JsValue::from(NGRAM_LENGTHS_MESSAGE));
+        }
+        self.builder
+            .with_ngram_lengths(short_text_ngram_lengths, long_text_ngram_lengths);
+        Ok(self.clone())
+    }
+
+    /// Configures `LanguageDetectorBuilder` to estimate the ngram probabilities with add-k
+    /// smoothing. Smoothing requires the ngram count files of all languages of the detector.
+    /// They are not bundled with the built-in languages, so smoothing is restricted to custom
+    /// languages.
+    ///
+    /// ⚠ Throws an error if `k` is not greater than 0.0.
+    pub fn withAddKSmoothing(&mut self, k: f64) -> Result<LanguageDetectorBuilder, JsValue> {
+        if !is_valid_add_k_constant(k) {
+            return Err(JsValue::from(ADD_K_SMOOTHING_MESSAGE));
+        }
+        self.builder.with_smoothing(Smoothing::AddK(k));
+        Ok(self.clone())
+    }
+
+    /// Configures `LanguageDetectorBuilder` to estimate the ngram probabilities with
+    /// Witten-Bell smoothing. Smoothing requires the ngram count files of all languages
+    /// of the detector. They are not bundled with the built-in languages, so smoothing is
+    /// restricted to custom languages.
+    pub fn withWittenBellSmoothing(&mut self) -> Self {
+        self.builder.with_smoothing(Smoothing::WittenBell);
+        self.clone()
+    }
+
+    /// Configures `LanguageDetectorBuilder` to estimate the ngram probabilities with
+    /// interpolated Kneser-Ney smoothing. Smoothing requires the ngram count files of
+    /// all languages of the detector. They are not bundled with the built-in languages,
+    /// so smoothing is restricted to custom languages.
+    ///
+    /// ⚠ Throws an error if `discount` does not lie in between 0.0 and 1.0.
+    pub fn withKneserNeySmoothing(
+        &mut self,
+        discount: f64,
+    ) -> Result<LanguageDetectorBuilder, JsValue> {
+        if !is_valid_kneser_ney_discount(discount) {
+            return Err(JsValue::from(KNESER_NEY_SMOOTHING_MESSAGE));
+        }
+        self.builder.with_smoothing(Smoothing::KneserNey(discount));
+        Ok(self.clone())
+    }
+
+    /// Configures `LanguageDetectorBuilder` to combine the ngram models with the relative
+    /// frequencies of whole words for text consisting of one or two words, using the given
+    /// weight for the words. The word models are not bundled with the built-in languages,
+    /// so this option is restricted to custom languages.
+    ///
+    /// ⚠ Throws an error if `weight` is not greater than 0.0 or greater than 1.0.
+    pub fn withWordModel(&mut self, weight: f64) -> Result<LanguageDetectorBuilder, JsValue> {
+        if !is_valid_word_model_weight(weight) {
+            return Err(JsValue::from(WORD_MODEL_WEIGHT_MESSAGE));
+        }
+        self.builder.with_word_model(weight);
+        Ok(self.clone())
+    }
+
+    /// Configures `LanguageDetectorBuilder` to apply the given normalizers, such as
+    /// `Nfkc` or `SquashElongation`, to the input text before it is split into words.
+    /// The normalizers are applied in the given order.
+    ///
+    /// ⚠ Throws an error if a normalizer name is not recognized.
+    #[wasm_bindgen(variadic)]
+    pub fn withNormalizers(
+        &mut self,
+        normalizers: Box<[JsValue]>,
+    ) -> Result<LanguageDetectorBuilder, JsValue> {
+        let mut selected_normalizers = vec![];
+
+        for name in normalizers.iter().filter_map(|it| it.as_string()) {
+            match Normalizer::from_str(&name) {
+                Ok(normalizer) => selected_normalizers.push(normalizer),
+                Err(_) => {
+                    return Err(JsValue::from(format!(
+                        "Normalizer '{}' is not supported",
+                        name
+                    )))
+                }
+            }
+        }
+
+        self.builder.with_normalizers(&selected_normalizers);
+        Ok(self.clone())
+    }
+
+    /// Configures `LanguageDetectorBuilder` to collapse the languages of the given
+    /// macrolanguages, such as `Norwegian` or `SerboCroatian`, into their macrolanguage.
+    /// The macrolanguages are reported by `detectCollapsedLanguageOf` and
+    /// `computeCollapsedLanguageConfidenceValues` of the detector.
+    ///
+    /// ⚠ Throws an error if no macrolanguage is passed or if a macrolanguage name
+    /// is not recognized.
+    #[wasm_bindgen(variadic)]
+    pub fn withMacroLanguages(
+        &mut self,
+        macro_languages: Box<[JsValue]>,
+    ) -> Result<LanguageDetectorBuilder, JsValue> {
+        let mut selected_macro_languages = vec![];
+
+        for name in macro_languages.iter().filter_map(|it| it.as_string()) {
+            match MacroLanguage::from_str(&name) {
+                Ok(macro_language) => selected_macro_languages.push(macro_language),
+                Err(_) => {
+                    return Err(JsValue::from(format!(
+                        "Macro language '{}' is not supported",
+                        name
+                    )))
+                }
+            }
+        }
+
+        if selected_macro_languages.is_empty() {
+            return Err(JsValue::from(MACRO_LANGUAGES_MESSAGE));
+        }
+
+        self.builder.with_macro_languages(&selected_macro_languages);
+        Ok(self.clone())
+    }
+
+    /// Configures `LanguageDetectorBuilder` to keep the memory occupied by the loaded
+    /// language models below the given number of bytes. The least recently used models
+    /// are evicted once the budget is exceeded and loaded again when they are needed.
+    /// The budget applies to each detector separately and covers only the models it has used.
+    ///
+    /// ⚠ Throws an error if `bytes` is 0.
+    pub fn withMemoryBudget(&mut self, bytes: usize) -> Result<LanguageDetectorBuilder, JsValue> {
+        if bytes == 0 {
+            return Err(JsValue::from(MEMORY_BUDGET_MESSAGE));
+        }
+        self.builder.with_memory_budget(bytes);
+        Ok(self.clone())
+    }
+
     /// Creates and returns the configured instance of `LanguageDetector`.
-    pub fn build(&mut self) -> LanguageDetector {
-        LanguageDetector {
-            detector: self.builder.build(),
+    ///
+    /// ⚠ Throws an error if the models required by one of the configured modes
+    /// are missing for any of the languages.
+    pub fn build(&mut self) -> Result<LanguageDetector, JsValue> {
+        if let Some(message) = self.builder.missing_models_message() {
+            return Err(JsValue::from(message));
         }
+        Ok(LanguageDetector {
+            detector: self.builder.build(),
+        })
     }
 }
 
@@ -276,12 +482,58 @@ impl LanguageDetector {
     /// Detects the language of given input text.
     /// If the language cannot be reliably detected, `undefined` is returned.
     pub fn detectLanguageOf(&self, text: &str) -> Option<String> {
-        match self.detector.detect_language_of(text) {
+        match self.detector.detect_language_of_str(text) {
             Some(language) => Some(language.to_string()),
             None => None,
         }
     }
 
+    /// Detects the alphabet of given input text if none of the languages of this
+    /// `LanguageDetector` instance is written in it. If the predominant alphabet of the
+    /// text is used by at least one of the configured languages, or if no alphabet can be
+    /// determined, `undefined` is returned.
+    pub fn detectUnsupportedAlphabetOf(&self, text: &str) -> Option<String> {
+        self.detector
+            .detect_unsupported_alphabet_of(text)
+            .map(|alphabet| alphabet.to_string())
+    }
+
+    /// Detects the language of given input text and tells apart why no language has been
+    /// detected, if so. The returned object holds either the detected language in its field
+    /// `language`, the alphabet of text which none of the configured languages uses in its
+    /// field `unsupportedAlphabet`, or neither if the language cannot be reliably detected.
+    pub fn detectLanguageOutcomeOf(&self, text: &str) -> JsValue {
+        let outcome = self.detector.detect_language_outcome_of(text);
+        let outcome = DetectionOutcome {
+            language: outcome.language().map(|language| language.to_string()),
+            unsupportedAlphabet: outcome
+                .unsupported_alphabet()
+                .map(|alphabet| alphabet.to_string()),
+        };
+
+        serde_wasm_bindgen::to_value(&outcome).unwrap()
+    }
+
+    /// Detects whether the given input text is written in Simplified or Traditional Chinese
+    /// and returns the corresponding BCP 47 tag `zh-Hans` or `zh-Hant`. Text containing a
+    /// substantial share of characters of both scripts is tagged as `zh`. If the language of
+    /// the text is not Chinese or if it consists of characters shared by both scripts only,
+    /// `undefined` is returned.
+    pub fn detectChineseScriptOf(&self, text: &str) -> Option<String> {
+        self.detector
+            .detect_chinese_script_of(text)
+            .map(|script| script.bcp47_tag().to_string())
+    }
+
+    /// Detects the language of the given input text, collapsing the languages of the
+    /// macrolanguages configured with `withMacroLanguages` into their macrolanguage.
+    /// If it cannot be reliably detected, `undefined` is returned.
+    pub fn detectCollapsedLanguageOf(&self, text: &str) -> Option<String> {
+        self.detector
+            .detect_collapsed_language_of(text)
+            .map(|language| language.to_string())
+    }
+
     /// Attempts to detect multiple languages in mixed-language text.
     ///
     /// This feature is experimental and under continuous development.
@@ -320,7 +572,25 @@ impl LanguageDetector {
     pub fn computeLanguageConfidenceValues(&self, text: &str) -> JsValue {
         let confidence_values = self
             .detector
-            .compute_language_confidence_values(text)
+            .compute_language_confidence_values_of_str(text)
+            .iter()
+            .map(|(language, confidence)| ConfidenceValue {
+                language: language.to_string(),
+                value: *confidence,
+            })
+            .collect_vec();
+
+        serde_wasm_bindgen::to_value(&confidence_values).unwrap()
+    }
+
+    /// Computes confidence values for each language supported by this detector for the given
+    /// input text, collapsing the languages of the macrolanguages configured with
+    /// `withMacroLanguages` into their macrolanguage. The confidence value of a macrolanguage
+    /// is the sum of the values of its individual languages.
+    pub fn computeCollapsedLanguageConfidenceValues(&self, text: &str) -> JsValue {
+        let confidence_values = self
+            .detector
+            .compute_collapsed_language_confidence_values(text)
             .iter()
             .map(|(language, confidence)| ConfidenceValue {
                 language: language.to_string(),
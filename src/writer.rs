@@ -15,30 +15,149 @@
  */
 
 use std::collections::HashMap;
-use std::fs::{remove_file, File};
+use std::fs::{create_dir_all, remove_file, File};
 use std::io;
 use std::io::{BufRead, BufReader, LineWriter, Write};
 use std::path::Path;
+use std::sync::Arc;
 
 use brotli::CompressorWriter;
 use itertools::Itertools;
 use regex::Regex;
 
-use crate::constant::{MULTIPLE_WHITESPACE, NUMBERS, PUNCTUATION};
-use crate::model::TrainingDataLanguageModel;
-use crate::ngram::Ngram;
+use crate::constant::{
+    MULTIPLE_WHITESPACE, NUMBERS, PUNCTUATION, WORD_END_MARKER, WORD_START_MARKER,
+};
+use crate::discriminator::PairDiscriminator;
+use crate::folding::{fold_case, fold_case_for};
+use crate::json::{counts_file_name, WORD_BOUNDARY_MODEL_PREFIX, WORD_MODEL_FILE_NAME};
+use crate::model::{mark_word_boundaries, TrainingDataLanguageModel, TrainingDataWordModel};
+use crate::ngram::{Ngram, MAXIMUM_NGRAM_LENGTH};
+use crate::normalizer::{normalize, Normalizer};
+use crate::rule::CharacterRules;
+use crate::tokenizer::Tokenizer;
+use crate::variant::LanguageVariant;
 use crate::Language;
 
+pub(crate) const MAXIMUM_NGRAM_LENGTH_MESSAGE: &str =
+    "Maximum ngram length must lie in between 1 and 8";
+
 /// This struct creates language model files and writes them to a directory.
+#[derive(Clone)]
 #[cfg_attr(feature = "python", pyo3::prelude::pyclass)]
-pub struct LanguageModelFilesWriter;
+pub struct LanguageModelFilesWriter {
+    normalizers: Vec<Normalizer>,
+    tokenizer: Option<Arc<dyn Tokenizer>>,
+    is_word_boundary_mode_enabled: bool,
+    is_ngram_count_output_enabled: bool,
+    is_word_model_output_enabled: bool,
+    maximum_ngram_length: usize,
+}
 
 /// This struct creates test data files for accuracy report generation
 /// and writes them to a directory.
 #[cfg_attr(feature = "python", pyo3::prelude::pyclass)]
 pub struct TestDataFilesWriter;
 
+/// This struct derives the character tables of the rule-based engine from the
+/// unigram models of all built-in languages and writes them as Rust source code.
+pub struct CharacterRulesWriter;
+
 impl LanguageModelFilesWriter {
+    /// Creates a new writer which builds the language models from the unmodified input text.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Configures the writer to apply the given normalizers to each line of the input text
+    /// before the language models are built from it.
+    ///
+    /// The normalizers should be the same as the ones passed to
+    /// [LanguageDetectorBuilder::with_normalizers](crate::LanguageDetectorBuilder::with_normalizers)
+    /// so that the models match the text they are compared with.
+    pub fn with_normalizers(&mut self, normalizers: &[Normalizer]) -> &mut Self {
+        self.normalizers = normalizers.to_vec();
+        self
+    }
+
+    /// Configures the writer to split each line of the input text into words with the given
+    /// tokenizer before the language models are built from it.
+    ///
+    /// The words are joined by single spaces, so no ngram spans more than one word as long as
+    /// the character class passed to [LanguageModelFilesWriter::write_language_model_files]
+    /// does not match whitespace. By default, the lines are used as they are.
+    pub fn with_tokenizer(&mut self, tokenizer: Arc<dyn Tokenizer>) -> &mut Self {
+        self.tokenizer = Some(tokenizer);
+        self
+    }
+
+    /// Configures the writer to surround each word with boundary markers, so that ngrams at
+    /// the start or the end of a word are distinguished from the same ngrams inside of it.
+    ///
+    /// The language model files are named `word-boundary-unigrams.json.br` and so on.
+    /// They are used by detectors built with
+    /// [LanguageDetectorBuilder::with_word_boundary_ngrams](crate::LanguageDetectorBuilder::with_word_boundary_ngrams).
+    pub fn with_word_boundary_ngrams(&mut self) -> &mut Self {
+        self.is_word_boundary_mode_enabled = true;
+        self
+    }
+
+    /// Configures the writer to additionally write the absolute frequencies of all ngrams
+    /// to files named `unigram-counts.json.br` and so on.
+    ///
+    /// The relative frequencies stored in the language models do not preserve the number
+    /// of times each ngram occurs in the training data. The smoothing schemes selected by
+    /// [LanguageDetectorBuilder::with_smoothing](crate::LanguageDetectorBuilder::with_smoothing)
+    /// are computed from these counts.
+    pub fn with_ngram_counts(&mut self) -> &mut Self {
+        self.is_ngram_count_output_enabled = true;
+        self
+    }
+
+    /// Configures the writer to additionally create a model of the relative frequencies of
+    /// whole words, which is written to a file named `words.json.br`.
+    ///
+    /// The words are extracted in the same way as for the ngram models. The word model is
+    /// used by detectors built with
+    /// [LanguageDetectorBuilder::with_word_model](crate::LanguageDetectorBuilder::with_word_model)
+    /// to classify text consisting of one or two words.
+    pub fn with_word_model(&mut self) -> &mut Self {
+        self.is_word_model_output_enabled = true;
+        self
+    }
+
+    /// Configures the writer to create language models for all ngram lengths from 1 up to
+    /// and including the given maximum ngram length.
+    ///
+    /// By default, unigram up to fivegram models are created. Higher-order models, such as
+    /// `sixgrams.json.br` and `sevengrams.json.br`, can be used by detectors built with
+    /// [LanguageDetectorBuilder::with_ngram_lengths](crate::LanguageDetectorBuilder::with_ngram_lengths).
+    ///
+    /// ⚠ Panics if `maximum_ngram_length` is smaller than 1 or greater than 8.
+    pub fn with_maximum_ngram_length(&mut self, maximum_ngram_length: usize) -> &mut Self {
+        if !(1..=MAXIMUM_NGRAM_LENGTH).contains(&maximum_ngram_length) {
+            panic!("{}", MAXIMUM_NGRAM_LENGTH_MESSAGE);
+        }
+        self.maximum_ngram_length = maximum_ngram_length;
+        self
+    }
+
+    /// Creates language model files with the default configuration and writes them to
+    /// a directory. See [LanguageModelFilesWriter::write_language_model_files] for details.
+    pub fn create_and_write_language_model_files(
+        input_file_path: &Path,
+        output_directory_path: &Path,
+        language: &Language,
+        char_class: &str,
+    ) -> io::Result<()> {
+        Self::new().write_language_model_files(
+            input_file_path,
+            output_directory_path,
+            language,
+            char_class,
+        )
+    }
+
     /// Creates language model files and writes them to a directory.
     ///
     /// `input_file_path`: The path to a txt file used for language model creation.
@@ -57,7 +176,8 @@ impl LanguageModelFilesWriter {
     /// - the input file's encoding is not UTF-8
     /// - the output directory path is not absolute or does not point to an existing directory
     /// - the character class cannot be compiled to a valid regular expression
-    pub fn create_and_write_language_model_files(
+    pub fn write_language_model_files(
+        &self,
         input_file_path: &Path,
         output_directory_path: &Path,
         language: &Language,
@@ -66,71 +186,117 @@ impl LanguageModelFilesWriter {
         check_input_file_path(input_file_path);
         check_output_directory_path(output_directory_path);
 
-        let unigram_model =
-            Self::create_language_model(input_file_path, language, 1, char_class, &hashmap!())?;
+        let mut lower_ngram_absolute_frequencies = hashmap!();
+
+        for ngram_length in 1..=self.maximum_ngram_length {
+            let mut model = self.create_language_model(
+                input_file_path,
+                language,
+                ngram_length,
+                char_class,
+                &lower_ngram_absolute_frequencies,
+            )?;
+            let ngram_name = Ngram::find_ngram_name_by_length(ngram_length);
+
+            self.write_compressed_language_model(
+                &model,
+                output_directory_path,
+                &format!("{ngram_name}s.json"),
+            )?;
+
+            if self.is_ngram_count_output_enabled {
+                let file_path = output_directory_path.join(counts_file_name(
+                    ngram_length,
+                    self.is_word_boundary_mode_enabled,
+                ));
+                write_compressed_file(&file_path, &model.to_counts_json())?;
+            }
 
-        let bigram_model = Self::create_language_model(
-            input_file_path,
-            language,
-            2,
-            char_class,
-            unigram_model.absolute_frequencies.as_ref().unwrap(),
-        )?;
+            lower_ngram_absolute_frequencies = model.absolute_frequencies.take().unwrap();
+        }
 
-        let trigram_model = Self::create_language_model(
-            input_file_path,
-            language,
-            3,
-            char_class,
-            bigram_model.absolute_frequencies.as_ref().unwrap(),
-        )?;
+        if self.is_word_model_output_enabled {
+            let word_model = self.create_word_model(input_file_path, language, char_class)?;
+            write_compressed_file(
+                &output_directory_path.join(WORD_MODEL_FILE_NAME),
+                &word_model.to_json(),
+            )?;
+        }
 
-        let quadrigram_model = Self::create_language_model(
-            input_file_path,
-            language,
-            4,
-            char_class,
-            trigram_model.absolute_frequencies.as_ref().unwrap(),
-        )?;
+        Ok(())
+    }
 
-        let fivegram_model = Self::create_language_model(
+    /// Creates the language model files of a regional language variant and writes them to
+    /// a subdirectory of the given output directory which is named after the variant's
+    /// BCP 47 tag, such as `pt-BR`. A word model is always written in addition to the
+    /// ngram models.
+    ///
+    /// The output directory can be passed to
+    /// [LanguageDetectorBuilder::with_language_variants](crate::LanguageDetectorBuilder::with_language_variants)
+    /// once the models of all desired variants have been written to it.
+    ///
+    /// ⚠ Panics under the same conditions as
+    /// [LanguageModelFilesWriter::write_language_model_files].
+    pub fn write_language_variant_model_files(
+        &self,
+        input_file_path: &Path,
+        output_directory_path: &Path,
+        variant: &LanguageVariant,
+        char_class: &str,
+    ) -> io::Result<()> {
+        check_output_directory_path(output_directory_path);
+
+        let variant_directory_path = output_directory_path.join(variant.to_string());
+        create_dir_all(&variant_directory_path)?;
+
+        self.clone().with_word_model().write_language_model_files(
             input_file_path,
-            language,
-            5,
+            &variant_directory_path,
+            &variant.language(),
             char_class,
-            quadrigram_model.absolute_frequencies.as_ref().unwrap(),
-        )?;
+        )
+    }
 
-        Self::write_compressed_language_model(
-            &unigram_model,
-            output_directory_path,
-            "unigrams.json",
-        )?;
-        Self::write_compressed_language_model(
-            &bigram_model,
-            output_directory_path,
-            "bigrams.json",
-        )?;
-        Self::write_compressed_language_model(
-            &trigram_model,
-            output_directory_path,
-            "trigrams.json",
-        )?;
-        Self::write_compressed_language_model(
-            &quadrigram_model,
-            output_directory_path,
-            "quadrigrams.json",
-        )?;
-        Self::write_compressed_language_model(
-            &fivegram_model,
-            output_directory_path,
-            "fivegrams.json",
-        )?;
+    /// Trains a discriminator for a pair of closely related languages and writes it to a file
+    /// named after the ISO 639-1 codes of both languages, such as `nb-nn.json.br`.
+    ///
+    /// The discriminator is a logistic regression model of the words and character trigrams
+    /// of each line of both input files. Detectors built with
+    /// [LanguageDetectorBuilder::with_pair_discriminators](crate::LanguageDetectorBuilder::with_pair_discriminators)
+    /// use it to decide between both languages if they are the two most likely ones for a text.
+    ///
+    /// ⚠ Panics if both languages are the same, or if any of the input files or the
+    /// output directory is invalid as described in
+    /// [LanguageModelFilesWriter::write_language_model_files].
+    pub fn write_pair_discriminator_file(
+        &self,
+        first_input_file_path: &Path,
+        second_input_file_path: &Path,
+        output_directory_path: &Path,
+        first_language: &Language,
+        second_language: &Language,
+        char_class: &str,
+    ) -> io::Result<()> {
+        check_input_file_path(first_input_file_path);
+        check_input_file_path(second_input_file_path);
+        check_output_directory_path(output_directory_path);
 
-        Ok(())
+        let discriminator = PairDiscriminator::train(
+            *first_language,
+            &self.split_file_into_lines_of_words(first_input_file_path, char_class)?,
+            *second_language,
+            &self.split_file_into_lines_of_words(second_input_file_path, char_class)?,
+        );
+        let file_name = PairDiscriminator::file_name(*first_language, *second_language);
+
+        write_compressed_file(
+            &output_directory_path.join(file_name),
+            &discriminator.to_json(),
+        )
     }
 
     fn create_language_model(
+        &self,
         input_file_path: &Path,
         language: &Language,
         ngram_length: usize,
@@ -139,33 +305,124 @@ impl LanguageModelFilesWriter {
     ) -> io::Result<TrainingDataLanguageModel> {
         let file = File::open(input_file_path)?;
         let reader = BufReader::new(file);
+        let word_regex = compile_word_regex(char_class);
         let lines = reader
             .lines()
             .map(|line| line.unwrap())
+            .map(|line| normalize(&line, &self.normalizers).into_owned())
+            .map(|line| {
+                if self.is_word_boundary_mode_enabled {
+                    let line = fold_case_for(&line, language);
+                    self.split_line_into_words(&line, &word_regex)
+                        .into_iter()
+                        .map(mark_word_boundaries)
+                        .join(" ")
+                } else {
+                    match &self.tokenizer {
+                        Some(tokenizer) => tokenizer.words(&line).join(" "),
+                        None => line,
+                    }
+                }
+            })
             .filter(|line| !line.trim().is_empty())
             .collect_vec();
         let lines_as_str = lines.iter().map(|line| line.as_str()).collect_vec();
 
+        let char_class = if self.is_word_boundary_mode_enabled {
+            format!("{char_class}{WORD_START_MARKER}{WORD_END_MARKER}")
+        } else {
+            char_class.to_string()
+        };
+
         Ok(TrainingDataLanguageModel::from_text(
             &lines_as_str,
             language,
             ngram_length,
-            char_class,
+            &char_class,
             lower_ngram_absolute_frequencies,
         ))
     }
 
+    fn create_word_model(
+        &self,
+        input_file_path: &Path,
+        language: &Language,
+        char_class: &str,
+    ) -> io::Result<TrainingDataWordModel> {
+        let file = File::open(input_file_path)?;
+        let reader = BufReader::new(file);
+        let word_regex = compile_word_regex(char_class);
+        let lines = reader
+            .lines()
+            .map(|line| line.unwrap())
+            .map(|line| fold_case_for(&normalize(&line, &self.normalizers), language))
+            .collect_vec();
+
+        Ok(TrainingDataWordModel::from_words(
+            lines
+                .iter()
+                .flat_map(|line| self.split_line_into_words(line, &word_regex)),
+            language,
+        ))
+    }
+
+    fn split_file_into_lines_of_words(
+        &self,
+        input_file_path: &Path,
+        char_class: &str,
+    ) -> io::Result<Vec<Vec<String>>> {
+        let file = File::open(input_file_path)?;
+        let reader = BufReader::new(file);
+        let word_regex = compile_word_regex(char_class);
+        let lines = reader
+            .lines()
+            .map(|line| line.unwrap())
+            .map(|line| fold_case(&normalize(&line, &self.normalizers)))
+            .map(|line| {
+                self.split_line_into_words(&line, &word_regex)
+                    .into_iter()
+                    .map(|word| word.to_string())
+                    .collect_vec()
+            })
+            .filter(|words| !words.is_empty())
+            .collect_vec();
+
+        Ok(lines)
+    }
+
+    fn split_line_into_words<'a>(&self, line: &'a str, word_regex: &Regex) -> Vec<&'a str> {
+        match &self.tokenizer {
+            Some(tokenizer) => tokenizer.words(line),
+            None => word_regex.find_iter(line).map(|mat| mat.as_str()).collect(),
+        }
+    }
+
     fn write_compressed_language_model(
+        &self,
         model: &TrainingDataLanguageModel,
         output_directory_path: &Path,
         file_name: &str,
     ) -> io::Result<()> {
-        let file_name = format!("{file_name}.br");
+        let file_name = if self.is_word_boundary_mode_enabled {
+            format!("{WORD_BOUNDARY_MODEL_PREFIX}{file_name}.br")
+        } else {
+            format!("{file_name}.br")
+        };
         let file_path = output_directory_path.join(file_name);
-        let file = File::create(file_path)?;
-        let mut compressed_file = CompressorWriter::new(file, 4096, 11, 22);
-        compressed_file.write_all(model.to_json().as_bytes())?;
-        Ok(())
+        write_compressed_file(&file_path, &model.to_json())
+    }
+}
+
+impl Default for LanguageModelFilesWriter {
+    fn default() -> Self {
+        Self {
+            normalizers: vec![],
+            tokenizer: None,
+            is_word_boundary_mode_enabled: false,
+            is_ngram_count_output_enabled: false,
+            is_word_model_output_enabled: false,
+            maximum_ngram_length: 5,
+        }
     }
 }
 
@@ -279,7 +536,7 @@ impl TestDataFilesWriter {
             let removed_quotes = normalized_whitespace.replace('\"', "");
             let mut single_words = removed_quotes
                 .split(' ')
-                .map(|word| word.trim().to_lowercase())
+                .map(|word| fold_case(word.trim()))
                 .filter(|word| word_regex.is_match(word))
                 .collect_vec();
 
@@ -334,6 +591,100 @@ impl TestDataFilesWriter {
     }
 }
 
+impl CharacterRulesWriter {
+    /// Derives the unique characters of each language and the mapping of characters
+    /// to the few languages sharing them from the unigram models of all built-in languages
+    /// and writes them to a file named `character-rules.rs` in the given directory.
+    ///
+    /// The file contains the match arms of `Language::unique_characters` and the entries
+    /// of `CHARS_TO_LANGUAGES_MAPPING` in the format of the hand-maintained tables, so that
+    /// these can be kept consistent with the language models. The same rules are derived
+    /// at runtime by detectors built with
+    /// [LanguageDetectorBuilder::with_derived_character_rules](crate::LanguageDetectorBuilder::with_derived_character_rules).
+    ///
+    /// ⚠ Panics if the output directory path is not absolute or does not point to an
+    /// existing directory.
+    pub fn create_and_write_character_rules_file(output_directory_path: &Path) -> io::Result<()> {
+        check_output_directory_path(output_directory_path);
+
+        let rules = CharacterRules::derived_from_unigram_models(&Language::all());
+        let mut source = String::from("// Match arms of Language::unique_characters()\n\n");
+
+        for (language, characters) in rules.unique_characters.iter().sorted() {
+            source.push_str(&format!(
+                "#[cfg(feature = \"{}\")]\nLanguage::{language} => Some(\"{}\"),\n\n",
+                language_feature(language),
+                with_uppercase_characters(characters)
+            ));
+        }
+
+        source.push_str("// Entries of CHARS_TO_LANGUAGES_MAPPING\n\n");
+
+        let mapping = rules
+            .chars_to_languages_mapping
+            .iter()
+            .map(|(characters, languages)| (languages.iter().sorted().collect_vec(), characters))
+            .sorted();
+
+        for (languages, characters) in mapping {
+            let condition = languages
+                .iter()
+                .map(|language| format!("cfg!(feature = \"{}\")", language_feature(language)))
+                .join(" || ");
+            source.push_str(&format!(
+                "if {condition} {{\n    mapping.insert(\"{}\", {{\n        let mut languages = hashset!();\n",
+                with_uppercase_characters(characters)
+            ));
+            for language in languages {
+                source.push_str(&format!(
+                    "        if cfg!(feature = \"{}\") {{\n            languages.insert(Language::from_str(\"{language}\").unwrap());\n        }}\n",
+                    language_feature(language)
+                ));
+            }
+            source.push_str("        languages\n    });\n}\n\n");
+        }
+
+        let mut file = File::create(output_directory_path.join("character-rules.rs"))?;
+        file.write_all(source.as_bytes())
+    }
+}
+
+/// Returns the name of the crate feature which enables the given built-in language.
+fn language_feature(language: &Language) -> String {
+    language.to_string().to_lowercase()
+}
+
+/// Precedes each character which has a single uppercase form with this form,
+/// as the hand-maintained tables contain both cases.
+fn with_uppercase_characters(characters: &str) -> String {
+    let mut result = String::new();
+    for character in characters.chars() {
+        let mut uppercase = character.to_uppercase();
+        if let (Some(uppercase_character), None) = (uppercase.next(), uppercase.next()) {
+            if uppercase_character != character {
+                result.push(uppercase_character);
+            }
+        }
+        result.push(character);
+    }
+    result
+}
+
+fn compile_word_regex(char_class: &str) -> Regex {
+    Regex::new(&format!("[{char_class}]+")).unwrap_or_else(|_| {
+        panic!(
+            "The character class '{char_class}' cannot be compiled to a valid regular expression"
+        )
+    })
+}
+
+fn write_compressed_file(file_path: &Path, content: &str) -> io::Result<()> {
+    let file = File::create(file_path)?;
+    let mut compressed_file = CompressorWriter::new(file, 4096, 11, 22);
+    compressed_file.write_all(content.as_bytes())?;
+    Ok(())
+}
+
 fn check_input_file_path(input_file_path: &Path) {
     if !input_file_path.is_absolute() {
         panic!(
@@ -417,6 +768,8 @@ mod tests {
         const EXPECTED_UNIGRAM_MODEL: &str = r#"
         {
             "language":"ENGLISH",
+            "version":1,
+            "checksum":"71301398d7451d89",
             "ngrams":{
                 "1/10":"n o s",
                 "1/20":"d r",
@@ -434,6 +787,8 @@ mod tests {
         const EXPECTED_BIGRAM_MODEL: &str = r#"
         {
             "language":"ENGLISH",
+            "version":1,
+            "checksum":"9c4554ad11195047",
             "ngrams":{
                 "1/1":"by he",
                 "1/2":"fo wa wo",
@@ -457,6 +812,8 @@ mod tests {
         const EXPECTED_TRIGRAM_MODEL: &str = r#"
         {
             "language":"ENGLISH",
+            "version":1,
+            "checksum":"f955fce341adb75e",
             "ngrams":{
                 "1/1":"are ces con cti ded duc for ion ist nce nde not nsi nte odu ose pos pro pur rds rod rpo sis tal the tot uct urp use way wor",
                 "1/2":"ons ord ota sti tin tio",
@@ -470,6 +827,8 @@ mod tests {
         const EXPECTED_QUADRIGRAM_MODEL: &str = r#"
         {
             "language":"ENGLISH",
+            "version":1,
+            "checksum":"a45a7f133922aafd",
             "ngrams":{
                 "1/1":"cons ctio duct ence ende ente esti hese inte nces nded nsis nten oduc onsi ords oses otal pose prod purp rodu rpos sent sist stin test ting tion tota ucti urpo word",
                 "1/2":"tenc tend",
@@ -481,6 +840,8 @@ mod tests {
         const EXPECTED_FIVEGRAM_MODEL: &str = r#"
         {
             "language":"ENGLISH",
+            "version":1,
+            "checksum":"0683b68d12088975",
             "ngrams":{
                 "1/1":"consi ction ducti ences ended enten estin inten nsist oduct onsis poses produ purpo roduc rpose sente sting tence tende testi these total uctio urpos words",
                 "1/2":"ntenc ntend"
@@ -506,7 +867,7 @@ mod tests {
             assert_eq!(files.len(), 5);
 
             let unigrams_file_path = files.get(4).unwrap();
-            let bigrams_file_path = files.get(0).unwrap();
+            let bigrams_file_path = files.first().unwrap();
             let trigrams_file_path = files.get(3).unwrap();
             let quadrigrams_file_path = files.get(2).unwrap();
             let fivegrams_file_path = files.get(1).unwrap();
@@ -524,6 +885,267 @@ mod tests {
             assert_file_content(fivegrams_file_path, EXPECTED_FIVEGRAM_MODEL);
         }
 
+        #[test]
+        fn test_language_model_files_writer_with_normalizers() {
+            let input_file = create_temp_input_file("Ｔｈｉｓ ｉｓ ｓｏｏｏｏ ｇｏｏｄ\u{200B}.");
+            let expected_input_file = create_temp_input_file("This is soo good.");
+            let output_directory = tempdir().expect("Temporary directory could not be created");
+            let expected_output_directory =
+                tempdir().expect("Temporary directory could not be created");
+
+            let result = LanguageModelFilesWriter::new()
+                .with_normalizers(&[
+                    Normalizer::RemoveInvisibleCharacters,
+                    Normalizer::FoldFullwidthCharacters,
+                    Normalizer::SquashElongation,
+                ])
+                .write_language_model_files(
+                    input_file.path(),
+                    output_directory.path(),
+                    &Language::English,
+                    "\\p{L}",
+                );
+            assert!(result.is_ok());
+
+            let expected_result = LanguageModelFilesWriter::create_and_write_language_model_files(
+                expected_input_file.path(),
+                expected_output_directory.path(),
+                &Language::English,
+                "\\p{L}",
+            );
+            assert!(expected_result.is_ok());
+
+            let files = read_directory_content(output_directory.path());
+            let expected_files = read_directory_content(expected_output_directory.path());
+
+            assert_eq!(files.len(), 5);
+
+            for (file_path, expected_file_path) in files.iter().zip(expected_files.iter()) {
+                assert_eq!(file_path.file_name(), expected_file_path.file_name());
+                assert_eq!(
+                    std::fs::read(file_path).unwrap(),
+                    std::fs::read(expected_file_path).unwrap()
+                );
+            }
+        }
+
+        #[test]
+        fn test_language_model_files_writer_with_tokenizer() {
+            struct SyllableTokenizer;
+
+            impl Tokenizer for SyllableTokenizer {
+                fn words<'a>(&self, text: &'a str) -> Vec<&'a str> {
+                    text.split(|ch: char| ch == '-' || ch.is_whitespace())
+                        .filter(|word| !word.is_empty())
+                        .collect()
+                }
+
+                fn token_ranges(&self, _text: &str) -> Vec<std::ops::Range<usize>> {
+                    vec![]
+                }
+            }
+
+            let input_file = create_temp_input_file("ab-cd");
+            let output_directory = tempdir().expect("Temporary directory could not be created");
+
+            let result = LanguageModelFilesWriter::new()
+                .with_tokenizer(Arc::new(SyllableTokenizer))
+                .write_language_model_files(
+                    input_file.path(),
+                    output_directory.path(),
+                    &Language::English,
+                    "\\p{L}-",
+                );
+            assert!(result.is_ok());
+
+            let files = read_directory_content(output_directory.path());
+            let bigrams_file_path = files.first().unwrap();
+
+            assert_file_names(bigrams_file_path, "bigrams.json.br");
+            assert_file_content(
+                bigrams_file_path,
+                r#"{"language":"ENGLISH","version":1,"checksum":"f9ad9c547d4dd99e","ngrams":{"1/1":"ab cd"}}"#,
+            );
+        }
+
+        #[test]
+        fn test_language_model_files_writer_with_word_boundary_ngrams() {
+            let input_file = create_temp_input_file("Sing, song!");
+            let output_directory = tempdir().expect("Temporary directory could not be created");
+
+            let result = LanguageModelFilesWriter::new()
+                .with_word_boundary_ngrams()
+                .write_language_model_files(
+                    input_file.path(),
+                    output_directory.path(),
+                    &Language::English,
+                    "\\p{L}",
+                );
+            assert!(result.is_ok());
+
+            let files = read_directory_content(output_directory.path());
+
+            assert_eq!(files.len(), 5);
+
+            let bigrams_file_path = files.first().unwrap();
+
+            assert_file_names(bigrams_file_path, "word-boundary-bigrams.json.br");
+            assert_file_content(
+                bigrams_file_path,
+                r#"{"language":"ENGLISH","version":1,"checksum":"93ff441d0883b04f","ngrams":{"1/1":"<s g> in ng on","1/2":"si so"}}"#,
+            );
+        }
+
+        #[test]
+        fn test_language_model_files_writer_with_maximum_ngram_length() {
+            let input_file = create_temp_input_file("Languages");
+            let output_directory = tempdir().expect("Temporary directory could not be created");
+
+            let result = LanguageModelFilesWriter::new()
+                .with_maximum_ngram_length(7)
+                .write_language_model_files(
+                    input_file.path(),
+                    output_directory.path(),
+                    &Language::English,
+                    "\\p{L}",
+                );
+            assert!(result.is_ok());
+
+            let files = read_directory_content(output_directory.path());
+
+            assert_eq!(files.len(), 7);
+
+            let sevengrams_file_path = files.get(3).unwrap();
+            let sixgrams_file_path = files.get(4).unwrap();
+
+            assert_file_names(sevengrams_file_path, "sevengrams.json.br");
+            assert_file_names(sixgrams_file_path, "sixgrams.json.br");
+            assert_file_content(
+                sevengrams_file_path,
+                r#"{"language":"ENGLISH","version":1,"checksum":"7ea6e56a3372711d","ngrams":{"1/1":"anguage languag nguages"}}"#,
+            );
+        }
+
+        #[test]
+        fn test_language_model_files_writer_with_ngram_counts() {
+            let input_file = create_temp_input_file("abracadabra");
+            let output_directory = tempdir().expect("Temporary directory could not be created");
+
+            let result = LanguageModelFilesWriter::new()
+                .with_ngram_counts()
+                .with_maximum_ngram_length(2)
+                .write_language_model_files(
+                    input_file.path(),
+                    output_directory.path(),
+                    &Language::English,
+                    "\\p{L}",
+                );
+            assert!(result.is_ok());
+
+            let files = read_directory_content(output_directory.path());
+
+            assert_eq!(files.len(), 4);
+
+            let bigram_counts_file_path = files.first().unwrap();
+            let unigram_counts_file_path = files.get(2).unwrap();
+
+            assert_file_names(bigram_counts_file_path, "bigram-counts.json.br");
+            assert_file_names(unigram_counts_file_path, "unigram-counts.json.br");
+            assert_file_content(
+                unigram_counts_file_path,
+                r#"{"language":"ENGLISH","version":1,"checksum":"5d4827d1e25f6e0d","ngrams":{"1":"c d","2":"b r","5":"a"}}"#,
+            );
+            assert_file_content(
+                bigram_counts_file_path,
+                r#"{"language":"ENGLISH","version":1,"checksum":"4cf717c318ace202","ngrams":{"1":"ac ad ca da","2":"ab br ra"}}"#,
+            );
+        }
+
+        #[test]
+        fn test_language_model_files_writer_with_word_model() {
+            let input_file = create_temp_input_file("The cat saw the dog.\nThe dog, 3 cats.");
+            let output_directory = tempdir().expect("Temporary directory could not be created");
+
+            let result = LanguageModelFilesWriter::new()
+                .with_word_model()
+                .with_maximum_ngram_length(1)
+                .write_language_model_files(
+                    input_file.path(),
+                    output_directory.path(),
+                    &Language::English,
+                    "\\p{L}",
+                );
+            assert!(result.is_ok());
+
+            let files = read_directory_content(output_directory.path());
+
+            assert_eq!(files.len(), 2);
+
+            let words_file_path = files.get(1).unwrap();
+
+            assert_file_names(words_file_path, "words.json.br");
+            assert_file_content(
+                words_file_path,
+                r#"{"language":"ENGLISH","version":1,"checksum":"5aa2f49ad8765d8e","ngrams":{"1/4":"dog","1/8":"cat cats saw","3/8":"the"}}"#,
+            );
+        }
+
+        #[test]
+        fn test_language_variant_model_files_writer() {
+            let input_file = create_temp_input_file("O ônibus chegou.");
+            let output_directory = tempdir().expect("Temporary directory could not be created");
+            let variant = LanguageVariant::new(Language::Portuguese, "BR");
+
+            let result = LanguageModelFilesWriter::new()
+                .with_maximum_ngram_length(1)
+                .write_language_variant_model_files(
+                    input_file.path(),
+                    output_directory.path(),
+                    &variant,
+                    "\\p{L}",
+                );
+            assert!(result.is_ok());
+
+            let directories = read_directory_content(output_directory.path());
+
+            assert_eq!(directories.len(), 1);
+            assert_file_names(directories.first().unwrap(), "pt-BR");
+
+            let files = read_directory_content(directories.first().unwrap());
+
+            assert_eq!(files.len(), 2);
+            assert_file_names(files.first().unwrap(), "unigrams.json.br");
+            assert_file_names(files.get(1).unwrap(), "words.json.br");
+        }
+
+        #[test]
+        fn test_pair_discriminator_files_writer() {
+            let bokmal_input_file = create_temp_input_file("Jeg vet ikke.\nHva gjør du nå?");
+            let nynorsk_input_file = create_temp_input_file("Eg veit ikkje.\nKva gjer du no?");
+            let output_directory = tempdir().expect("Temporary directory could not be created");
+
+            let result = LanguageModelFilesWriter::new().write_pair_discriminator_file(
+                nynorsk_input_file.path(),
+                bokmal_input_file.path(),
+                output_directory.path(),
+                &Language::Nynorsk,
+                &Language::Bokmal,
+                "\\p{L}",
+            );
+            assert!(result.is_ok());
+
+            let files = read_directory_content(output_directory.path());
+
+            assert_eq!(files.len(), 1);
+            assert_file_names(files.first().unwrap(), "nb-nn.json.br");
+        }
+
+        #[test]
+        #[should_panic(expected = "Maximum ngram length must lie in between 1 and 8")]
+        fn assert_maximum_ngram_length_above_eight_is_rejected() {
+            LanguageModelFilesWriter::new().with_maximum_ngram_length(9);
+        }
+
         fn assert_file_names(file_path: &Path, expected_file_name: &str) {
             assert_eq!(file_path.file_name().unwrap(), expected_file_name);
         }
@@ -629,4 +1251,49 @@ mod tests {
             assert_eq!(test_data_file_content, expected_file_content);
         }
     }
+
+    mod character_rules_file {
+        use indoc::indoc;
+
+        use super::*;
+
+        #[test]
+        fn test_character_rules_writer() {
+            let output_directory = tempdir().expect("Temporary directory could not be created");
+            let result = CharacterRulesWriter::create_and_write_character_rules_file(
+                output_directory.path(),
+            );
+
+            assert!(result.is_ok());
+
+            let files = read_directory_content(output_directory.path());
+            assert_eq!(files.len(), 1);
+            assert_eq!(files[0].file_name().unwrap(), "character-rules.rs");
+
+            let mut content = String::new();
+            File::open(&files[0])
+                .unwrap()
+                .read_to_string(&mut content)
+                .unwrap();
+
+            assert!(content.contains(indoc! {r#"
+                #[cfg(feature = "german")]
+                Language::German => Some("ß"),
+            "#}));
+            assert!(content.contains(indoc! {r#"
+                if cfg!(feature = "lithuanian") || cfg!(feature = "polish") {
+                    mapping.insert("ĄąĘę", {
+                        let mut languages = hashset!();
+                        if cfg!(feature = "lithuanian") {
+                            languages.insert(Language::from_str("Lithuanian").unwrap());
+                        }
+                        if cfg!(feature = "polish") {
+                            languages.insert(Language::from_str("Polish").unwrap());
+                        }
+                        languages
+                    });
+                }
+            "#}));
+        }
+    }
 }
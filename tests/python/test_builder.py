@@ -15,7 +15,14 @@
 
 import pytest
 
-from lingua import IsoCode639_1, IsoCode639_3, Language, LanguageDetectorBuilder
+from lingua import (
+    IsoCode639_1,
+    IsoCode639_3,
+    Language,
+    LanguageDetectorBuilder,
+    MacroLanguage,
+    Normalizer
+)
 
 
 def test_build_from_blacklist_does_not_panic():
@@ -102,3 +109,217 @@ def test_build_with_low_accuracy_mode_does_not_panic():
         .from_all_languages()
         .with_low_accuracy_mode()
     )
+
+
+def test_build_with_accent_insensitive_mode_does_not_panic():
+    (
+        LanguageDetectorBuilder
+        .from_all_languages()
+        .with_accent_insensitive_mode()
+    )
+
+
+def test_build_with_derived_character_rules_does_not_panic():
+    (
+        LanguageDetectorBuilder
+        .from_all_languages()
+        .with_derived_character_rules()
+    )
+
+
+def test_build_with_word_boundary_ngrams_does_not_panic():
+    (
+        LanguageDetectorBuilder
+        .from_all_languages()
+        .with_word_boundary_ngrams()
+    )
+
+
+def test_cannot_build_without_word_boundary_models():
+    builder = LanguageDetectorBuilder.from_languages(
+        Language.GERMAN, Language.ENGLISH
+    ).with_word_boundary_ngrams()
+    with pytest.raises(ValueError) as exception_info:
+        builder.build()
+    assert (
+        exception_info.value.args[0]
+        == "Word boundary ngrams cannot be used as the word boundary models are missing for English, German. "
+        "These models are not bundled with the built-in languages, so they can only be used for custom "
+        "languages and with the models directory of the crate feature `external-models`."
+    )
+
+
+def test_build_with_ngram_lengths_does_not_panic():
+    (
+        LanguageDetectorBuilder
+        .from_all_languages()
+        .with_ngram_lengths((1, 6), (3, 7))
+    )
+
+
+def test_cannot_build_with_invalid_ngram_lengths():
+    builder = LanguageDetectorBuilder.from_all_languages()
+    for short_text_ngram_lengths, long_text_ngram_lengths in (
+        ((0, 5), (3, 3)),
+        ((1, 9), (3, 3)),
+        ((1, 5), (4, 3)),
+    ):
+        with pytest.raises(ValueError) as exception_info:
+            builder.with_ngram_lengths(short_text_ngram_lengths, long_text_ngram_lengths)
+        assert (
+            exception_info.value.args[0]
+            == "Ngram lengths must be non-empty ranges lying in between 1 and 8"
+        )
+
+
+def test_build_with_smoothing_does_not_panic():
+    (
+        LanguageDetectorBuilder
+        .from_all_languages()
+        .with_add_k_smoothing(0.5)
+        .with_witten_bell_smoothing()
+        .with_kneser_ney_smoothing()
+    )
+
+
+def test_cannot_build_without_ngram_counts():
+    builder = LanguageDetectorBuilder.from_languages(
+        Language.GERMAN, Language.ENGLISH
+    ).with_witten_bell_smoothing()
+    with pytest.raises(ValueError) as exception_info:
+        builder.build()
+    assert (
+        exception_info.value.args[0]
+        == "Smoothing cannot be applied as the ngram counts are missing for English, German. "
+        "These models are not bundled with the built-in languages, so they can only be used for custom "
+        "languages and with the models directory of the crate feature `external-models`."
+    )
+
+
+def test_cannot_build_with_invalid_smoothing_parameters():
+    builder = LanguageDetectorBuilder.from_all_languages()
+    with pytest.raises(ValueError) as exception_info:
+        builder.with_add_k_smoothing(0.0)
+    assert (
+        exception_info.value.args[0]
+        == "The constant k of add-k smoothing must be greater than 0.0"
+    )
+    with pytest.raises(ValueError) as exception_info:
+        builder.with_kneser_ney_smoothing(1.0)
+    assert (
+        exception_info.value.args[0]
+        == "The discount of Kneser-Ney smoothing must lie in between 0.0 and 1.0"
+    )
+
+
+def test_build_with_word_model_does_not_panic():
+    (
+        LanguageDetectorBuilder
+        .from_all_languages()
+        .with_word_model(0.4)
+    )
+
+
+def test_cannot_build_without_word_models():
+    builder = LanguageDetectorBuilder.from_languages(
+        Language.GERMAN, Language.ENGLISH
+    ).with_word_model(0.4)
+    with pytest.raises(ValueError) as exception_info:
+        builder.build()
+    assert (
+        exception_info.value.args[0]
+        == "Word models cannot be used as they are missing for English, German. "
+        "These models are not bundled with the built-in languages, so they can only be used for custom "
+        "languages and with the models directory of the crate feature `external-models`."
+    )
+
+
+def test_cannot_build_with_invalid_word_model_weight():
+    builder = LanguageDetectorBuilder.from_all_languages()
+    for value in (0.0, -0.5, 1.1):
+        with pytest.raises(ValueError) as exception_info:
+            builder.with_word_model(value)
+        assert (
+            exception_info.value.args[0]
+            == "Word model weight must be greater than 0.0 and at most 1.0"
+        )
+
+
+def test_build_with_language_variants_does_not_panic(tmp_path):
+    (
+        LanguageDetectorBuilder
+        .from_all_languages()
+        .with_language_variants(tmp_path)
+    )
+
+
+def test_cannot_build_with_missing_language_variants_directory(tmp_path):
+    builder = LanguageDetectorBuilder.from_all_languages()
+    with pytest.raises(ValueError) as exception_info:
+        builder.with_language_variants(tmp_path / "missing")
+    assert (
+        exception_info.value.args[0]
+        == "Language variant models directory must be an existing directory"
+    )
+
+
+def test_build_with_pair_discriminators_does_not_panic(tmp_path):
+    (
+        LanguageDetectorBuilder
+        .from_all_languages()
+        .with_pair_discriminators(tmp_path)
+    )
+
+
+def test_cannot_build_with_missing_pair_discriminators_directory(tmp_path):
+    builder = LanguageDetectorBuilder.from_all_languages()
+    with pytest.raises(ValueError) as exception_info:
+        builder.with_pair_discriminators(tmp_path / "missing")
+    assert (
+        exception_info.value.args[0]
+        == "Pair discriminator models directory must be an existing directory"
+    )
+
+
+def test_build_with_macro_languages_does_not_panic():
+    (
+        LanguageDetectorBuilder
+        .from_all_languages()
+        .with_macro_languages(MacroLanguage.NORWEGIAN, MacroLanguage.MALAY)
+    )
+
+
+def test_cannot_build_without_macro_languages():
+    builder = LanguageDetectorBuilder.from_all_languages()
+    with pytest.raises(ValueError) as exception_info:
+        builder.with_macro_languages()
+    assert (
+        exception_info.value.args[0]
+        == "At least one macro language must be specified"
+    )
+
+
+def test_build_with_memory_budget_does_not_panic():
+    (
+        LanguageDetectorBuilder
+        .from_all_languages()
+        .with_memory_budget(50_000_000)
+    )
+
+
+def test_cannot_build_with_zero_memory_budget():
+    builder = LanguageDetectorBuilder.from_all_languages()
+    with pytest.raises(ValueError) as exception_info:
+        builder.with_memory_budget(0)
+    assert (
+        exception_info.value.args[0]
+        == "Memory budget must be greater than 0 bytes"
+    )
+
+
+def test_build_with_normalizers_does_not_panic():
+    (
+        LanguageDetectorBuilder
+        .from_all_languages()
+        .with_normalizers([Normalizer.NFKC, Normalizer.SQUASH_ELONGATION])
+    )
@@ -13,12 +13,19 @@
 # See the License for the specific language governing permissions and
 # limitations under the License.
 
+import time
+
 import pytest
 
 from lingua import (
+    Alphabet,
+    ChineseScript,
     ConfidenceValue,
     Language,
-    LanguageDetectorBuilder
+    LanguageDetector,
+    LanguageDetectorBuilder,
+    MacroLanguage,
+    ModelKind
 )
 
 detector_for_english_and_german = (
@@ -63,6 +70,245 @@ def test_no_language_is_returned():
     )
 
 
+@pytest.mark.parametrize(
+    "text,expected_alphabet",
+    [
+        pytest.param("ಕನ್ನಡ ಭಾಷೆ", Alphabet.KANNADA, id="KANNADA"),
+        pytest.param("проарплап", Alphabet.CYRILLIC, id="CYRILLIC"),
+        pytest.param("languages are awesome", None, id="LATIN"),
+    ],
+)
+def test_detect_unsupported_alphabet(text, expected_alphabet):
+    assert (
+        detector_for_english_and_german
+        .detect_unsupported_alphabet_of(text)
+        == expected_alphabet
+    )
+
+
+def test_detect_language_outcome():
+    outcome = detector_for_english_and_german.detect_language_outcome_of("Alter")
+    assert outcome.language == Language.GERMAN
+    assert outcome.unsupported_alphabet is None
+    assert not outcome.is_undecided
+
+    outcome = detector_for_english_and_german.detect_language_outcome_of("ಕನ್ನಡ ಭಾಷೆ")
+    assert outcome.language is None
+    assert outcome.unsupported_alphabet == Alphabet.KANNADA
+    assert not outcome.is_undecided
+
+    outcome = detector_for_english_and_german.detect_language_outcome_of("12345")
+    assert outcome.language is None
+    assert outcome.unsupported_alphabet is None
+    assert outcome.is_undecided
+
+
+@pytest.mark.parametrize(
+    "text,expected_script",
+    [
+        pytest.param("北京大学的图书馆很大", ChineseScript.SIMPLIFIED, id="SIMPLIFIED"),
+        pytest.param("臺灣大學的圖書館很大", ChineseScript.TRADITIONAL, id="TRADITIONAL"),
+        pytest.param("这个問題很難回答", ChineseScript.MIXED, id="MIXED"),
+        pytest.param("languages are awesome", None, id="ENGLISH"),
+    ],
+)
+def test_detect_chinese_script(text, expected_script):
+    assert (
+        detector_for_all_languages
+        .detect_chinese_script_of(text)
+        == expected_script
+    )
+
+
+def test_chinese_script_bcp47_tag():
+    assert ChineseScript.SIMPLIFIED.bcp47_tag() == "zh-Hans"
+    assert ChineseScript.TRADITIONAL.bcp47_tag() == "zh-Hant"
+    assert ChineseScript.MIXED.bcp47_tag() == "zh"
+
+
+def test_detect_collapsed_language():
+    detector = (
+        LanguageDetectorBuilder.from_languages(
+            Language.BOKMAL, Language.ENGLISH, Language.NYNORSK)
+        .with_macro_languages(MacroLanguage.NORWEGIAN)
+        .build()
+    )
+    text = "Jeg vet ikke hva du mener"
+
+    assert detector.detect_collapsed_language_of(text) == MacroLanguage.NORWEGIAN
+    assert (
+        detector.detect_collapsed_language_of("languages are awesome")
+        == Language.ENGLISH
+    )
+
+    confidence_values = detector.compute_language_confidence_values(text)
+    collapsed_confidence_values = (
+        detector.compute_collapsed_language_confidence_values(text)
+    )
+    norwegian_value = sum(
+        confidence.value
+        for confidence in confidence_values
+        if confidence.language in (Language.BOKMAL, Language.NYNORSK)
+    )
+
+    assert len(collapsed_confidence_values) == 2
+    assert collapsed_confidence_values[0][0] == MacroLanguage.NORWEGIAN
+    assert collapsed_confidence_values[0][1] == pytest.approx(norwegian_value)
+    assert collapsed_confidence_values[1][0] == Language.ENGLISH
+
+
+def test_macro_language_iso_code_and_languages():
+    assert MacroLanguage.NORWEGIAN.iso_code_639_3() == "nor"
+    assert MacroLanguage.MALAY.iso_code_639_3() == "msa"
+    assert MacroLanguage.SERBO_CROATIAN.iso_code_639_3() == "hbs"
+    assert MacroLanguage.NORWEGIAN.languages() == {Language.BOKMAL, Language.NYNORSK}
+
+
+def test_preloading_progress_of_background_preloading():
+    detector = (
+        LanguageDetectorBuilder.from_languages(Language.ENGLISH, Language.GERMAN)
+        .with_preloaded_language_models_in_background()
+        .build()
+    )
+
+    assert detector.detect_language_of("languages are awesome") == Language.ENGLISH
+
+    progress = detector.preloading_progress()
+    assert progress.total_language_count == 2
+
+    while not progress.is_complete():
+        time.sleep(0.01)
+        progress = detector.preloading_progress()
+
+    assert progress.loaded_language_count == 2
+
+
+def test_preloading_progress_without_preloading():
+    detector = LanguageDetectorBuilder.from_languages(
+        Language.ENGLISH, Language.GERMAN).build()
+    assert detector.preloading_progress() is None
+
+
+def test_model_status_of_healthy_detector():
+    status = detector_for_english_and_german.model_status()
+    assert status.is_healthy()
+    assert status.failures == []
+    assert (Language.ENGLISH, 1) in status.loaded_models
+    assert (Language.GERMAN, 3) in status.loaded_models
+
+
+def test_model_status_reports_load_failures(tmp_path):
+    (tmp_path / "unigrams.json.br").write_text("not a compressed model")
+    quenya = Language.register_custom(
+        "Quenya", "qy", "qya", [Alphabet.LATIN], tmp_path
+    )
+    detector = LanguageDetectorBuilder.from_languages(
+        Language.ENGLISH, quenya).build()
+    detector.detect_language_of("elen sila lumenn omentielvo")
+
+    status = detector.model_status()
+    assert not status.is_healthy()
+    assert all(language == Language.ENGLISH for language, _ in status.loaded_models)
+    assert all(failure.language == quenya for failure in status.failures)
+    assert status.failures[0].kind == ModelKind.NGRAMS
+    assert status.failures[0].ngram_length == 1
+    assert status.failures[0].error.startswith("model file cannot be read")
+    assert status.failures[1].error == "model file does not exist"
+
+
+def test_model_status_reports_ngram_count_load_failures(tmp_path):
+    languages = []
+    for name, iso_code_639_1, iso_code_639_3 in [
+        ("Quenya", "qy", "qya"),
+        ("Telerin", "tx", "tlx"),
+    ]:
+        models_directory = tmp_path / iso_code_639_1
+        models_directory.mkdir()
+        for ngram_name in ["unigrams", "bigrams", "trigrams", "quadrigrams", "fivegrams"]:
+            counts_file_path = models_directory / f"{ngram_name}-counts.json.br"
+            counts_file_path.write_text("not a compressed model")
+        languages.append(
+            Language.register_custom(
+                name, iso_code_639_1, iso_code_639_3, [Alphabet.LATIN], models_directory
+            )
+        )
+    detector = (
+        LanguageDetectorBuilder.from_languages(*languages)
+        .with_witten_bell_smoothing()
+        .build()
+    )
+    detector.detect_language_of("elen sila lumenn omentielvo")
+
+    status = detector.model_status()
+    assert not status.is_healthy()
+    assert {failure.language for failure in status.failures} == set(languages)
+    for failure in status.failures:
+        assert failure.kind == ModelKind.NGRAM_COUNTS
+        assert failure.ngram_length == 1
+        assert failure.error.startswith("model file cannot be read")
+
+
+def test_model_status_reports_word_model_load_failures(tmp_path):
+    languages = []
+    for name, iso_code_639_1, iso_code_639_3 in [
+        ("Vanyarin", "vy", "vya"),
+        ("Noldorin", "nq", "nql"),
+    ]:
+        models_directory = tmp_path / iso_code_639_1
+        models_directory.mkdir()
+        word_model_file_path = models_directory / "words.json.br"
+        word_model_file_path.write_text("not a compressed model")
+        languages.append(
+            Language.register_custom(
+                name, iso_code_639_1, iso_code_639_3, [Alphabet.LATIN], models_directory
+            )
+        )
+    detector = (
+        LanguageDetectorBuilder.from_languages(*languages).with_word_model().build()
+    )
+    detector.detect_language_of("elen sila")
+
+    word_model_failures = [
+        failure
+        for failure in detector.model_status().failures
+        if failure.kind == ModelKind.WORDS
+    ]
+    assert {failure.language for failure in word_model_failures} == set(languages)
+    for failure in word_model_failures:
+        assert failure.ngram_length == 0
+        assert failure.error.startswith("model file cannot be read")
+
+    detector.unload_language_models()
+    assert detector.model_status().is_healthy()
+
+
+def test_detector_is_restored_from_snapshot(tmp_path):
+    snapshot_file_path = tmp_path / "detector.snapshot"
+    detector = (
+        LanguageDetectorBuilder.from_languages(Language.ENGLISH, Language.GERMAN)
+        .with_minimum_relative_distance(0.1)
+        .build()
+    )
+    detector.write_snapshot(snapshot_file_path)
+
+    restored_detector = LanguageDetector.from_snapshot(snapshot_file_path)
+
+    for text in ["languages are awesome", "Sprachen sind großartig"]:
+        assert (
+            restored_detector.detect_language_of(text)
+            == detector.detect_language_of(text)
+        )
+
+
+def test_invalid_snapshot_is_rejected(tmp_path):
+    snapshot_file_path = tmp_path / "detector.snapshot"
+    snapshot_file_path.write_bytes(b"no snapshot")
+
+    with pytest.raises(ValueError) as exception_info:
+        LanguageDetector.from_snapshot(snapshot_file_path)
+    assert exception_info.value.args[0] == "file is not a Lingua snapshot"
+
+
 def test_detect_multiple_languages_for_empty_string():
     assert (
         detector_for_english_and_german
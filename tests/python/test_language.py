@@ -13,7 +13,9 @@
 # See the License for the specific language governing permissions and
 # limitations under the License.
 
-from lingua import IsoCode639_1, IsoCode639_3, Language
+import pytest
+
+from lingua import Alphabet, IsoCode639_1, IsoCode639_3, Language
 
 
 def test_iso_code_639_1_name():
@@ -301,3 +303,24 @@ def test_language_from_iso_code_639_1():
 
 def test_language_from_iso_code_639_3():
     assert Language.from_iso_code_639_3(IsoCode639_3.DEU) == Language.GERMAN
+
+
+def test_custom_language_can_be_registered(tmp_path):
+    language = Language.register_custom(
+        "Galician", "gl", "glg", [Alphabet.LATIN], tmp_path
+    )
+    assert language == Language.GALICIAN
+    assert language.name == "GALICIAN"
+    assert language.iso_code_639_1 == IsoCode639_1.GL
+    assert language.iso_code_639_3 == IsoCode639_3.GLG
+    assert Language.from_iso_code_639_1(IsoCode639_1.GL) == language
+    assert language not in Language.all()
+
+
+def test_custom_language_cannot_replace_built_in_language(tmp_path):
+    with pytest.raises(ValueError) as exception_info:
+        Language.register_custom("German", "xy", "xyz", [Alphabet.LATIN], tmp_path)
+    assert (
+        exception_info.value.args[0]
+        == "The name and ISO codes of a custom language must not be used by another language"
+    )
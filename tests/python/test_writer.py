@@ -70,6 +70,101 @@ def test_language_model_files_writer(language_model_files_text):
     assert files[1] == "fivegrams.json.br"
 
 
+def test_configured_language_model_files_writer(language_model_files_text):
+    input_file = create_temp_input_file(language_model_files_text)
+    input_file_path = Path(input_file.name)
+
+    output_directory = TemporaryDirectory()
+    output_directory_path = Path(output_directory.name)
+
+    writer = (
+        LanguageModelFilesWriter()
+        .with_word_boundary_ngrams()
+        .with_maximum_ngram_length(2)
+        .with_ngram_counts()
+        .with_word_model()
+    )
+    writer.write_language_model_files(
+        input_file_path=input_file_path,
+        output_directory_path=output_directory_path,
+        language=Language.ENGLISH,
+        char_class="\\p{L}",
+    )
+
+    assert read_directory_content(output_directory_path) == [
+        "word-boundary-bigram-counts.json.br",
+        "word-boundary-bigrams.json.br",
+        "word-boundary-unigram-counts.json.br",
+        "word-boundary-unigrams.json.br",
+        "words.json.br",
+    ]
+
+
+def test_invalid_maximum_ngram_length_raises_exception():
+    with pytest.raises(ValueError) as exception_info:
+        LanguageModelFilesWriter().with_maximum_ngram_length(9)
+    assert (
+        exception_info.value.args[0]
+        == "Maximum ngram length must lie in between 1 and 8"
+    )
+
+
+def test_language_variant_model_files_writer(language_model_files_text):
+    input_file = create_temp_input_file(language_model_files_text)
+    input_file_path = Path(input_file.name)
+
+    output_directory = TemporaryDirectory()
+    output_directory_path = Path(output_directory.name)
+
+    LanguageModelFilesWriter().with_maximum_ngram_length(
+        1
+    ).write_language_variant_model_files(
+        input_file_path=input_file_path,
+        output_directory_path=output_directory_path,
+        variant="en-GB",
+        char_class="\\p{L}",
+    )
+
+    assert read_directory_content(output_directory_path) == ["en-GB"]
+
+    files = read_directory_content(output_directory_path / "en-GB")
+
+    assert files == ["unigrams.json.br", "words.json.br"]
+
+
+def test_invalid_language_variant_raises_exception():
+    with pytest.raises(ValueError) as exception_info:
+        LanguageModelFilesWriter().write_language_variant_model_files(
+            input_file_path=Path("/some/input.txt"),
+            output_directory_path=Path("/some/output"),
+            variant="en-GB1",
+            char_class="\\p{L}",
+        )
+    assert (
+        exception_info.value.args[0]
+        == "'en-GB1' is not a valid language variant tag such as pt-BR"
+    )
+
+
+def test_pair_discriminator_file_writer():
+    bokmal_input_file = create_temp_input_file("Jeg vet ikke.\nHva gjør du nå?")
+    nynorsk_input_file = create_temp_input_file("Eg veit ikkje.\nKva gjer du no?")
+
+    output_directory = TemporaryDirectory()
+    output_directory_path = Path(output_directory.name)
+
+    LanguageModelFilesWriter().write_pair_discriminator_file(
+        first_input_file_path=Path(nynorsk_input_file.name),
+        second_input_file_path=Path(bokmal_input_file.name),
+        output_directory_path=output_directory_path,
+        first_language=Language.NYNORSK,
+        second_language=Language.BOKMAL,
+        char_class="\\p{L}",
+    )
+
+    assert read_directory_content(output_directory_path) == ["nb-nn.json.br"]
+
+
 def test_test_data_files_writer(test_data_files_text):
     input_file = create_temp_input_file(test_data_files_text)
     input_file_path = Path(input_file.name)
@@ -17,8 +17,8 @@
 #![cfg(target_family = "wasm")]
 
 use lingua::{
-    ConfidenceValue, IsoCode639_1, IsoCode639_3, Language, WasmDetectionResult,
-    WasmLanguageDetectorBuilder,
+    Alphabet, ConfidenceValue, IsoCode639_1, IsoCode639_3, Language, WasmDetectionOutcome,
+    WasmDetectionResult, WasmLanguageDetectorBuilder,
 };
 use wasm_bindgen::JsValue;
 use wasm_bindgen_test::*;
@@ -249,6 +249,190 @@ fn assert_detector_cannot_be_built_from_too_large_minimum_relative_distance() {
     );
 }
 
+#[wasm_bindgen_test]
+fn assert_detector_can_be_built_with_ngram_lengths() {
+    let mut builder = WasmLanguageDetectorBuilder::fromAllLanguages();
+    let result = builder.withNgramLengths(1, 6, 3, 7);
+    assert!(result.is_ok());
+}
+
+#[wasm_bindgen_test]
+fn assert_detector_cannot_be_built_with_too_large_ngram_lengths() {
+    let mut builder = WasmLanguageDetectorBuilder::fromAllLanguages();
+    let result = builder.withNgramLengths(1, 9, 3, 3);
+    assert_eq!(
+        result.err(),
+        Some(JsValue::from(
+            "Ngram lengths must be non-empty ranges lying in between 1 and 8"
+        ))
+    );
+}
+
+#[wasm_bindgen_test]
+fn assert_detector_cannot_be_built_without_word_boundary_models() {
+    let result = WasmLanguageDetectorBuilder::fromLanguages(Box::new([
+        JsValue::from(Language::German.to_string()),
+        JsValue::from(Language::English.to_string()),
+    ]))
+    .unwrap()
+    .withWordBoundaryNgrams()
+    .build();
+    assert_eq!(
+        result.err(),
+        Some(JsValue::from(
+            "Word boundary ngrams cannot be used as the word boundary models are missing for English, German. \
+             These models are not bundled with the built-in languages, so they can only be used for custom \
+             languages and with the models directory of the crate feature `external-models`."
+        ))
+    );
+}
+
+#[wasm_bindgen_test]
+fn assert_detector_can_be_built_with_smoothing() {
+    let mut builder = WasmLanguageDetectorBuilder::fromAllLanguages();
+    assert!(builder.withAddKSmoothing(0.5).is_ok());
+    assert!(builder.withKneserNeySmoothing(0.75).is_ok());
+}
+
+#[wasm_bindgen_test]
+fn assert_detector_cannot_be_built_without_ngram_counts() {
+    let result = WasmLanguageDetectorBuilder::fromLanguages(Box::new([
+        JsValue::from(Language::German.to_string()),
+        JsValue::from(Language::English.to_string()),
+    ]))
+    .unwrap()
+    .withWittenBellSmoothing()
+    .build();
+    assert_eq!(
+        result.err(),
+        Some(JsValue::from(
+            "Smoothing cannot be applied as the ngram counts are missing for English, German. \
+             These models are not bundled with the built-in languages, so they can only be used for custom \
+             languages and with the models directory of the crate feature `external-models`."
+        ))
+    );
+}
+
+#[wasm_bindgen_test]
+fn assert_detector_cannot_be_built_with_invalid_kneser_ney_discount() {
+    let mut builder = WasmLanguageDetectorBuilder::fromAllLanguages();
+    let result = builder.withKneserNeySmoothing(1.5);
+    assert_eq!(
+        result.err(),
+        Some(JsValue::from(
+            "The discount of Kneser-Ney smoothing must lie in between 0.0 and 1.0"
+        ))
+    );
+}
+
+#[wasm_bindgen_test]
+fn assert_detector_can_be_built_with_word_model() {
+    let mut builder = WasmLanguageDetectorBuilder::fromAllLanguages();
+    let result = builder.withWordModel(0.4);
+    assert!(result.is_ok());
+}
+
+#[wasm_bindgen_test]
+fn assert_detector_cannot_be_built_without_word_models() {
+    let result = WasmLanguageDetectorBuilder::fromLanguages(Box::new([
+        JsValue::from(Language::German.to_string()),
+        JsValue::from(Language::English.to_string()),
+    ]))
+    .unwrap()
+    .withWordModel(0.4)
+    .unwrap()
+    .build();
+    assert_eq!(
+        result.err(),
+        Some(JsValue::from(
+            "Word models cannot be used as they are missing for English, German. \
+             These models are not bundled with the built-in languages, so they can only be used for custom \
+             languages and with the models directory of the crate feature `external-models`."
+        ))
+    );
+}
+
+#[wasm_bindgen_test]
+fn assert_detector_cannot_be_built_with_too_large_word_model_weight() {
+    let mut builder = WasmLanguageDetectorBuilder::fromAllLanguages();
+    let result = builder.withWordModel(1.5);
+    assert_eq!(
+        result.err(),
+        Some(JsValue::from(
+            "Word model weight must be greater than 0.0 and at most 1.0"
+        ))
+    );
+}
+
+#[wasm_bindgen_test]
+fn assert_detector_can_be_built_with_normalizers() {
+    let mut builder = WasmLanguageDetectorBuilder::fromAllLanguages();
+    let result = builder.withNormalizers(Box::new([
+        JsValue::from("Nfkc"),
+        JsValue::from("SquashElongation"),
+    ]));
+    assert!(result.is_ok());
+}
+
+#[wasm_bindgen_test]
+fn assert_detector_cannot_be_built_with_unknown_normalizer() {
+    let mut builder = WasmLanguageDetectorBuilder::fromAllLanguages();
+    let result = builder.withNormalizers(Box::new([JsValue::from("Lowercase")]));
+    assert_eq!(
+        result.err(),
+        Some(JsValue::from("Normalizer 'Lowercase' is not supported"))
+    );
+}
+
+#[wasm_bindgen_test]
+fn assert_detector_can_be_built_with_macro_languages() {
+    let mut builder = WasmLanguageDetectorBuilder::fromAllLanguages();
+    let result = builder.withMacroLanguages(Box::new([
+        JsValue::from("Norwegian"),
+        JsValue::from("SerboCroatian"),
+    ]));
+    assert!(result.is_ok());
+}
+
+#[wasm_bindgen_test]
+fn assert_detector_cannot_be_built_with_unknown_macro_language() {
+    let mut builder = WasmLanguageDetectorBuilder::fromAllLanguages();
+    let result = builder.withMacroLanguages(Box::new([JsValue::from("Chinese")]));
+    assert_eq!(
+        result.err(),
+        Some(JsValue::from("Macro language 'Chinese' is not supported"))
+    );
+}
+
+#[wasm_bindgen_test]
+fn assert_detector_cannot_be_built_without_macro_languages() {
+    let mut builder = WasmLanguageDetectorBuilder::fromAllLanguages();
+    let result = builder.withMacroLanguages(Box::new([]));
+    assert_eq!(
+        result.err(),
+        Some(JsValue::from(
+            "At least one macro language must be specified"
+        ))
+    );
+}
+
+#[wasm_bindgen_test]
+fn assert_detector_can_be_built_with_memory_budget() {
+    let mut builder = WasmLanguageDetectorBuilder::fromAllLanguages();
+    let result = builder.withMemoryBudget(50_000_000);
+    assert!(result.is_ok());
+}
+
+#[wasm_bindgen_test]
+fn assert_detector_cannot_be_built_with_zero_memory_budget() {
+    let mut builder = WasmLanguageDetectorBuilder::fromAllLanguages();
+    let result = builder.withMemoryBudget(0);
+    assert_eq!(
+        result.err(),
+        Some(JsValue::from("Memory budget must be greater than 0 bytes"))
+    );
+}
+
 #[wasm_bindgen_test]
 fn test_detect_language() {
     let detector = WasmLanguageDetectorBuilder::fromLanguages(Box::new([
@@ -257,7 +441,8 @@ fn test_detect_language() {
         JsValue::from(Language::French.to_string()),
     ]))
     .unwrap()
-    .build();
+    .build()
+    .unwrap();
 
     let mut language = detector.detectLanguageOf("mein Haus ist groß");
     assert_eq!(language, Some(Language::German.to_string()));
@@ -269,6 +454,94 @@ fn test_detect_language() {
     assert_eq!(language, Some(Language::French.to_string()));
 }
 
+#[wasm_bindgen_test]
+fn test_detect_unsupported_alphabet() {
+    let detector = WasmLanguageDetectorBuilder::fromLanguages(Box::new([
+        JsValue::from(Language::German.to_string()),
+        JsValue::from(Language::English.to_string()),
+    ]))
+    .unwrap()
+    .build()
+    .unwrap();
+
+    let mut alphabet = detector.detectUnsupportedAlphabetOf("ಕನ್ನಡ ಭಾಷೆ");
+    assert_eq!(alphabet, Some(Alphabet::Kannada.to_string()));
+
+    alphabet = detector.detectUnsupportedAlphabetOf("my house is big");
+    assert_eq!(alphabet, None);
+}
+
+#[wasm_bindgen_test]
+fn test_detect_language_outcome() {
+    let detector = WasmLanguageDetectorBuilder::fromLanguages(Box::new([
+        JsValue::from(Language::German.to_string()),
+        JsValue::from(Language::English.to_string()),
+    ]))
+    .unwrap()
+    .build()
+    .unwrap();
+
+    let mut outcome: WasmDetectionOutcome =
+        serde_wasm_bindgen::from_value(detector.detectLanguageOutcomeOf("mein Haus ist groß"))
+            .unwrap();
+    assert_eq!(
+        outcome,
+        WasmDetectionOutcome {
+            language: Some(Language::German.to_string()),
+            unsupportedAlphabet: None
+        }
+    );
+
+    outcome = serde_wasm_bindgen::from_value(detector.detectLanguageOutcomeOf("ಕನ್ನಡ ಭಾಷೆ")).unwrap();
+    assert_eq!(
+        outcome,
+        WasmDetectionOutcome {
+            language: None,
+            unsupportedAlphabet: Some(Alphabet::Kannada.to_string())
+        }
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_detect_chinese_script() {
+    let detector = WasmLanguageDetectorBuilder::fromLanguages(Box::new([
+        JsValue::from(Language::Chinese.to_string()),
+        JsValue::from(Language::English.to_string()),
+    ]))
+    .unwrap()
+    .build()
+    .unwrap();
+
+    let mut script = detector.detectChineseScriptOf("北京大学的图书馆很大");
+    assert_eq!(script, Some("zh-Hans".to_string()));
+
+    script = detector.detectChineseScriptOf("臺灣大學的圖書館很大");
+    assert_eq!(script, Some("zh-Hant".to_string()));
+
+    script = detector.detectChineseScriptOf("my house is big");
+    assert_eq!(script, None);
+}
+
+#[wasm_bindgen_test]
+fn test_detect_collapsed_language() {
+    let detector = WasmLanguageDetectorBuilder::fromLanguages(Box::new([
+        JsValue::from(Language::Bokmal.to_string()),
+        JsValue::from(Language::English.to_string()),
+        JsValue::from(Language::Nynorsk.to_string()),
+    ]))
+    .unwrap()
+    .withMacroLanguages(Box::new([JsValue::from("Norwegian")]))
+    .unwrap()
+    .build()
+    .unwrap();
+
+    let mut language = detector.detectCollapsedLanguageOf("Jeg vet ikke hva du mener");
+    assert_eq!(language, Some("Norwegian".to_string()));
+
+    language = detector.detectCollapsedLanguageOf("languages are awesome");
+    assert_eq!(language, Some(Language::English.to_string()));
+}
+
 #[wasm_bindgen_test]
 fn test_detect_multiple_languages() {
     let detector = WasmLanguageDetectorBuilder::fromLanguages(Box::new([
@@ -277,7 +550,8 @@ fn test_detect_multiple_languages() {
         JsValue::from(Language::French.to_string()),
     ]))
     .unwrap()
-    .build();
+    .build()
+    .unwrap();
 
     let sentence = "Parlez-vous français? Ich spreche Französisch nur ein bisschen. A little bit is better than nothing.";
     let sentence_chars = sentence.chars().collect::<Vec<_>>();
@@ -320,7 +594,8 @@ fn test_compute_language_confidence_values() {
         JsValue::from(Language::French.to_string()),
     ]))
     .unwrap()
-    .build();
+    .build()
+    .unwrap();
 
     let confidence_values: Vec<ConfidenceValue> = serde_wasm_bindgen::from_value(
         detector.computeLanguageConfidenceValues("mein Haus ist groß"),
@@ -362,7 +637,8 @@ fn test_compute_language_confidence() {
         JsValue::from(Language::French.to_string()),
     ]))
     .unwrap()
-    .build();
+    .build()
+    .unwrap();
 
     let confidence = detector
         .computeLanguageConfidence("mein Haus ist groß", &Language::German.to_string())